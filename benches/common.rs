@@ -1,33 +1,90 @@
 #![feature(test)]
 extern crate test;
 
-use algo::common::{QuickUnionUF, WeightedQuickUnionUF, UF};
-use std::str::FromStr;
+use algo::common::{QuickFindUF, QuickUnionUF, UnionFind, WeightedQuickUnionUF, PQ, UF};
 use test::Bencher;
 
 // const TINY_UF: &'static str = include_str!("../res/common/tinyUF.txt");
 const MEDIUM_UF: &'static str = include_str!("../res/common/mediumUF.txt");
 
-#[bench]
-fn weighted_quf(b: &mut Bencher) {
+fn uf_pairs() -> (usize, Vec<(usize, usize)>) {
+    let mut lines = MEDIUM_UF.lines();
+    let n = lines.next().unwrap().trim().parse().unwrap();
+    let pairs = lines
+        .filter_map(|l| {
+            let mut it = l.split_whitespace().map(|v| v.parse::<usize>().unwrap());
+            Some((it.next()?, it.next()?))
+        })
+        .collect();
+    (n, pairs)
+}
+
+fn unions<U: UnionFind>(b: &mut Bencher) {
+    let (n, pairs) = uf_pairs();
     b.iter(|| {
-        let i = MEDIUM_UF;
-        let _uf = WeightedQuickUnionUF::from_str(i).unwrap();
+        let mut uf = U::new(n);
+        for &(p, q) in pairs.iter() {
+            if !uf.connected(p, q) {
+                uf.union(p, q);
+            }
+        }
+        uf.count()
     });
 }
 
+#[bench]
+fn weighted_quf(b: &mut Bencher) {
+    unions::<WeightedQuickUnionUF>(b);
+}
+
 #[bench]
 fn quf(b: &mut Bencher) {
-    b.iter(|| {
-        let i = MEDIUM_UF;
-        let _uf = QuickUnionUF::from_str(i).unwrap();
-    });
+    unions::<QuickUnionUF>(b);
+}
+
+#[bench]
+fn qf(b: &mut Bencher) {
+    unions::<QuickFindUF>(b);
 }
 
 #[bench]
 fn uf(b: &mut Bencher) {
+    unions::<UF>(b);
+}
+
+fn pq_keys() -> Vec<u32> {
+    let mut seed = 1u32;
+    (0..10_000)
+        .map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 8
+        })
+        .collect()
+}
+
+fn pq_enqueue_dequeue(b: &mut Bencher, d: usize) {
+    let keys = pq_keys();
     b.iter(|| {
-        let i = MEDIUM_UF;
-        let _uf = UF::from_str(i).unwrap();
+        let mut pq = PQ::new_min_pq(keys.len()).with_arity(d);
+        for &k in keys.iter() {
+            pq.enqueue(k);
+        }
+        while pq.dequeue().is_some() {}
     });
 }
+
+#[bench]
+fn pq_binary(b: &mut Bencher) {
+    pq_enqueue_dequeue(b, 2);
+}
+
+#[bench]
+fn pq_4ary(b: &mut Bencher) {
+    pq_enqueue_dequeue(b, 4);
+}
+
+#[bench]
+fn pq_from_vec(b: &mut Bencher) {
+    let keys = pq_keys();
+    b.iter(|| PQ::from_vec(keys.clone()));
+}
@@ -0,0 +1,108 @@
+#![feature(test)]
+extern crate test;
+
+use algo::common::{IndexFibonacciHeap, IndexMinPQ, IndexPQ, IndexPairingHeap, UnionFind, UF};
+use algo::graph::mst::{EWGraph, PrimMST};
+use algo::graph::shortest::{DijkstraSP, EWDigraph};
+use algo::graph::undirected::ParallelCC;
+use algo::graph::{IEWDigraph, IEWGraph};
+use rand::{Rng, SeedableRng};
+use test::Bencher;
+
+// dense graph, every vertex has an edge to every other vertex
+const V: usize = 300;
+
+fn weights() -> Vec<(usize, usize, f32)> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let mut edges = Vec::with_capacity(V * (V - 1));
+    for v in 0..V {
+        for w in (0..V).filter(|&w| w != v) {
+            edges.push((v, w, rng.gen()));
+        }
+    }
+    edges
+}
+
+fn dijkstra<P: IndexMinPQ<f32>>(b: &mut Bencher) {
+    let mut g = EWDigraph::from(V);
+    for (v, w, weight) in weights() {
+        g.add_edge(v, w, weight);
+    }
+    b.iter(|| DijkstraSP::with_pq::<P>(&g, 0));
+}
+
+fn prim<P: IndexMinPQ<f32>>(b: &mut Bencher) {
+    let mut g = EWGraph::from(V);
+    for (v, w, weight) in weights().into_iter().filter(|&(v, w, _)| v < w) {
+        g.add_edge(v, w, weight);
+    }
+    b.iter(|| PrimMST::with_pq::<P>(&g));
+}
+
+#[bench]
+fn dijkstra_index_pq(b: &mut Bencher) {
+    dijkstra::<IndexPQ<f32>>(b);
+}
+
+#[bench]
+fn dijkstra_pairing_heap(b: &mut Bencher) {
+    dijkstra::<IndexPairingHeap<f32>>(b);
+}
+
+#[bench]
+fn dijkstra_fibonacci_heap(b: &mut Bencher) {
+    dijkstra::<IndexFibonacciHeap<f32>>(b);
+}
+
+#[bench]
+fn prim_index_pq(b: &mut Bencher) {
+    prim::<IndexPQ<f32>>(b);
+}
+
+#[bench]
+fn prim_pairing_heap(b: &mut Bencher) {
+    prim::<IndexPairingHeap<f32>>(b);
+}
+
+#[bench]
+fn prim_fibonacci_heap(b: &mut Bencher) {
+    prim::<IndexFibonacciHeap<f32>>(b);
+}
+
+// sparse graph of random edges, for the connected components
+fn random_edges(n: usize, m: usize) -> Vec<(usize, usize)> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    (0..m)
+        .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+        .collect()
+}
+
+const CC_V: usize = 100_000;
+const CC_E: usize = 400_000;
+
+#[bench]
+fn cc_uf(b: &mut Bencher) {
+    let edges = random_edges(CC_V, CC_E);
+    b.iter(|| {
+        let mut uf = UF::new(CC_V);
+        for &(v, w) in edges.iter() {
+            uf.union(v, w);
+        }
+        uf.count()
+    });
+}
+
+fn parallel_cc(b: &mut Bencher, threads: usize) {
+    let edges = random_edges(CC_V, CC_E);
+    b.iter(|| ParallelCC::new(CC_V, &edges, threads).count());
+}
+
+#[bench]
+fn cc_parallel_1(b: &mut Bencher) {
+    parallel_cc(b, 1);
+}
+
+#[bench]
+fn cc_parallel_4(b: &mut Bencher) {
+    parallel_cc(b, 4);
+}
@@ -0,0 +1,201 @@
+#![feature(test)]
+extern crate test;
+
+use algo::common::util;
+use algo::tree::binary::Tree;
+use test::Bencher;
+
+static DATA_LEN: usize = 1000;
+
+#[bench]
+fn avl_get(b: &mut Bencher) {
+    use algo::tree::binary::avl::AvlTree;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = Tree::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = tree.get(v);
+        }
+    });
+}
+
+#[bench]
+fn rb2_get(b: &mut Bencher) {
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = Tree::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = tree.get(v);
+        }
+    });
+}
+
+#[bench]
+fn separate_chaining_hash_st_get(b: &mut Bencher) {
+    use algo::search::SeparateChainingHashST;
+    let data = gen_random_data(DATA_LEN);
+    let mut st = SeparateChainingHashST::default();
+    for v in &data {
+        st.put(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = st.get(v);
+        }
+    });
+}
+
+#[bench]
+fn linear_probing_hash_st_get(b: &mut Bencher) {
+    use algo::search::LinearProbingHashST;
+    let data = gen_random_data(DATA_LEN);
+    let mut st = LinearProbingHashST::default();
+    for v in &data {
+        st.put(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = st.get(v);
+        }
+    });
+}
+
+#[bench]
+fn avl_insert(b: &mut Bencher) {
+    use algo::tree::binary::avl::AvlTree;
+    let data = gen_random_data(DATA_LEN);
+    b.iter(|| {
+        let mut tree = Tree::default();
+        for v in &data {
+            tree.insert(*v, *v);
+        }
+    });
+}
+
+#[bench]
+fn rb2_insert(b: &mut Bencher) {
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let data = gen_random_data(DATA_LEN);
+    b.iter(|| {
+        let mut tree = Tree::default();
+        for v in &data {
+            tree.insert(*v, *v);
+        }
+    });
+}
+
+#[bench]
+fn arena_tree_get(b: &mut Bencher) {
+    use algo::tree::binary::arena_tree::ArenaTree;
+    use algo::tree::binary::bst::BSTree;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = ArenaTree::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = tree.get(v);
+        }
+    });
+}
+
+#[bench]
+fn arena_tree_insert(b: &mut Bencher) {
+    use algo::tree::binary::arena_tree::ArenaTree;
+    use algo::tree::binary::bst::BSTree;
+    let data = gen_random_data(DATA_LEN);
+    b.iter(|| {
+        let mut tree = ArenaTree::with_capacity(data.len());
+        for v in &data {
+            tree.insert(*v, *v);
+        }
+    });
+}
+
+#[bench]
+fn treap_get(b: &mut Bencher) {
+    use algo::tree::binary::bst::BSTree;
+    use algo::tree::binary::treap::Treap;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = Treap::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = tree.get(v);
+        }
+    });
+}
+
+#[bench]
+fn skip_list_get(b: &mut Bencher) {
+    use algo::search::SkipList;
+    use algo::tree::binary::bst::BSTree;
+    let data = gen_random_data(DATA_LEN);
+    let mut list = SkipList::default();
+    for v in &data {
+        list.insert(*v, *v);
+    }
+    b.iter(|| {
+        for v in &data {
+            let _ = list.get(v);
+        }
+    });
+}
+
+#[bench]
+fn splay_get_skewed(b: &mut Bencher) {
+    use algo::tree::binary::bst::BSTree;
+    use algo::tree::binary::splay::SplayTree;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = SplayTree::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    let access = gen_skewed_access(DATA_LEN);
+    b.iter(|| {
+        for v in &access {
+            let _ = tree.splay_get(v);
+        }
+    });
+}
+
+#[bench]
+fn rb2_get_skewed(b: &mut Bencher) {
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let data = gen_random_data(DATA_LEN);
+    let mut tree = Tree::default();
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    let access = gen_skewed_access(DATA_LEN);
+    b.iter(|| {
+        for v in &access {
+            let _ = tree.get(v);
+        }
+    });
+}
+
+// 9 of 10 accesses hit a working set of 10 keys
+fn gen_skewed_access(len: usize) -> Vec<i32> {
+    let mut data: Vec<i32> = (0..len as i32)
+        .map(|i| if i % 10 == 0 { i } else { i % 10 * 7 })
+        .collect();
+    util::shuffle(&mut data);
+    data
+}
+
+fn gen_random_data(len: usize) -> Vec<i32> {
+    let mut data: Vec<i32> = (0..len as i32).collect();
+    util::shuffle(&mut data);
+    data
+}
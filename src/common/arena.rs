@@ -0,0 +1,139 @@
+//! A slab of nodes addressed by index handles.
+//!
+//! The nodes of a linked structure are kept in one Vec instead of being
+//! allocated one by one, so
+//!   - all nodes are freed at once, dropping the arena is a loop over
+//!     the Vec, no recursion whatever the shape of the structure
+//!   - the nodes are close to each other in memory
+//!   - a link is a 4 bytes handle, Option<Handle> is 4 bytes too
+//!
+//! The freed slots are chained into a free list and reused by alloc,
+//! so a handle must not be used after its node is freed.
+
+use std::convert::TryFrom;
+use std::num::NonZeroU32;
+use std::ops::{Index, IndexMut};
+
+/// Index of a node in the Arena
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Handle(NonZeroU32);
+
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    // first free slot
+    free: Option<Handle>,
+    len: usize,
+}
+
+enum Slot<T> {
+    Occupied(T),
+    // next free slot
+    Vacant(Option<Handle>),
+}
+
+impl Handle {
+    fn new(i: usize) -> Self {
+        let i = u32::try_from(i + 1).expect("arena is full");
+        Handle(NonZeroU32::new(i).unwrap())
+    }
+
+    fn index(self) -> usize {
+        self.0.get() as usize - 1
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of nodes in the arena
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of slots, occupied and free
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Puts the node into a free slot, returns its handle
+    pub fn alloc(&mut self, v: T) -> Handle {
+        self.len += 1;
+        match self.free {
+            Some(h) => {
+                let slot = std::mem::replace(&mut self.slots[h.index()], Slot::Occupied(v));
+                match slot {
+                    Slot::Vacant(next) => self.free = next,
+                    Slot::Occupied(_) => unreachable!("free list points to a node"),
+                }
+                h
+            }
+            None => {
+                self.slots.push(Slot::Occupied(v));
+                Handle::new(self.slots.len() - 1)
+            }
+        }
+    }
+
+    /// Removes the node from the arena, returns it
+    pub fn free(&mut self, h: Handle) -> T {
+        assert!(self.get(h).is_some(), "free a free slot {:?}", h);
+        let slot = std::mem::replace(&mut self.slots[h.index()], Slot::Vacant(self.free));
+        self.free = Some(h);
+        self.len -= 1;
+        match slot {
+            Slot::Occupied(v) => v,
+            Slot::Vacant(_) => unreachable!(),
+        }
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        match self.slots.get(h.index()) {
+            Some(Slot::Occupied(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, h: Handle) -> Option<&mut T> {
+        match self.slots.get_mut(h.index()) {
+            Some(Slot::Occupied(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Drops all nodes, keeps the allocated memory
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.free = None;
+        self.len = 0;
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<T> Index<Handle> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, h: Handle) -> &T {
+        self.get(h).expect("invalid handle")
+    }
+}
+
+impl<T> IndexMut<Handle> for Arena<T> {
+    fn index_mut(&mut self, h: Handle) -> &mut T {
+        self.get_mut(h).expect("invalid handle")
+    }
+}
@@ -0,0 +1,155 @@
+//! Ordering strategies for the keys of the ordered symbol tables
+//!
+//! The trees (Tree, RedBlackTreeV2, Treap, SkipList...) order the keys
+//! by K: Ord. To store keys with another ordering, wrap them in By,
+//! which orders the keys by a strategy type implementing Compare:
+//!
+//!   let mut tree: Tree<By<String, CaseInsensitive>, i32> = Tree::default();
+//!   tree.insert(By::new("Apple".to_string()), 1);
+//!   assert!(tree.get(By::from_ref(&"APPLE".to_string())).is_some());
+//!
+//! The strategy is a zero-sized type, so By<K, C> has the same size and
+//! layout as K, and the trees need no extra field or extra argument.
+//!
+//! Strategies:
+//!   - Natural: K: Ord
+//!   - Reverse: the reverse of K: Ord
+//!   - CaseInsensitive: strings, ignoring case
+//!   - TotalOrder: f32 / f64 by the IEEE 754 totalOrder predicate,
+//!     -NaN < -inf < ... < -0 < +0 < ... < +inf < +NaN
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// An ordering strategy of K, it must be a total order
+pub trait Compare<K: ?Sized> {
+    fn compare(a: &K, b: &K) -> Ordering;
+}
+
+/// The key, ordered by the strategy C
+#[repr(transparent)]
+pub struct By<K, C> {
+    key: K,
+    _c: PhantomData<fn() -> C>,
+}
+
+pub struct Natural;
+pub struct Reverse;
+pub struct CaseInsensitive;
+pub struct TotalOrder;
+
+impl<K: Ord + ?Sized> Compare<K> for Natural {
+    fn compare(a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<K: Ord + ?Sized> Compare<K> for Reverse {
+    fn compare(a: &K, b: &K) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<K: AsRef<str> + ?Sized> Compare<K> for CaseInsensitive {
+    fn compare(a: &K, b: &K) -> Ordering {
+        let a = a.as_ref().chars().flat_map(char::to_lowercase);
+        let b = b.as_ref().chars().flat_map(char::to_lowercase);
+        a.cmp(b)
+    }
+}
+
+impl Compare<f64> for TotalOrder {
+    fn compare(a: &f64, b: &f64) -> Ordering {
+        a.total_cmp(b)
+    }
+}
+
+impl Compare<f32> for TotalOrder {
+    fn compare(a: &f32, b: &f32) -> Ordering {
+        a.total_cmp(b)
+    }
+}
+
+impl<K, C> By<K, C> {
+    pub fn new(key: K) -> Self {
+        Self {
+            key,
+            _c: PhantomData,
+        }
+    }
+
+    /// Views a key as a By key, to search for it without moving it
+    pub fn from_ref(key: &K) -> &Self {
+        // By is repr(transparent) over K
+        unsafe { &*(key as *const K as *const Self) }
+    }
+
+    pub fn into_inner(self) -> K {
+        self.key
+    }
+}
+
+impl<K, C: Compare<K>> Ord for By<K, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        C::compare(&self.key, &other.key)
+    }
+}
+
+impl<K, C: Compare<K>> PartialOrd for By<K, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, C: Compare<K>> PartialEq for By<K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K, C: Compare<K>> Eq for By<K, C> {}
+
+impl<K, C> Deref for By<K, C> {
+    type Target = K;
+
+    fn deref(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K, C> From<K> for By<K, C> {
+    fn from(key: K) -> Self {
+        Self::new(key)
+    }
+}
+
+impl<K: Clone, C> Clone for By<K, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.key.clone())
+    }
+}
+
+impl<K: Copy, C> Copy for By<K, C> {}
+
+impl<K: fmt::Debug, C> fmt::Debug for By<K, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+impl<K: fmt::Display, C> fmt::Display for By<K, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+impl<K: FromStr, C> FromStr for By<K, C> {
+    type Err = K::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::new)
+    }
+}
@@ -0,0 +1,206 @@
+//! A lock-free union-find, shared by threads (Send + Sync)
+//!
+//! Every element is an AtomicU64 holding its parent (low 32 bits) and
+//! its rank (high 32 bits), so the parent and the rank change together
+//! with one CAS (compare and swap).
+//!
+//!   - find: path halving, every step tries to CAS the parent of p to
+//!     its grandparent. A failed CAS means another thread has changed
+//!     it already, the step is just skipped, the parent pointers only
+//!     move up, so find is never wrong.
+//!   - union: find the two roots, link the one of the smaller rank
+//!     (by index if the ranks are equal) under the other by a CAS that
+//!     succeeds only if it is still a root of the same rank, otherwise
+//!     retry from the finds. Then increase the rank of the new root by
+//!     another CAS if the ranks were equal, a failure means the new root
+//!     is not a root anymore and its rank does not matter.
+//!   - connected: equal roots are connected, but different roots may be
+//!     linked by another thread in between, so retry while the root of
+//!     p is not a root anymore.
+//!
+//! Some thread always makes progress (lock-free), no thread waits for
+//! a lock held by a thread descheduled.
+//!
+//! The inherent methods take &self, so the threads can share it. It is
+//! also a UnionFind (&mut self) on top of them, for the clients generic
+//! over the strategies. The sizes of the sets are not kept by union, a
+//! size moved from a root that another thread is still linking a tree
+//! under would be lost. component_size, with no thread running, counts
+//! them again after the unions.
+//!
+//! Anderson, Woll, Wait-free parallel algorithms for the union-find
+//! problem, 1991
+
+use crate::common::UnionFind;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+const PARENT: u64 = 0xFFFF_FFFF;
+
+pub struct ConcurrentUF {
+    // data[i] = rank << 32 | parent
+    data: Vec<AtomicU64>,
+    count: AtomicUsize,
+    // size[root] = number of elements in the set, valid if not dirty
+    size: Vec<usize>,
+    // a union is made since size was counted
+    dirty: AtomicBool,
+}
+
+impl ConcurrentUF {
+    /// Initializes n elements 0 through n-1, each in its own set
+    ///
+    /// # Panics
+    ///
+    /// if n does not fit in 32 bits
+    pub fn new(n: usize) -> Self {
+        assert!(n as u64 <= PARENT, "too many elements: {}", n);
+        Self {
+            data: (0..n as u64).map(AtomicU64::new).collect(),
+            count: AtomicUsize::new(n),
+            size: vec![1; n],
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the canonical element of the set containing element p
+    pub fn find(&self, p: usize) -> usize {
+        let mut p = p;
+        loop {
+            let value = self.data[p].load(Ordering::Acquire);
+            let up = parent(value);
+            if up == p {
+                return p;
+            }
+            // path compression by halving
+            let grandparent = parent(self.data[up].load(Ordering::Acquire));
+            if grandparent != up {
+                let halved = (value & !PARENT) | grandparent as u64;
+                let _ = self.data[p].compare_exchange_weak(
+                    value,
+                    halved,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                );
+            }
+            p = grandparent;
+        }
+    }
+
+    /// Merges the set containing element p with the set containing
+    /// element q, returns false if they are already in the same set
+    pub fn union(&self, p: usize, q: usize) -> bool {
+        let (mut p, mut q) = (p, q);
+        loop {
+            p = self.find(p);
+            q = self.find(q);
+            if p == q {
+                return false;
+            }
+
+            let (mut rank_p, mut rank_q) = (self.rank(p), self.rank(q));
+            // p becomes the child
+            if rank_p > rank_q || (rank_p == rank_q && p > q) {
+                std::mem::swap(&mut p, &mut q);
+                std::mem::swap(&mut rank_p, &mut rank_q);
+            }
+
+            let old = rank_p << 32 | p as u64;
+            let new = rank_p << 32 | q as u64;
+            if self.data[p]
+                .compare_exchange(old, new, Ordering::AcqRel, Ordering::Relaxed)
+                .is_err()
+            {
+                // p is not a root of rank_p anymore
+                continue;
+            }
+
+            if rank_p == rank_q {
+                let old = rank_q << 32 | q as u64;
+                let new = (rank_q + 1) << 32 | q as u64;
+                let _ =
+                    self.data[q].compare_exchange(old, new, Ordering::AcqRel, Ordering::Relaxed);
+            }
+            self.count.fetch_sub(1, Ordering::AcqRel);
+            self.dirty.store(true, Ordering::Release);
+            return true;
+        }
+    }
+
+    /// Returns true if the two elements are in the same set
+    pub fn connected(&self, p: usize, q: usize) -> bool {
+        let (mut p, mut q) = (p, q);
+        loop {
+            p = self.find(p);
+            q = self.find(q);
+            if p == q {
+                return true;
+            }
+            // p is still a root, p and q were not connected at that time
+            if self.is_root(p) {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the number of sets, exact when no union is running
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    fn rank(&self, p: usize) -> u64 {
+        self.data[p].load(Ordering::Acquire) >> 32
+    }
+
+    fn is_root(&self, p: usize) -> bool {
+        parent(self.data[p].load(Ordering::Acquire)) == p
+    }
+}
+
+impl UnionFind for ConcurrentUF {
+    fn new(n: usize) -> Self {
+        ConcurrentUF::new(n)
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        ConcurrentUF::find(self, p)
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        ConcurrentUF::union(self, p, q);
+    }
+
+    fn connected(&mut self, p: usize, q: usize) -> bool {
+        ConcurrentUF::connected(self, p, q)
+    }
+
+    fn count(&self) -> usize {
+        ConcurrentUF::count(self)
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        if *self.dirty.get_mut() {
+            self.size.iter_mut().for_each(|s| *s = 0);
+            for v in 0..self.data.len() {
+                let root = ConcurrentUF::find(self, v);
+                self.size[root] += 1;
+            }
+            *self.dirty.get_mut() = false;
+        }
+        let root = ConcurrentUF::find(self, p);
+        self.size[root]
+    }
+}
+
+fn parent(value: u64) -> usize {
+    (value & PARENT) as usize
+}
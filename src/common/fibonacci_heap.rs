@@ -0,0 +1,329 @@
+//! Fibonacci heap, a min heap as a forest of heap-ordered trees
+//!
+//! The roots are kept in a circular doubly linked list, and so are the
+//! children of every node. min points to the root with the smallest key.
+//!
+//!   - push, meld: splice into the root list, O(1)
+//!   - pop: move the children of min into the root list, then
+//!     consolidate: link the roots of the same degree (number of
+//!     children) until all roots have distinct degrees, amortized
+//!     O(log N)
+//!   - decrease_key: if the node is smaller than its parent, cut it
+//!     into the root list, then cut its parent too if the parent has
+//!     lost a child before (marked), and so on up (cascading cut),
+//!     amortized O(1)
+//!
+//! The cascading cuts keep a node of degree d having at least
+//! F(d + 2) descendants, so the degree is O(log N).
+//!
+//! Introduction to Algorithms, 3rd Edition, Chapter 19 Fibonacci Heaps
+
+use std::cmp::Ordering;
+use std::ptr::NonNull;
+
+pub struct FibonacciHeap<K, V> {
+    min: Link<K, V>,
+    len: usize,
+}
+
+/// The entry pushed into the heap, to decrease its key
+pub struct Handle<K, V>(NonNull<Node<K, V>>);
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    parent: Link<K, V>,
+    child: Link<K, V>,
+    left: NonNull<Node<K, V>>,
+    right: NonNull<Node<K, V>>,
+    degree: usize,
+    // has lost a child since it became a child
+    mark: bool,
+}
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+impl<K: PartialOrd, V> FibonacciHeap<K, V> {
+    pub fn new() -> Self {
+        Self { min: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the entry with the smallest key
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.min.map(|x| unsafe {
+            let x = &*x.as_ptr();
+            (&x.key, &x.val)
+        })
+    }
+
+    pub fn push(&mut self, key: K, val: V) -> Handle<K, V> {
+        let x = Box::new(Node {
+            key,
+            val,
+            parent: None,
+            child: None,
+            left: NonNull::dangling(),
+            right: NonNull::dangling(),
+            degree: 0,
+            mark: false,
+        });
+        let mut x = NonNull::from(Box::leak(x));
+        unsafe {
+            x.as_mut().left = x;
+            x.as_mut().right = x;
+        }
+        self.min = self.meld_list(Some(x));
+        self.len += 1;
+        Handle(x)
+    }
+
+    /// Removes the entry with the smallest key
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let z = self.min?;
+        unsafe {
+            // children of z become roots
+            let mut child = z.as_ref().child;
+            if let Some(c) = child {
+                let mut x = c;
+                loop {
+                    x.as_mut().parent = None;
+                    x = x.as_ref().right;
+                    if x == c {
+                        break;
+                    }
+                }
+            }
+
+            let next = z.as_ref().right;
+            remove(z);
+            self.min = if next == z { None } else { Some(next) };
+            self.min = concat(self.min, child.take());
+            self.consolidate();
+
+            let z = Box::from_raw(z.as_ptr());
+            self.len -= 1;
+            Some((z.key, z.val))
+        }
+    }
+
+    /// Decreases the key of the entry to key
+    ///
+    /// # Safety
+    ///
+    /// h is returned by push of this heap, or of a heap melded into
+    /// it, and is not popped yet
+    ///
+    /// # Panics
+    ///
+    /// if key is greater than the key of the entry
+    pub unsafe fn decrease_key(&mut self, h: Handle<K, V>, key: K) {
+        let mut x = h.0;
+        assert!(
+            key.partial_cmp(&x.as_ref().key) != Some(Ordering::Greater),
+            "key is greater than the key of the entry"
+        );
+        x.as_mut().key = key;
+        if let Some(p) = x.as_ref().parent {
+            if x.as_ref().key < p.as_ref().key {
+                self.cut(x, p);
+                self.cascading_cut(p);
+            }
+        }
+        if x.as_ref().key < self.min.unwrap().as_ref().key {
+            self.min = Some(x);
+        }
+    }
+
+    /// Moves all entries of other into self, the handles of other
+    /// are handles of self
+    pub fn meld(&mut self, mut other: Self) {
+        self.min = self.meld_list(other.min.take());
+        self.len += other.len;
+    }
+
+    /// Returns the key of the entry
+    ///
+    /// # Safety
+    ///
+    /// the same as decrease_key
+    pub unsafe fn key_of(&self, h: Handle<K, V>) -> &K {
+        &(*h.0.as_ptr()).key
+    }
+
+    /// splices the list of roots into the root list, returns the new min
+    fn meld_list(&mut self, list: Link<K, V>) -> Link<K, V> {
+        let min = concat(self.min, list);
+        match (self.min, list) {
+            (Some(a), Some(b)) if unsafe { b.as_ref().key < a.as_ref().key } => Some(b),
+            (Some(a), _) => Some(a),
+            _ => min,
+        }
+    }
+
+    /// links the roots of the same degree, then finds the new min
+    fn consolidate(&mut self) {
+        let start = match self.min {
+            None => return,
+            Some(x) => x,
+        };
+
+        // the roots, the root list changes while linking
+        let mut roots = Vec::new();
+        let mut x = start;
+        loop {
+            roots.push(x);
+            x = unsafe { x.as_ref().right };
+            if x == start {
+                break;
+            }
+        }
+
+        // a[d] is the root of degree d
+        let mut a: Vec<Link<K, V>> = Vec::new();
+        for mut x in roots {
+            let mut d = unsafe { x.as_ref().degree };
+            loop {
+                if a.len() <= d {
+                    a.resize(d + 1, None);
+                }
+                let mut y = match a[d].take() {
+                    None => break,
+                    Some(y) => y,
+                };
+                unsafe {
+                    if y.as_ref().key < x.as_ref().key {
+                        std::mem::swap(&mut x, &mut y);
+                    }
+                    link(y, x);
+                }
+                d += 1;
+            }
+            a[d] = Some(x);
+        }
+
+        // the roots left are linked by link, find the min of them
+        let mut min: Link<K, V> = None;
+        for x in a.into_iter().flatten() {
+            if min.is_none_or(|m| unsafe { x.as_ref().key < m.as_ref().key }) {
+                min = Some(x);
+            }
+        }
+        self.min = min;
+    }
+
+    /// cuts x off its parent p, into the root list
+    unsafe fn cut(&mut self, mut x: NonNull<Node<K, V>>, mut p: NonNull<Node<K, V>>) {
+        let right = x.as_ref().right;
+        if p.as_ref().child == Some(x) {
+            p.as_mut().child = if right == x { None } else { Some(right) };
+        }
+        remove(x);
+        p.as_mut().degree -= 1;
+        x.as_mut().parent = None;
+        x.as_mut().mark = false;
+        self.min = concat(self.min, Some(x));
+    }
+
+    unsafe fn cascading_cut(&mut self, mut y: NonNull<Node<K, V>>) {
+        while let Some(z) = y.as_ref().parent {
+            if !y.as_ref().mark {
+                y.as_mut().mark = true;
+                break;
+            }
+            self.cut(y, z);
+            y = z;
+        }
+    }
+}
+
+impl<K: PartialOrd, V> Default for FibonacciHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for FibonacciHeap<K, V> {
+    fn drop(&mut self) {
+        // the first nodes of the lists not freed yet
+        let mut lists: Vec<NonNull<Node<K, V>>> = self.min.take().into_iter().collect();
+        while let Some(first) = lists.pop() {
+            let mut x = first;
+            loop {
+                let node = unsafe { Box::from_raw(x.as_ptr()) };
+                lists.extend(node.child);
+                x = node.right;
+                if x == first {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> Clone for Handle<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Handle<K, V> {}
+
+impl<K, V> PartialEq for Handle<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K, V> Eq for Handle<K, V> {}
+
+impl<K, V> std::fmt::Debug for Handle<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle({:p})", self.0)
+    }
+}
+
+/// concatenates two circular lists, returns a node of the result,
+/// the first one of a if a is not empty
+fn concat<K, V>(a: Link<K, V>, b: Link<K, V>) -> Link<K, V> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => unsafe {
+            // a .. a_last, b .. b_last => a .. a_last b .. b_last
+            let mut a_last = a.as_ref().left;
+            let mut b_last = b.as_ref().left;
+            a_last.as_mut().right = b;
+            b.as_mut().left = a_last;
+            b_last.as_mut().right = a;
+            a.as_mut().left = b_last;
+            Some(a)
+        },
+    }
+}
+
+/// removes x from its list, x becomes a list of itself
+unsafe fn remove<K, V>(mut x: NonNull<Node<K, V>>) {
+    let mut left = x.as_ref().left;
+    let mut right = x.as_ref().right;
+    left.as_mut().right = right;
+    right.as_mut().left = left;
+    x.as_mut().left = x;
+    x.as_mut().right = x;
+}
+
+/// makes the root y a child of the root x
+unsafe fn link<K, V>(mut y: NonNull<Node<K, V>>, mut x: NonNull<Node<K, V>>) {
+    remove(y);
+    y.as_mut().parent = Some(x);
+    y.as_mut().mark = false;
+    x.as_mut().child = concat(x.as_ref().child, Some(y));
+    x.as_mut().degree += 1;
+}
@@ -0,0 +1,117 @@
+//! Meldable heaps as indexed min priority queues
+//!
+//! PairingHeap and FibonacciHeap address their entries by handles.
+//! IndexHeap keeps the handle of every index, so they can replace
+//! IndexPQ in the graph algorithms (DijkstraSP, PrimMST), which use
+//! the vertices as indexes.
+
+use crate::common::fibonacci_heap::{self, FibonacciHeap};
+use crate::common::pairing_heap::{self, PairingHeap};
+use crate::common::IndexMinPQ;
+use std::marker::PhantomData;
+
+/// A min heap with handles to its entries and O(1) meld
+pub trait MeldableHeap<K, V>: Default {
+    type Handle: Copy;
+
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn peek(&self) -> Option<(&K, &V)>;
+    fn push(&mut self, key: K, val: V) -> Self::Handle;
+    fn pop(&mut self) -> Option<(K, V)>;
+    /// # Safety
+    ///
+    /// h is returned by push of this heap, or of a heap melded into
+    /// it, and is not popped yet
+    unsafe fn decrease_key(&mut self, h: Self::Handle, key: K);
+    fn meld(&mut self, other: Self);
+}
+
+/// An indexed min priority queue on a MeldableHeap
+pub struct IndexHeap<H, K>
+where
+    H: MeldableHeap<K, usize>,
+{
+    heap: H,
+    // handles[i] is the handle of index i, if i is in the heap
+    handles: Vec<Option<H::Handle>>,
+    _k: PhantomData<K>,
+}
+
+pub type IndexPairingHeap<K> = IndexHeap<PairingHeap<K, usize>, K>;
+pub type IndexFibonacciHeap<K> = IndexHeap<FibonacciHeap<K, usize>, K>;
+
+macro_rules! impl_meldable_heap {
+    ($heap:ident, $module:ident) => {
+        impl<K: PartialOrd, V> MeldableHeap<K, V> for $heap<K, V> {
+            type Handle = $module::Handle<K, V>;
+
+            fn len(&self) -> usize {
+                $heap::len(self)
+            }
+
+            fn peek(&self) -> Option<(&K, &V)> {
+                $heap::peek(self)
+            }
+
+            fn push(&mut self, key: K, val: V) -> Self::Handle {
+                $heap::push(self, key, val)
+            }
+
+            fn pop(&mut self) -> Option<(K, V)> {
+                $heap::pop(self)
+            }
+
+            unsafe fn decrease_key(&mut self, h: Self::Handle, key: K) {
+                $heap::decrease_key(self, h, key)
+            }
+
+            fn meld(&mut self, other: Self) {
+                $heap::meld(self, other)
+            }
+        }
+    };
+}
+
+impl_meldable_heap!(PairingHeap, pairing_heap);
+impl_meldable_heap!(FibonacciHeap, fibonacci_heap);
+
+impl<H, K> IndexMinPQ<K> for IndexHeap<H, K>
+where
+    H: MeldableHeap<K, usize>,
+{
+    fn with_capacity(n: usize) -> Self {
+        Self {
+            heap: H::default(),
+            handles: vec![None; n],
+            _k: PhantomData,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.handles[i].is_some()
+    }
+
+    fn enqueue(&mut self, i: usize, key: K) {
+        assert!(!self.contains(i), "index {} is already in the heap", i);
+        self.handles[i] = Some(self.heap.push(key, i));
+    }
+
+    fn dequeue(&mut self) -> Option<usize> {
+        let (_, i) = self.heap.pop()?;
+        self.handles[i] = None;
+        Some(i)
+    }
+
+    fn decrease_key(&mut self, i: usize, key: K) {
+        let h = self.handles[i].expect("index is not in the heap");
+        // the handle is dropped when its entry is popped
+        unsafe { self.heap.decrease_key(h, key) }
+    }
+}
@@ -0,0 +1,144 @@
+//! Merges k sorted streams into one sorted stream, lazily
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! 2.4 Priority Queues, Multiway
+//!
+//! The head of every stream is on a priority queue, the next item of the
+//! merged stream is the smallest head, which is replaced by the next item
+//! of its stream. An item takes time proportional to log k, k items are
+//! kept in memory.
+//!
+//! The priority queue is
+//!   - a tournament tree: replaces the smallest head by replaying log k
+//!     matches without swapping anything, the merge is stable, equal
+//!     items are taken from the streams in order
+//!   - IndexPQ: the book's Multiway, the index of a head is its stream
+//!
+//! Descending merges streams sorted in descending order, the next item
+//! is the largest head.
+
+use crate::common::IndexPQ;
+use crate::tree::binary::builder::tournament::Tournament;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MergeOrder {
+    Ascending,
+    Descending,
+}
+
+/// An iterator merging sorted iterators
+pub struct KWayMerge<I: Iterator> {
+    streams: Vec<I>,
+    queue: Queue<I::Item>,
+}
+
+enum Queue<T> {
+    Tournament {
+        tree: Tournament,
+        heads: Vec<Option<T>>,
+        order: MergeOrder,
+    },
+    Heap(IndexPQ<T>),
+}
+
+impl<I> KWayMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    /// Merges the streams with a tournament tree
+    pub fn tournament<S>(streams: S, order: MergeOrder) -> Self
+    where
+        S: IntoIterator,
+        S::Item: IntoIterator<IntoIter = I, Item = I::Item>,
+    {
+        let mut streams: Vec<I> = streams.into_iter().map(IntoIterator::into_iter).collect();
+        let heads: Vec<Option<I::Item>> = streams.iter_mut().map(Iterator::next).collect();
+        let tree = Tournament::new(heads.len(), |a, b| beats(&heads, order, a, b));
+        Self {
+            streams,
+            queue: Queue::Tournament { tree, heads, order },
+        }
+    }
+
+    /// Merges the streams with an IndexPQ
+    pub fn index_pq<S>(streams: S, order: MergeOrder) -> Self
+    where
+        S: IntoIterator,
+        S::Item: IntoIterator<IntoIter = I, Item = I::Item>,
+    {
+        let mut streams: Vec<I> = streams.into_iter().map(IntoIterator::into_iter).collect();
+        let mut pq = match order {
+            MergeOrder::Ascending => IndexPQ::new_min_pq(streams.len()),
+            MergeOrder::Descending => IndexPQ::new_max_pq(streams.len()),
+        };
+        for (i, stream) in streams.iter_mut().enumerate() {
+            if let Some(v) = stream.next() {
+                let _ = pq.enqueue(i, v);
+            }
+        }
+        Self {
+            streams,
+            queue: Queue::Heap(pq),
+        }
+    }
+}
+
+impl<I> Iterator for KWayMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.queue {
+            Queue::Tournament { tree, heads, order } => {
+                let i = tree.winner()?;
+                // the winner is exhausted, so are all streams
+                let v = heads[i].take()?;
+                heads[i] = self.streams[i].next();
+                let order = *order;
+                tree.replay(i, |a, b| beats(heads, order, a, b));
+                Some(v)
+            }
+            Queue::Heap(pq) => {
+                let (i, v) = pq.dequeue_entry()?;
+                if let Some(next) = self.streams[i].next() {
+                    let _ = pq.enqueue(i, next);
+                }
+                Some(v)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let queued = match &self.queue {
+            Queue::Tournament { heads, .. } => heads.iter().filter(|h| h.is_some()).count(),
+            Queue::Heap(pq) => pq.len(),
+        };
+        self.streams.iter().map(Iterator::size_hint).fold(
+            (queued, Some(queued)),
+            |(lo, hi), (l, h)| {
+                (
+                    lo.saturating_add(l),
+                    hi.and_then(|hi| h.and_then(|h| hi.checked_add(h))),
+                )
+            },
+        )
+    }
+}
+
+/// does the head of stream a win the head of stream b,
+/// an exhausted stream loses to any stream
+fn beats<T: Ord>(heads: &[Option<T>], order: MergeOrder, a: usize, b: usize) -> bool {
+    match (&heads[a], &heads[b]) {
+        (Some(x), Some(y)) => match order {
+            MergeOrder::Ascending => x < y,
+            MergeOrder::Descending => x > y,
+        },
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
@@ -1,6 +1,13 @@
+pub mod arena;
 pub mod binary_tree;
+pub mod compare;
+mod concurrent_uf;
 pub mod drop;
+pub mod fibonacci_heap;
+mod index_heap;
+mod kway_merge;
 pub mod max_heap;
+pub mod pairing_heap;
 mod priority_queue;
 mod queue;
 mod stack;
@@ -8,8 +15,17 @@ mod top_m;
 mod uf;
 pub mod util;
 
-pub use priority_queue::{IndexPQ, PQ};
+pub use arena::{Arena, Handle};
+pub use concurrent_uf::ConcurrentUF;
+pub use fibonacci_heap::FibonacciHeap;
+pub use index_heap::{IndexFibonacciHeap, IndexHeap, IndexPairingHeap, MeldableHeap};
+pub use kway_merge::{KWayMerge, MergeOrder};
+pub use pairing_heap::PairingHeap;
+pub use priority_queue::{IndexMinPQ, IndexPQ, IndexPQError, PeekMut, PQ};
 pub use queue::Queue;
 pub use stack::Stack;
 pub use top_m::TopM;
-pub use uf::{QuickFindUF, QuickUnionUF, WeightedQuickUnionUF, UF};
+pub use uf::{
+    Contradiction, Potential, PotentialUF, QuickFindUF, QuickUnionUF, RollbackUF, UnionFind,
+    WeightedQuickUnionUF, UF,
+};
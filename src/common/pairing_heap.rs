@@ -0,0 +1,223 @@
+//! Pairing heap, a min heap as a multiway tree
+//!
+//! The root holds the smallest key, the children of a node are kept in a
+//! linked list (child, then next siblings), every node links back to its
+//! previous sibling, or to its parent if it is the first child.
+//!
+//!   - link(a, b): the root with the larger key becomes the first child
+//!     of the other, O(1)
+//!   - push, meld: link with the root, O(1)
+//!   - decrease_key: cut the subtree of the node, link it with the
+//!     root, O(1), amortized O(log N) by the analysis of Fredman et al.
+//!     (the conjectured o(log N) is still open)
+//!   - pop: remove the root, link its children in pairs from left to
+//!     right, then link the pairs from right to left (two-pass),
+//!     amortized O(log N)
+//!
+//! Fredman, Sedgewick, Sleator, Tarjan, The pairing heap: a new form of
+//! self-adjusting heap, 1986
+
+use std::cmp::Ordering;
+use std::ptr::NonNull;
+
+pub struct PairingHeap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+/// The entry pushed into the heap, to decrease its key
+pub struct Handle<K, V>(NonNull<Node<K, V>>);
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    child: Link<K, V>,
+    next: Link<K, V>,
+    // previous sibling, or parent for the first child
+    prev: Link<K, V>,
+}
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+impl<K: PartialOrd, V> PairingHeap<K, V> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the entry with the smallest key
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.root.map(|x| unsafe {
+            let x = &*x.as_ptr();
+            (&x.key, &x.val)
+        })
+    }
+
+    pub fn push(&mut self, key: K, val: V) -> Handle<K, V> {
+        let x = Box::new(Node {
+            key,
+            val,
+            child: None,
+            next: None,
+            prev: None,
+        });
+        let x = NonNull::from(Box::leak(x));
+        self.root = link(self.root, Some(x));
+        self.len += 1;
+        Handle(x)
+    }
+
+    /// Removes the entry with the smallest key
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let root = self.root?;
+        let root = unsafe { Box::from_raw(root.as_ptr()) };
+        self.root = merge_pairs(root.child);
+        self.len -= 1;
+        Some((root.key, root.val))
+    }
+
+    /// Decreases the key of the entry to key
+    ///
+    /// # Safety
+    ///
+    /// h is returned by push of this heap, or of a heap melded into
+    /// it, and is not popped yet
+    ///
+    /// # Panics
+    ///
+    /// if key is greater than the key of the entry
+    pub unsafe fn decrease_key(&mut self, h: Handle<K, V>, key: K) {
+        let mut x = h.0;
+        assert!(
+            key.partial_cmp(&x.as_ref().key) != Some(Ordering::Greater),
+            "key is greater than the key of the entry"
+        );
+        x.as_mut().key = key;
+        if self.root != Some(x) {
+            cut(x);
+            self.root = link(self.root, Some(x));
+        }
+    }
+
+    /// Moves all entries of other into self, the handles of other
+    /// are handles of self
+    pub fn meld(&mut self, mut other: Self) {
+        self.root = link(self.root, other.root.take());
+        self.len += other.len;
+    }
+
+    /// Returns the key of the entry
+    ///
+    /// # Safety
+    ///
+    /// the same as decrease_key
+    pub unsafe fn key_of(&self, h: Handle<K, V>) -> &K {
+        &(*h.0.as_ptr()).key
+    }
+}
+
+impl<K: PartialOrd, V> Default for PairingHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for PairingHeap<K, V> {
+    fn drop(&mut self) {
+        let mut stack: Vec<NonNull<Node<K, V>>> = self.root.take().into_iter().collect();
+        while let Some(x) = stack.pop() {
+            let x = unsafe { Box::from_raw(x.as_ptr()) };
+            stack.extend(x.child);
+            stack.extend(x.next);
+        }
+    }
+}
+
+impl<K, V> Clone for Handle<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Handle<K, V> {}
+
+impl<K, V> PartialEq for Handle<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K, V> Eq for Handle<K, V> {}
+
+impl<K, V> std::fmt::Debug for Handle<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle({:p})", self.0)
+    }
+}
+
+/// links two roots, returns the new root, it has no siblings
+fn link<K: PartialOrd, V>(a: Link<K, V>, b: Link<K, V>) -> Link<K, V> {
+    let (mut a, mut b) = match (a, b) {
+        (None, b) => return b,
+        (a, None) => return a,
+        (Some(a), Some(b)) => (a, b),
+    };
+    unsafe {
+        if b.as_ref().key < a.as_ref().key {
+            std::mem::swap(&mut a, &mut b);
+        }
+        // b becomes the first child of a
+        let first = a.as_ref().child;
+        b.as_mut().next = first;
+        b.as_mut().prev = Some(a);
+        if let Some(mut first) = first {
+            first.as_mut().prev = Some(b);
+        }
+        a.as_mut().child = Some(b);
+        a.as_mut().next = None;
+        a.as_mut().prev = None;
+    }
+    Some(a)
+}
+
+/// cuts the subtree of x off its parent and siblings
+unsafe fn cut<K, V>(mut x: NonNull<Node<K, V>>) {
+    let next = x.as_ref().next;
+    if let Some(mut prev) = x.as_ref().prev {
+        if prev.as_ref().child == Some(x) {
+            prev.as_mut().child = next;
+        } else {
+            prev.as_mut().next = next;
+        }
+    }
+    if let Some(mut next) = next {
+        next.as_mut().prev = x.as_ref().prev;
+    }
+    x.as_mut().next = None;
+    x.as_mut().prev = None;
+}
+
+/// two-pass merge of the siblings starting at first
+fn merge_pairs<K: PartialOrd, V>(first: Link<K, V>) -> Link<K, V> {
+    // left to right, link in pairs
+    let mut pairs = Vec::new();
+    let mut x = first;
+    while let Some(mut a) = x {
+        let b = unsafe { a.as_ref().next };
+        x = b.and_then(|b| unsafe { b.as_ref().next });
+        unsafe {
+            a.as_mut().prev = None;
+            a.as_mut().next = None;
+        }
+        pairs.push(link(Some(a), b));
+    }
+    // right to left, link the pairs into one
+    pairs.into_iter().rev().fold(None, link)
+}
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
 
 /// Often, we collect a set of items, then process the one with the
 /// largest key, then perhaps collect more items, then process the
@@ -12,12 +14,22 @@ use std::cmp::Ordering;
 
 type Comparator<T> = Box<dyn Fn(&T, &T) -> bool>;
 
+/// d-ary heap, 0-based indexing: the children of k are d*k+1 ..= d*k+d,
+/// the parent of k is (k-1)/d. A larger d makes the heap shallower, so
+/// enqueue is faster (log_d N compares) and dequeue is slower (d log_d N
+/// compares), d = 4 is often faster than d = 2 for its cache behavior.
 pub struct PQ<T> {
-    pq: Vec<T>, // 为了计算方便, pq的index是从1开始计算的, 0号元素未被使用
-    n: usize,
+    pq: Vec<T>,
+    d: usize,
     comparator: Comparator<T>,
 }
 
+/// A mutable reference to the top key of PQ, the key sinks to its place
+/// when the reference is dropped
+pub struct PeekMut<'a, T: PartialOrd> {
+    pq: &'a mut PQ<T>,
+}
+
 pub struct IndexPQ<T> {
     // max_n: usize, // maximum number of elements on PQ
     n: usize,     // number of elements on PQ
@@ -27,15 +39,14 @@ pub struct IndexPQ<T> {
     comparator: Comparator<Option<T>>,
 }
 
-impl<T: PartialOrd + Default> PQ<T> {
+impl<T: PartialOrd> PQ<T> {
     /// The MinPQ represents a priority queue of generic keys.
     /// It supports the usual insert and delete-the-minimum
     /// operations, along with methods for peeking at the minimum key,
     /// testing if the priority queue is empty, and iterating through
     /// the keys.
     pub fn new_min_pq(cap: usize) -> Self {
-        let comparator = Box::new(|a: &T, b: &T| a.gt(b));
-        Self::new(cap, comparator)
+        Self::new(Vec::with_capacity(cap), Box::new(|a: &T, b: &T| a.gt(b)))
     }
 
     /// The MaxPQ represents a priority queue of generic keys.
@@ -44,72 +55,127 @@ impl<T: PartialOrd + Default> PQ<T> {
     /// testing if the priority queue is empty, and iterating through
     /// the keys.
     pub fn new_max_pq(cap: usize) -> Self {
-        let comparator = Box::new(|a: &T, b: &T| a.lt(b));
-        Self::new(cap, comparator)
+        Self::new(Vec::with_capacity(cap), Box::new(|a: &T, b: &T| a.lt(b)))
+    }
+
+    /// Builds a MinPQ of the keys bottom-up, in linear time,
+    /// from_vec_max builds a MaxPQ
+    pub fn from_vec(keys: Vec<T>) -> Self {
+        Self::new(keys, Box::new(|a: &T, b: &T| a.gt(b)))
     }
 
-    fn new(cap: usize, comparator: Comparator<T>) -> Self {
+    /// Builds a MaxPQ of the keys bottom-up, in linear time
+    pub fn from_vec_max(keys: Vec<T>) -> Self {
+        Self::new(keys, Box::new(|a: &T, b: &T| a.lt(b)))
+    }
+
+    fn new(keys: Vec<T>, comparator: Comparator<T>) -> Self {
         let mut pq = Self {
-            pq: Vec::with_capacity(cap + 1),
-            n: 0,
+            pq: keys,
+            d: 2,
             comparator,
         };
-
-        // 虽然此处初始化了0号元素, 但0号元素并不参与算法过程，
-        pq.pq.push(T::default());
-
+        pq.heapify();
         pq
     }
 
+    /// Uses a d-ary heap, d >= 2, the default is 2
+    pub fn with_arity(mut self, d: usize) -> Self {
+        assert!(d >= 2, "arity {} is less than 2", d);
+        self.d = d;
+        self.heapify();
+        self
+    }
+
     /// Returns true if this priority queue is empty
     pub fn is_empty(&self) -> bool {
-        self.n == 0
+        self.pq.is_empty()
     }
 
     /// Returns the number of keys on this priority queue
     pub fn len(&self) -> usize {
-        self.n
+        self.pq.len()
     }
 
     /// MinPQ: Returns a smallest key on this priority queue
     /// MaxPQ: Returns a largest key on this priority queue
     pub fn peek(&self) -> Option<&T> {
-        self.pq.get(1)
+        self.pq.first()
+    }
+
+    /// Returns a mutable reference to the top key, it is moved to its
+    /// place when the reference is dropped
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { pq: self })
+        }
     }
 
     /// Adds a new key to this priority queue
     pub fn enqueue(&mut self, x: T) {
         self.pq.push(x);
-        self.n += 1;
-        self.swim(self.n);
+        self.swim(self.pq.len() - 1);
     }
 
     /// MinPQ: Removes and returns a smallest key on this priority queue
     /// MaxPQ: Removes and returns a largest key on this priority queue
     pub fn dequeue(&mut self) -> Option<T> {
-        if self.n == 0 {
+        if self.pq.is_empty() {
             None
         } else {
-            self.pq.swap(1, self.n);
-            self.n -= 1;
-            self.sink(1);
-            self.pq.pop()
+            let last = self.pq.len() - 1;
+            self.pq.swap(0, last);
+            let top = self.pq.pop();
+            self.sink(0, self.pq.len());
+            top
+        }
+    }
+
+    /// Returns the keys in the order of dequeue, by heapsort in place
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        // move the top to the end of the heap, the heap shrinks
+        let mut n = self.pq.len();
+        while n > 1 {
+            n -= 1;
+            self.pq.swap(0, n);
+            self.sink(0, n);
+        }
+        self.pq.reverse();
+        self.pq
+    }
+
+    /// 自底向上，从最后一个有孩子的节点开始sink, O(n)
+    fn heapify(&mut self) {
+        let n = self.pq.len();
+        if n > 1 {
+            for k in (0..=(n - 2) / self.d).rev() {
+                self.sink(k, n);
+            }
         }
     }
 
     fn swim(&mut self, mut k: usize) {
-        while k > 1 && self.compare(k / 2, k) {
-            self.pq.swap(k, k / 2);
-            k /= 2;
+        while k > 0 && self.compare((k - 1) / self.d, k) {
+            self.pq.swap(k, (k - 1) / self.d);
+            k = (k - 1) / self.d;
         }
     }
 
-    fn sink(&mut self, mut k: usize) {
-        let n = self.n;
-        while 2 * k <= n {
-            let mut j = 2 * k;
-            if j < n && self.compare(j, j + 1) {
-                j += 1;
+    /// sinks the key at k in the heap pq[..n]
+    fn sink(&mut self, mut k: usize, n: usize) {
+        loop {
+            let first = self.d * k + 1;
+            if first >= n {
+                break;
+            }
+            // the top child
+            let mut j = first;
+            for c in first + 1..(first + self.d).min(n) {
+                if self.compare(j, c) {
+                    j = c;
+                }
             }
             if !self.compare(k, j) {
                 break;
@@ -124,7 +190,39 @@ impl<T: PartialOrd + Default> PQ<T> {
     }
 }
 
-impl<T: PartialOrd + Copy + Clone> IndexPQ<T> {
+impl<'a, T: PartialOrd> PeekMut<'a, T> {
+    /// Removes the top key
+    pub fn pop(this: Self) -> T {
+        let pq = &mut *this.pq;
+        let last = pq.pq.len() - 1;
+        pq.pq.swap(0, last);
+        // the new top sinks when this is dropped
+        pq.pq.pop().unwrap()
+    }
+}
+
+impl<'a, T: PartialOrd> Deref for PeekMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.pq.pq[0]
+    }
+}
+
+impl<'a, T: PartialOrd> DerefMut for PeekMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.pq.pq[0]
+    }
+}
+
+impl<'a, T: PartialOrd> Drop for PeekMut<'a, T> {
+    fn drop(&mut self) {
+        let n = self.pq.pq.len();
+        self.pq.sink(0, n);
+    }
+}
+
+impl<T: PartialOrd> IndexPQ<T> {
     pub fn new_min_pq(max_n: usize) -> Self {
         let comparator = Box::new(|a: &Option<T>, b: &Option<T>| a.gt(b));
         Self::new(max_n, comparator)
@@ -141,7 +239,7 @@ impl<T: PartialOrd + Copy + Clone> IndexPQ<T> {
             n: 0,
             pq: vec![-1; max_n + 1],
             qp: vec![-1; max_n + 1],
-            keys: vec![None; max_n + 1],
+            keys: std::iter::repeat_with(|| None).take(max_n + 1).collect(),
             comparator,
         }
     }
@@ -158,25 +256,32 @@ impl<T: PartialOrd + Copy + Clone> IndexPQ<T> {
 
     /// Is i an index on this priority queue?
     pub fn contains(&self, i: usize) -> bool {
-        self.qp[i] != -1
+        self.qp.get(i).is_some_and(|&k| k != -1)
     }
 
     /// MinPQ: Returns an index associated with a minimum key
     /// MaxPQ: Returns an index associated with a maximum key
     pub fn peek_index(&self) -> Option<&i32> {
-        self.pq.get(1)
+        self.pq[..=self.n].get(1)
     }
 
     /// MinPQ: Returns a minimum key
     /// MaxPQ: Returns a maximum key
     pub fn peek_key(&self) -> Option<&T> {
-        self.pq.get(1).and_then(|&i| self.keys[i as usize].as_ref())
+        self.peek_index()
+            .and_then(|&i| self.keys[i as usize].as_ref())
+    }
+
+    /// Returns the key associated with index i
+    pub fn key_of(&self, i: usize) -> Option<&T> {
+        self.keys.get(i).and_then(Option::as_ref)
     }
 
     /// Associates key with index i
-    pub fn enqueue(&mut self, i: usize, key: T) -> Result<(), &'static str> {
+    pub fn enqueue(&mut self, i: usize, key: T) -> Result<(), IndexPQError> {
+        self.validate(i)?;
         if self.contains(i) {
-            Err("index is already in the priority queue")
+            Err(IndexPQError::AlreadyExists(i))
         } else {
             self.n += 1;
             self.qp[i] = self.n as i32;
@@ -190,36 +295,97 @@ impl<T: PartialOrd + Copy + Clone> IndexPQ<T> {
     /// MinPQ: Removes a minimum key and returns its associated index
     /// MaxPQ: Removes a maximum key and returns its associated index
     pub fn dequeue(&mut self) -> Option<usize> {
+        self.dequeue_entry().map(|(i, _)| i)
+    }
+
+    /// MinPQ: Removes a minimum key and returns it with its associated index
+    /// MaxPQ: Removes a maximum key and returns it with its associated index
+    pub fn dequeue_entry(&mut self) -> Option<(usize, T)> {
         if self.n == 0 {
             None
         } else {
-            let min = self.pq[1] as usize;
-            self.exch(1, self.n);
-            self.n -= 1;
-            self.sink(1);
-            debug_assert_eq!(self.pq[self.n + 1], min as i32);
-            self.qp[min] = -1; // delete
-            let _ = self.keys[min].take();
-            self.pq[self.n + 1] = -1;
-            Some(min)
+            let i = self.pq[1] as usize;
+            Some((i, self.remove_at(1)))
+        }
+    }
+
+    /// Removes the key associated with index i, returns the key
+    pub fn delete(&mut self, i: usize) -> Result<T, IndexPQError> {
+        self.validate_contains(i)?;
+        Ok(self.remove_at(self.qp[i] as usize))
+    }
+
+    /// Changes the key associated with index i to the specified value
+    pub fn change_key(&mut self, i: usize, key: T) -> Result<(), IndexPQError> {
+        self.validate_contains(i)?;
+        self.keys[i] = Some(key);
+        let k = self.qp[i] as usize;
+        self.swim(k);
+        self.sink(self.qp[i] as usize);
+        Ok(())
+    }
+
+    /// Decreases the key associated with index i to the specified value,
+    /// the key must be strictly less than the key of i
+    pub fn decrease_key(&mut self, i: usize, key: T) -> Result<(), IndexPQError> {
+        self.validate_contains(i)?;
+        match self.keys[i].as_ref().and_then(|k| k.partial_cmp(&key)) {
+            None => Err(IndexPQError::Incomparable(i)),
+            Some(Ordering::Greater) => self.change_key(i, key),
+            Some(_) => Err(IndexPQError::NotDecreased(i)),
+        }
+    }
+
+    /// Increases the key associated with index i to the specified value,
+    /// the key must be strictly greater than the key of i
+    pub fn increase_key(&mut self, i: usize, key: T) -> Result<(), IndexPQError> {
+        self.validate_contains(i)?;
+        match self.keys[i].as_ref().and_then(|k| k.partial_cmp(&key)) {
+            None => Err(IndexPQError::Incomparable(i)),
+            Some(Ordering::Less) => self.change_key(i, key),
+            Some(_) => Err(IndexPQError::NotIncreased(i)),
+        }
+    }
+
+    /// Returns an iterator over (index, key) in priority order, the
+    /// priority queue is unchanged
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            pq: self,
+            heap: if self.n == 0 { vec![] } else { vec![1] },
+        }
+    }
+
+    fn validate(&self, i: usize) -> Result<(), IndexPQError> {
+        if i + 1 < self.pq.len() {
+            Ok(())
+        } else {
+            Err(IndexPQError::OutOfBounds(i))
         }
     }
 
-    pub fn decrease_key(&mut self, i: usize, key: T) -> Result<(), &'static str> {
-        if !self.contains(i) {
-            Err("index is not in the priority queue")
+    fn validate_contains(&self, i: usize) -> Result<(), IndexPQError> {
+        self.validate(i)?;
+        if self.contains(i) {
+            Ok(())
         } else {
-            match self.keys[i].partial_cmp(&Some(key)) {
-                None => Err("Calling decreaseKey() with a key that comparison is impossible"),
-                Some(Ordering::Equal) => Err("Calling decreaseKey() with a key equal to the key in the priority queue"),
-                Some(Ordering::Less) => Err("Calling decreaseKey() with a key strictly greater than the key in the priority queue"),
-                Some(Ordering::Greater) => {
-                    self.keys[i] = Some(key);
-                    self.swim(self.qp[i] as usize);
-                    Ok(())
-                }
-            }
+            Err(IndexPQError::NotFound(i))
+        }
+    }
+
+    /// removes the key at heap position k, returns it
+    fn remove_at(&mut self, k: usize) -> T {
+        let i = self.pq[k] as usize;
+        self.exch(k, self.n);
+        self.n -= 1;
+        if k <= self.n {
+            self.swim(k);
+            self.sink(k);
         }
+        debug_assert_eq!(self.pq[self.n + 1], i as i32);
+        self.qp[i] = -1; // delete
+        self.pq[self.n + 1] = -1;
+        self.keys[i].take().unwrap()
     }
 
     fn swim(&mut self, mut k: usize) {
@@ -255,3 +421,151 @@ impl<T: PartialOrd + Copy + Clone> IndexPQ<T> {
         self.qp.swap(self.pq[i] as usize, self.pq[j] as usize);
     }
 }
+
+/// An indexed min priority queue, the keys are associated with the
+/// indexes 0..n
+///
+/// It is what DijkstraSP and PrimMST need of the priority queue, so
+/// they can run on IndexPQ or the meldable heaps (IndexHeap).
+pub trait IndexMinPQ<K> {
+    /// Creates an empty priority queue of the indexes 0..n
+    fn with_capacity(n: usize) -> Self;
+    fn is_empty(&self) -> bool;
+    /// Is i an index on this priority queue?
+    fn contains(&self, i: usize) -> bool;
+    /// Associates key with index i, i must not be on the queue
+    fn enqueue(&mut self, i: usize, key: K);
+    /// Removes a minimum key and returns its associated index
+    fn dequeue(&mut self) -> Option<usize>;
+    /// Decreases the key associated with index i, the key must not be
+    /// greater than the key of i
+    fn decrease_key(&mut self, i: usize, key: K);
+}
+
+impl<K: PartialOrd> IndexMinPQ<K> for IndexPQ<K> {
+    fn with_capacity(n: usize) -> Self {
+        IndexPQ::new_min_pq(n)
+    }
+
+    fn is_empty(&self) -> bool {
+        IndexPQ::is_empty(self)
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        IndexPQ::contains(self, i)
+    }
+
+    fn enqueue(&mut self, i: usize, key: K) {
+        IndexPQ::enqueue(self, i, key).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn dequeue(&mut self) -> Option<usize> {
+        IndexPQ::dequeue(self)
+    }
+
+    fn decrease_key(&mut self, i: usize, key: K) {
+        IndexPQ::change_key(self, i, key).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// Iterates the keys of IndexPQ in priority order
+///
+/// The next key is the top of the heap positions not yet visited whose
+/// parent is visited, they are kept on a small heap of heap positions,
+/// so the IndexPQ is not copied, and k keys take time k log k.
+pub struct Iter<'a, T> {
+    pq: &'a IndexPQ<T>,
+    // heap of positions in pq.pq, heap[0] is the top
+    heap: Vec<usize>,
+}
+
+impl<'a, T: PartialOrd> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.heap.swap(0, last);
+        let k = self.heap.pop().unwrap();
+        self.sink(0);
+        for &c in [2 * k, 2 * k + 1].iter() {
+            if c <= self.pq.n {
+                self.heap.push(c);
+                self.swim(self.heap.len() - 1);
+            }
+        }
+        let i = self.pq.pq[k] as usize;
+        self.pq.keys[i].as_ref().map(|key| (i, key))
+    }
+}
+
+impl<'a, T: PartialOrd> Iter<'a, T> {
+    // does heap[a] come after heap[b]
+    fn after(&self, a: usize, b: usize) -> bool {
+        self.pq.compare(self.heap[a], self.heap[b])
+    }
+
+    fn swim(&mut self, mut k: usize) {
+        while k > 0 && self.after((k - 1) / 2, k) {
+            self.heap.swap(k, (k - 1) / 2);
+            k = (k - 1) / 2;
+        }
+    }
+
+    fn sink(&mut self, mut k: usize) {
+        let n = self.heap.len();
+        while 2 * k + 1 < n {
+            let mut j = 2 * k + 1;
+            if j + 1 < n && self.after(j, j + 1) {
+                j += 1;
+            }
+            if !self.after(k, j) {
+                break;
+            }
+            self.heap.swap(k, j);
+            k = j;
+        }
+    }
+}
+
+/// Errors of IndexPQ, with the index
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IndexPQError {
+    /// the index is not less than max_n
+    OutOfBounds(usize),
+    /// the index is already in the priority queue
+    AlreadyExists(usize),
+    /// the index is not in the priority queue
+    NotFound(usize),
+    /// the new key can not be compared with the key of the index
+    Incomparable(usize),
+    /// decrease_key with a key not strictly less than the key of the index
+    NotDecreased(usize),
+    /// increase_key with a key not strictly greater than the key of the index
+    NotIncreased(usize),
+}
+
+impl fmt::Display for IndexPQError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IndexPQError::OutOfBounds(i) => write!(f, "index {} is out of bounds", i),
+            IndexPQError::AlreadyExists(i) => {
+                write!(f, "index {} is already in the priority queue", i)
+            }
+            IndexPQError::NotFound(i) => write!(f, "index {} is not in the priority queue", i),
+            IndexPQError::Incomparable(i) => {
+                write!(f, "key can not be compared with the key of index {}", i)
+            }
+            IndexPQError::NotDecreased(i) => {
+                write!(f, "key is not strictly less than the key of index {}", i)
+            }
+            IndexPQError::NotIncreased(i) => {
+                write!(f, "key is not strictly greater than the key of index {}", i)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexPQError {}
@@ -8,7 +8,7 @@ pub struct TopM<T> {
     m: usize,
 }
 
-impl<T: PartialOrd + Default> TopM<T> {
+impl<T: PartialOrd> TopM<T> {
     pub fn new(m: usize) -> Self {
         Self {
             pq: PQ::new_min_pq(m + 1),
@@ -1,6 +1,7 @@
 //! 此处实现了多种union-find算法，UF算法性能最好
 
 use std::cmp::Ordering;
+use std::fmt;
 
 /// The QuickUnionUF represents a union–find data type
 /// (also known as the disjoint-sets data type).
@@ -39,8 +40,9 @@ use std::cmp::Ordering;
 /// For alternative implementations of the same API, see
 /// UF, QuickFindUF, and WeightedQuickUnionUF.
 pub struct QuickFindUF {
-    id: Vec<usize>, // id[i] = component identifier of i
-    count: usize,   // number of components
+    id: Vec<usize>,   // id[i] = component identifier of i
+    size: Vec<usize>, // size[id] = number of elements in component id
+    count: usize,     // number of components
 }
 
 /// In particular, suppose that we use quick-find for the dynamic connectivity
@@ -61,6 +63,7 @@ pub struct QuickFindUF {
 /// find() operations for these N pairs is 2 (1 + 2 + . . . + N ) ~ N^2
 pub struct QuickUnionUF {
     parent: Vec<usize>, // parent[i] = parent of i
+    size: Vec<usize>,   // size[i] = number of elements in tree rooted at i
     count: usize,       // number of components
 }
 
@@ -81,23 +84,122 @@ pub struct UF {
     // rank[i] = rank of subtree rooted at i (never more than 31)
     // rank == 31的话，将有 2 ^ 31 个节点, 一棵满二叉树的节点数量
     rank: Vec<usize>,
-    count: usize, // number of components
+    size: Vec<usize>, // size[i] = number of elements in tree rooted at i
+    count: usize,     // number of components
 }
 
-impl QuickFindUF {
-    pub fn new(n: usize) -> Self {
+/// This implementation uses union by rank without path compression,
+/// so a union can be undone: every union pushes the link it makes
+/// onto a history, rollback pops the links back off.
+///
+/// snapshot() returns the current length of the history, rollback(to)
+/// undoes all the unions made after that snapshot, in reverse order.
+/// The find operation takes O(log n) time, union takes O(log n) time,
+/// undoing one union takes O(1) time.
+///
+/// Used by the offline dynamic connectivity (graph::undirected::DynamicCC),
+/// which adds edges while walking down a segment tree over time and
+/// removes them on the way back.
+pub struct RollbackUF {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+    // (child, root, rank of root increased) of every union made
+    history: Vec<(usize, usize, bool)>,
+}
+
+/// A union-find of elements with potentials (values known only relative
+/// to each other), for the relative constraints like "x is 5 more than
+/// y", or "x and y have different parity".
+///
+/// Every element stores its diff to its parent, so the diff to the root
+/// is the sum along the path. The same union by rank and path halving
+/// as UF, while halving, the diff of p to its grandparent is the sum of
+/// the diffs of p and of its parent.
+///
+/// union_with_diff(p, q, d) adds the constraint value(q) - value(p) = d,
+/// diff(p, q) returns value(q) - value(p) if p and q are in the same set.
+/// A constraint of p and q already in the same set must agree with the
+/// diff derived from the existing ones, otherwise it is a Contradiction
+/// and is rejected. As a UnionFind, union(p, q) is the constraint
+/// value(q) = value(p).
+pub struct PotentialUF<D> {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+    // diff[i] = value(i) - value(parent[i]), zero for the roots
+    diff: Vec<D>,
+    count: usize,
+}
+
+/// The potentials of PotentialUF, an abelian group: the integers under
+/// wrapping +, bool under xor (parity). Not the floats, + rounds, so it
+/// is not associative, and the diffs derived along different paths
+/// would not be exactly equal.
+pub trait Potential: Copy + PartialEq + fmt::Debug {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+}
+
+/// The constraint value(q) - value(p) = given of union_with_diff
+/// conflicts with value(q) - value(p) = expected, derived from the
+/// constraints added before
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contradiction<D> {
+    pub p: usize,
+    pub q: usize,
+    pub expected: D,
+    pub given: D,
+}
+
+/// The union-find operations, implemented by QuickFindUF, QuickUnionUF,
+/// WeightedQuickUnionUF, UF, RollbackUF, PotentialUF and ConcurrentUF,
+/// so the clients (e.g. KruskalMST) can swap the strategies.
+///
+/// find takes &mut self, UF compresses the paths while finding.
+pub trait UnionFind {
+    /// Initializes an empty union-find data structure with n elements
+    /// 0 through n-1, each element is in its own set
+    fn new(n: usize) -> Self
+    where
+        Self: Sized;
+
+    /// Returns the canonical element of the set containing element p
+    fn find(&mut self, p: usize) -> usize;
+
+    /// Merges the set containing element p with the
+    /// the set containing element q
+    fn union(&mut self, p: usize, q: usize);
+
+    /// Returns true if the two elements are in the same set
+    fn connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(p) == self.find(q)
+    }
+
+    /// Returns the number of sets
+    fn count(&self) -> usize;
+
+    /// Returns the number of elements in the set containing element p
+    fn component_size(&mut self, p: usize) -> usize;
+}
+
+impl UnionFind for QuickFindUF {
+    fn new(n: usize) -> Self {
         let id = (0..n).collect();
-        Self { id, count: n }
+        Self {
+            id,
+            size: vec![1; n],
+            count: n,
+        }
     }
 
-    /// Returns the canonical element of the set containing element p
-    pub fn find(&self, p: usize) -> usize {
+    fn find(&mut self, p: usize) -> usize {
         self.id[p]
     }
 
-    /// Merges the set containing element p with the
-    /// the set containing element q
-    pub fn union(&mut self, p: usize, q: usize) {
+    fn union(&mut self, p: usize, q: usize) {
         let p_id = self.id[p];
         let q_id = self.id[q];
         // p and q are already in the same component
@@ -112,27 +214,37 @@ impl QuickFindUF {
             }
         }
 
+        self.size[q_id] += self.size[p_id];
         self.count -= 1;
     }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        self.size[self.id[p]]
+    }
 }
 
-impl QuickUnionUF {
-    pub fn new(n: usize) -> Self {
+impl UnionFind for QuickUnionUF {
+    fn new(n: usize) -> Self {
         let parent = (0..n).collect();
-        Self { parent, count: n }
+        Self {
+            parent,
+            size: vec![1; n],
+            count: n,
+        }
     }
 
-    /// Returns the canonical element of the set containing element p
-    pub fn find(&self, mut p: usize) -> usize {
+    fn find(&mut self, mut p: usize) -> usize {
         while p != self.parent[p] {
             p = self.parent[p];
         }
         p
     }
 
-    /// Merges the set containing element p with the
-    /// the set containing element q
-    pub fn union(&mut self, p: usize, q: usize) {
+    fn union(&mut self, p: usize, q: usize) {
         let root_p = self.find(p);
         let root_q = self.find(q);
         // p and q are already in the same component
@@ -141,12 +253,22 @@ impl QuickUnionUF {
         }
 
         self.parent[root_p] = root_q;
+        self.size[root_q] += self.size[root_p];
         self.count -= 1;
     }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
 }
 
-impl WeightedQuickUnionUF {
-    pub fn new(n: usize) -> Self {
+impl UnionFind for WeightedQuickUnionUF {
+    fn new(n: usize) -> Self {
         let parent = (0..n).collect();
         Self {
             parent,
@@ -155,14 +277,14 @@ impl WeightedQuickUnionUF {
         }
     }
 
-    pub fn find(&self, mut p: usize) -> usize {
+    fn find(&mut self, mut p: usize) -> usize {
         while p != self.parent[p] {
             p = self.parent[p];
         }
         p
     }
 
-    pub fn union(&mut self, p: usize, q: usize) {
+    fn union(&mut self, p: usize, q: usize) {
         let root_p = self.find(p);
         let root_q = self.find(q);
         // p and q are already in the same component
@@ -180,19 +302,29 @@ impl WeightedQuickUnionUF {
 
         self.count -= 1;
     }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
 }
 
-impl UF {
-    pub fn new(n: usize) -> Self {
+impl UnionFind for UF {
+    fn new(n: usize) -> Self {
         let parent = (0..n).collect();
         Self {
             parent,
             rank: vec![0; n],
+            size: vec![1; n],
             count: n,
         }
     }
 
-    pub fn find(&mut self, mut p: usize) -> usize {
+    fn find(&mut self, mut p: usize) -> usize {
         while p != self.parent[p] {
             self.parent[p] = self.parent[self.parent[p]]; // path compression by halving
             p = self.parent[p];
@@ -200,7 +332,7 @@ impl UF {
         p
     }
 
-    pub fn union(&mut self, p: usize, q: usize) {
+    fn union(&mut self, p: usize, q: usize) {
         let root_p = self.find(p);
         let root_q = self.find(q);
         // p and q are already in the same component
@@ -208,19 +340,260 @@ impl UF {
             return;
         }
 
-        match self.rank[root_p].cmp(&self.rank[root_q]) {
-            Ordering::Less => self.parent[root_p] = root_q,
-            Ordering::Greater => self.parent[root_q] = root_p,
+        let (child, root) = match self.rank[root_p].cmp(&self.rank[root_q]) {
+            Ordering::Less => (root_p, root_q),
+            Ordering::Greater => (root_q, root_p),
             Ordering::Equal => {
-                self.parent[root_q] = root_p;
                 self.rank[root_p] += 1;
+                (root_q, root_p)
             }
+        };
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+
+        self.count -= 1;
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
+}
+
+impl RollbackUF {
+    /// Returns a snapshot of the current state, to roll back to
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes all the unions made since the snapshot to
+    ///
+    /// # Panics
+    ///
+    /// if to is newer than the current state
+    pub fn rollback(&mut self, to: usize) {
+        assert!(to <= self.history.len(), "rollback to a future snapshot");
+        while self.history.len() > to {
+            let (child, root, rank_increased) = self.history.pop().unwrap();
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            if rank_increased {
+                self.rank[root] -= 1;
+            }
+            self.count += 1;
+        }
+    }
+}
+
+impl UnionFind for RollbackUF {
+    fn new(n: usize) -> Self {
+        let parent = (0..n).collect();
+        Self {
+            parent,
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+            history: Vec::new(),
         }
+    }
+
+    fn find(&mut self, mut p: usize) -> usize {
+        // no path compression, the links must stay as union made them
+        while p != self.parent[p] {
+            p = self.parent[p];
+        }
+        p
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        let root_p = self.find(p);
+        let root_q = self.find(q);
+        // p and q are already in the same component, nothing to undo
+        if root_p == root_q {
+            return;
+        }
+
+        let (child, root, rank_increased) = match self.rank[root_p].cmp(&self.rank[root_q]) {
+            Ordering::Less => (root_p, root_q, false),
+            Ordering::Greater => (root_q, root_p, false),
+            Ordering::Equal => (root_q, root_p, true),
+        };
+        if rank_increased {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.history.push((child, root, rank_increased));
 
         self.count -= 1;
     }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
+}
+
+impl<D: Potential> PotentialUF<D> {
+    /// Returns value(q) - value(p), None if p and q are not in the
+    /// same set (not constrained relative to each other)
+    pub fn diff(&mut self, p: usize, q: usize) -> Option<D> {
+        let (root_p, diff_p) = self.find_with_diff(p);
+        let (root_q, diff_q) = self.find_with_diff(q);
+        if root_p == root_q {
+            Some(diff_q.sub(diff_p))
+        } else {
+            None
+        }
+    }
+
+    /// Adds the constraint value(q) - value(p) = d, merges the sets of
+    /// p and q. Returns false if p and q are already in the same set
+    /// and the constraint agrees with the existing ones, nothing changes
+    /// on a Contradiction.
+    pub fn union_with_diff(&mut self, p: usize, q: usize, d: D) -> Result<bool, Contradiction<D>> {
+        let (root_p, diff_p) = self.find_with_diff(p);
+        let (root_q, diff_q) = self.find_with_diff(q);
+        if root_p == root_q {
+            let expected = diff_q.sub(diff_p);
+            return if expected == d {
+                Ok(false)
+            } else {
+                Err(Contradiction {
+                    p,
+                    q,
+                    expected,
+                    given: d,
+                })
+            };
+        }
+
+        // value(root_q) - value(root_p)
+        let d = d.add(diff_p).sub(diff_q);
+        let (child, root, d) = match self.rank[root_p].cmp(&self.rank[root_q]) {
+            Ordering::Less => (root_p, root_q, D::zero().sub(d)),
+            Ordering::Greater => (root_q, root_p, d),
+            Ordering::Equal => {
+                self.rank[root_p] += 1;
+                (root_q, root_p, d)
+            }
+        };
+        self.parent[child] = root;
+        self.diff[child] = d;
+        self.size[root] += self.size[child];
+
+        self.count -= 1;
+        Ok(true)
+    }
+
+    /// Returns the root of p and value(p) - value(root)
+    fn find_with_diff(&mut self, mut p: usize) -> (usize, D) {
+        let mut diff = D::zero();
+        while p != self.parent[p] {
+            let parent = self.parent[p];
+            // path compression by halving, the root's diff is zero
+            self.diff[p] = self.diff[p].add(self.diff[parent]);
+            self.parent[p] = self.parent[parent];
+            diff = diff.add(self.diff[p]);
+            p = self.parent[p];
+        }
+        (p, diff)
+    }
+}
+
+impl<D: Potential> UnionFind for PotentialUF<D> {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            diff: vec![D::zero(); n],
+            count: n,
+        }
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        self.find_with_diff(p).0
+    }
+
+    /// Adds the constraint value(q) = value(p)
+    ///
+    /// # Panics
+    ///
+    /// if it contradicts the constraints added before
+    fn union(&mut self, p: usize, q: usize) {
+        if let Err(e) = self.union_with_diff(p, q, D::zero()) {
+            panic!("{}", e);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn component_size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
 }
 
+macro_rules! impl_potential {
+    ($($T: ty),*) => {
+        $(
+            impl Potential for $T {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add(self, other: Self) -> Self {
+                    self.wrapping_add(other)
+                }
+
+                fn sub(self, other: Self) -> Self {
+                    self.wrapping_sub(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_potential!(i8, i16, i32, i64, i128, isize);
+
+/// parity: value(q) xor value(p)
+impl Potential for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn add(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self ^ other
+    }
+}
+
+impl<D: fmt::Debug> fmt::Display for Contradiction<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "diff({}, {}) = {:?} contradicts {:?}",
+            self.p, self.q, self.given, self.expected
+        )
+    }
+}
+
+impl<D: fmt::Debug> std::error::Error for Contradiction<D> {}
+
 impl ToString for UF {
     fn to_string(&self) -> String {
         let mut buf = Vec::new();
@@ -237,18 +610,6 @@ impl ToString for UF {
 
 macro_rules! uf_util {
     ($UF: ty) => {
-        impl $UF {
-            /// Returns the number of sets
-            pub fn count(&self) -> usize {
-                self.count
-            }
-
-            /// Returns true if the two elements are in the same set
-            pub fn connected(&mut self, p: usize, q: usize) -> bool {
-                self.find(p) == self.find(q)
-            }
-        }
-
         /// test data: the file tinyUF.txt contains the 11 connections among 10 sites
         impl std::str::FromStr for $UF {
             type Err = ();
@@ -279,3 +640,4 @@ uf_util!(QuickFindUF);
 uf_util!(QuickUnionUF);
 uf_util!(WeightedQuickUnionUF);
 uf_util!(UF);
+uf_util!(RollbackUF);
@@ -1,6 +1,6 @@
 #![allow(clippy::many_single_char_names)]
 
-use crate::common::{Queue, UF};
+use crate::common::{Queue, UnionFind, UF};
 use crate::graph::mst::{Edge, MST};
 use crate::graph::IEWGraph;
 use crate::ll::linked_list::Iter;
@@ -12,6 +12,11 @@ pub struct KruskalMST {
 
 impl KruskalMST {
     pub fn new(g: &dyn IEWGraph) -> Self {
+        Self::with_uf::<UF>(g)
+    }
+
+    /// Computes the MST with the union-find strategy U
+    pub fn with_uf<U: UnionFind>(g: &dyn IEWGraph) -> Self {
         let mut edges = g.edges();
         // When applicable, unstable sorting is preferred because it is
         // generally faster than stable sorting and it doesn’t allocate
@@ -21,7 +26,7 @@ impl KruskalMST {
 
         let mut mst = Queue::default();
         let mut weight = 0.0;
-        let mut uf = UF::new(g.V());
+        let mut uf = U::new(g.V());
         let mut i = 0;
         while i < g.E() && mst.len() < g.V() - 1 {
             let e = edges[i];
@@ -29,7 +34,7 @@ impl KruskalMST {
             let w = e.other(v);
 
             // v-w does not create a cycle
-            if uf.find(v) != uf.find(w) {
+            if !uf.connected(v, w) {
                 uf.union(v, w);
                 mst.enqueue(e);
                 weight += e.weight();
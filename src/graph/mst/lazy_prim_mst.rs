@@ -1,6 +1,6 @@
 #![allow(clippy::many_single_char_names)]
 
-use crate::common::{Queue, PQ, UF};
+use crate::common::{Queue, UnionFind, PQ, UF};
 use crate::graph::mst::{Edge, MST};
 use crate::graph::IEWGraph;
 use crate::ll::linked_list::Iter;
@@ -1,4 +1,4 @@
-use crate::common::{IndexPQ, UF};
+use crate::common::{IndexMinPQ, IndexPQ, UnionFind, UF};
 use crate::graph::mst::Edge;
 use crate::graph::IEWGraph;
 
@@ -16,23 +16,28 @@ pub struct PrimMST {
     edge_to: Vec<Option<Edge>>, // edgeTo[v] = shortest edge from tree vertex to non-tree vertex
     dist_to: Vec<f32>,          // distTo[v] = weight of shortest such edge
     marked: Vec<bool>,          // marked[v] = true if v on tree, false otherwise
-    pq: IndexPQ<f32>,           // eligible crossing edges
 }
 
 impl PrimMST {
     /// Compute a minimum spanning tree (or forest) of an edge-weighted graph
     pub fn new(g: &dyn IEWGraph) -> Self {
+        Self::with_pq::<IndexPQ<f32>>(g)
+    }
+
+    /// Compute a minimum spanning tree (or forest) with the priority
+    /// queue P, e.g. IndexPQ, IndexPairingHeap or IndexFibonacciHeap
+    pub fn with_pq<P: IndexMinPQ<f32>>(g: &dyn IEWGraph) -> Self {
         let gv = g.V();
         let mut mst = Self {
             edge_to: vec![None; gv],
             dist_to: vec![f32::MAX; gv],
             marked: vec![false; gv],
-            pq: IndexPQ::new_min_pq(gv),
         };
+        let mut pq = P::with_capacity(gv); // eligible crossing edges
 
         for s in 0..gv {
             if !mst.marked[s] {
-                mst.prim(g, s);
+                mst.prim(g, &mut pq, s);
             }
         }
 
@@ -53,15 +58,14 @@ impl PrimMST {
     }
 
     /// run Prim's algorithm in graph G, starting from vertex s
-    fn prim(&mut self, g: &dyn IEWGraph, s: usize) {
-        let _ = self.pq.enqueue(s, 0.0);
-        while !self.pq.is_empty() {
-            let v = self.pq.dequeue().unwrap();
-            self.scan(g, v);
+    fn prim<P: IndexMinPQ<f32>>(&mut self, g: &dyn IEWGraph, pq: &mut P, s: usize) {
+        pq.enqueue(s, 0.0);
+        while let Some(v) = pq.dequeue() {
+            self.scan(g, pq, v);
         }
     }
 
-    fn scan(&mut self, g: &dyn IEWGraph, v: usize) {
+    fn scan<P: IndexMinPQ<f32>>(&mut self, g: &dyn IEWGraph, pq: &mut P, v: usize) {
         self.marked[v] = true;
         for e in g.adj(v) {
             let w = e.other(v);
@@ -71,10 +75,10 @@ impl PrimMST {
             if e.weight() < self.dist_to[w] {
                 self.dist_to[w] = e.weight();
                 self.edge_to[w] = Some(*e);
-                if self.pq.contains(w) {
-                    let _ = self.pq.decrease_key(w, self.dist_to[w]);
+                if pq.contains(w) {
+                    pq.decrease_key(w, self.dist_to[w]);
                 } else {
-                    let _ = self.pq.enqueue(w, self.dist_to[w]);
+                    pq.enqueue(w, self.dist_to[w]);
                 }
             }
         }
@@ -1,14 +1,13 @@
 //! Dijkstra's algorithm. Computes the shortest path tree.
 //! Assumes all weights are non-negative.
 
-use crate::common::IndexPQ;
+use crate::common::{IndexMinPQ, IndexPQ};
 use crate::graph::shortest::DirectedEdge;
 use crate::graph::IEWDigraph;
 
 pub struct DijkstraSP {
     dist_to: Vec<f32>,                  // distTo[v] = distance  of shortest s->v path
     edge_to: Vec<Option<DirectedEdge>>, // edgeTo[v] = last edge on shortest s->v path
-    pq: IndexPQ<f32>,                   // priority queue of vertices
 }
 
 /// The DijkstraAllPairsSP represents a data type for solving the
@@ -20,6 +19,12 @@ pub struct DijkstraAllPairsSP {
 
 impl DijkstraSP {
     pub fn new(g: &dyn IEWDigraph, s: usize) -> Self {
+        Self::with_pq::<IndexPQ<f32>>(g, s)
+    }
+
+    /// Computes the shortest path tree with the priority queue P,
+    /// e.g. IndexPQ, IndexPairingHeap or IndexFibonacciHeap
+    pub fn with_pq<P: IndexMinPQ<f32>>(g: &dyn IEWDigraph, s: usize) -> Self {
         for e in g.edges() {
             if e.weight() < 0.0 {
                 panic!("edge {} has negative weight", e.to_string());
@@ -30,15 +35,14 @@ impl DijkstraSP {
         let mut sp = Self {
             dist_to: vec![f32::MAX; gv],
             edge_to: vec![None; gv],
-            pq: IndexPQ::new_min_pq(gv),
         };
+        let mut pq = P::with_capacity(gv); // priority queue of vertices
 
         sp.dist_to[s] = 0.0;
-        let _ = sp.pq.enqueue(s, sp.dist_to[s]);
-        while !sp.pq.is_empty() {
-            let v = sp.pq.dequeue().unwrap();
+        pq.enqueue(s, sp.dist_to[s]);
+        while let Some(v) = pq.dequeue() {
             for e in g.adj(v) {
-                sp.relax(e);
+                sp.relax(&mut pq, e);
             }
         }
 
@@ -46,16 +50,16 @@ impl DijkstraSP {
     }
 
     // relax edge e and update pq if changed
-    fn relax(&mut self, e: &DirectedEdge) {
+    fn relax<P: IndexMinPQ<f32>>(&mut self, pq: &mut P, e: &DirectedEdge) {
         let v = e.from();
         let w = e.to();
         if self.dist_to[w] > self.dist_to[v] + e.weight() {
             self.dist_to[w] = self.dist_to[v] + e.weight();
             self.edge_to[w] = Some(*e);
-            if self.pq.contains(w) {
-                let _ = self.pq.decrease_key(w, self.dist_to[w]);
+            if pq.contains(w) {
+                pq.decrease_key(w, self.dist_to[w]);
             } else {
-                let _ = self.pq.enqueue(w, self.dist_to[w]);
+                pq.enqueue(w, self.dist_to[w]);
             }
         }
     }
@@ -0,0 +1,185 @@
+//! The DynamicCC answers the connectivity queries of an undirected
+//! graph whose edges are added and removed over time, offline: all the
+//! operations are recorded first, then solve answers all the queries
+//! at once.
+//!
+//! A union-find handles the insertions only, it can not split a set
+//! when an edge is removed. But offline, the whole life of every edge
+//! is known: edge v-w exists while answering the queries from..to.
+//!
+//! Segment tree over time: the leaves are the queries 0..q, every node
+//! covers a range of the queries. The life of an edge is split into
+//! O(log q) nodes whose ranges cover it exactly, like the range query
+//! of a segment tree. Then a DFS over the tree adds the edges of a
+//! node when entering it (union), answers the query at a leaf, and
+//! removes them when leaving it (rollback), so at every leaf the
+//! union-find holds exactly the edges alive at that query.
+//!
+//! RollbackUF undoes the unions in O(1), without path compression a
+//! find takes O(log n), so solve takes O((m log q + q) log n) time,
+//! where m is the number of insertions.
+
+use crate::common::{RollbackUF, UnionFind};
+use std::collections::HashMap;
+
+/// Offline dynamic connectivity
+pub struct DynamicCC {
+    n: usize,
+    // alive[(v, w)] = the queries recorded when the copies of v-w
+    // not removed yet were added
+    alive: HashMap<(usize, usize), Vec<usize>>,
+    // (v, w, from, to): v-w exists while answering the queries from..to
+    lives: Vec<(usize, usize, usize, usize)>,
+    queries: Vec<(usize, usize)>,
+}
+
+impl DynamicCC {
+    /// Initializes a graph of n vertices with no edges
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            alive: HashMap::new(),
+            lives: Vec::new(),
+            queries: Vec::new(),
+        }
+    }
+
+    /// number of vertices
+    #[allow(non_snake_case)]
+    pub fn V(&self) -> usize {
+        self.n
+    }
+
+    /// add edge v-w, parallel edges are allowed
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        let from = self.queries.len();
+        self.alive.entry(key(v, w)).or_default().push(from);
+    }
+
+    /// remove edge v-w, one copy of it if parallel
+    ///
+    /// # Panics
+    ///
+    /// if there is no edge v-w
+    pub fn remove_edge(&mut self, v: usize, w: usize) {
+        let from = self
+            .alive
+            .get_mut(&key(v, w))
+            .and_then(|froms| froms.pop())
+            .unwrap_or_else(|| panic!("no edge {}-{}", v, w));
+        let to = self.queries.len();
+        self.lives.push((v, w, from, to));
+    }
+
+    /// Records the query: are v and w connected?
+    /// Returns the index of its answer in solve
+    pub fn query(&mut self, v: usize, w: usize) -> usize {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.queries.push((v, w));
+        self.queries.len() - 1
+    }
+
+    /// number of queries recorded
+    pub fn queries(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Answers all the queries, in the order they are recorded
+    pub fn solve(&self) -> Vec<bool> {
+        let q = self.queries.len();
+        let mut answers = vec![false; q];
+        if q == 0 {
+            return answers;
+        }
+
+        // the edges not removed live until the last query
+        let lives = self.lives.iter().cloned().chain(
+            self.alive
+                .iter()
+                .flat_map(|(&(v, w), froms)| froms.iter().map(move |&from| (v, w, from, q))),
+        );
+        let mut tree = vec![Vec::new(); 4 * q];
+        for (v, w, from, to) in lives {
+            if from < to {
+                insert(&mut tree, 1, 0, q, from, to, (v, w));
+            }
+        }
+
+        let mut uf = RollbackUF::new(self.n);
+        self.dfs(&tree, 1, 0, q, &mut uf, &mut answers);
+        answers
+    }
+}
+
+impl DynamicCC {
+    /// node covers the queries lo..hi
+    fn dfs(
+        &self,
+        tree: &[Vec<(usize, usize)>],
+        node: usize,
+        lo: usize,
+        hi: usize,
+        uf: &mut RollbackUF,
+        answers: &mut [bool],
+    ) {
+        let snapshot = uf.snapshot();
+        for &(v, w) in tree[node].iter() {
+            uf.union(v, w);
+        }
+
+        if hi - lo == 1 {
+            let (v, w) = self.queries[lo];
+            answers[lo] = uf.connected(v, w);
+        } else {
+            let mid = lo + (hi - lo) / 2;
+            self.dfs(tree, 2 * node, lo, mid, uf, answers);
+            self.dfs(tree, 2 * node + 1, mid, hi, uf, answers);
+        }
+
+        uf.rollback(snapshot);
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(
+            v < self.n,
+            "vertex {} is not between 0 and {}",
+            v,
+            self.n.saturating_sub(1)
+        );
+    }
+}
+
+/// adds edge e to the nodes covering from..to exactly,
+/// node covers the queries lo..hi
+fn insert(
+    tree: &mut [Vec<(usize, usize)>],
+    node: usize,
+    lo: usize,
+    hi: usize,
+    from: usize,
+    to: usize,
+    e: (usize, usize),
+) {
+    if to <= lo || hi <= from {
+        return;
+    }
+    if from <= lo && hi <= to {
+        tree[node].push(e);
+        return;
+    }
+    let mid = lo + (hi - lo) / 2;
+    insert(tree, 2 * node, lo, mid, from, to, e);
+    insert(tree, 2 * node + 1, mid, hi, from, to, e);
+}
+
+/// v-w and w-v are the same edge
+fn key(v: usize, w: usize) -> (usize, usize) {
+    if v <= w {
+        (v, w)
+    } else {
+        (w, v)
+    }
+}
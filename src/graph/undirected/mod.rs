@@ -10,6 +10,8 @@
 //! | single source paths              |    DepthFirstPaths (util/paths.rs)      |
 //! | single source shortest paths     |    BreadthFirstPaths (util/paths.rs)    |
 //! | connectivity                     |    CC (cc.rs)                           |
+//! | dynamic connectivity (offline)   |    DynamicCC (dynamic_cc.rs)            |
+//! | connectivity (multithreaded)     |    ParallelCC (parallel_cc.rs)          |
 //! | cycle detection                  |    Cycle (bipartite)                 |
 //! | two-color (bipartite)            |    TwoColor (bipartite)              |
 //!
@@ -18,14 +20,18 @@ pub use cc::CC;
 pub use cycle::Cycle;
 pub use dfs::DepthFirstSearch;
 pub use dfs2::NonRecursiveDFS;
+pub use dynamic_cc::DynamicCC;
 pub use graph::Graph;
+pub use parallel_cc::ParallelCC;
 
 mod bipartite;
 mod cc;
 mod cycle;
 mod dfs;
 mod dfs2;
+mod dynamic_cc;
 mod graph;
+mod parallel_cc;
 
 use crate::ll::linked_list::Iter;
 pub trait IGraph {
@@ -0,0 +1,93 @@
+//! The ParallelCC computes the connected components of an undirected
+//! graph given as a slice of edges, by threads sharing a lock-free
+//! union-find (ConcurrentUF): the edges are split into chunks, every
+//! thread unions the edges of its chunk.
+//!
+//! The component identifiers are the same as CC's: numbered from 0 in
+//! the order of the smallest vertex of each component.
+
+use crate::common::ConcurrentUF;
+use crate::graph::IGraph;
+use std::thread;
+
+/// Connected components, computed in parallel
+pub struct ParallelCC {
+    count: usize,     // number of connected components
+    id: Vec<usize>,   // id[v] = id of connected component containing v
+    size: Vec<usize>, // size[id] = number of vertices in given component
+}
+
+impl ParallelCC {
+    /// Computes the components of the graph of n vertices and the
+    /// edges, by the threads
+    ///
+    /// # Panics
+    ///
+    /// if threads is 0, or a vertex of the edges is not less than n
+    pub fn new(n: usize, edges: &[(usize, usize)], threads: usize) -> Self {
+        assert!(threads > 0, "no thread");
+        let uf = ConcurrentUF::new(n);
+        let chunk = edges.len().div_ceil(threads).max(1);
+        thread::scope(|s| {
+            for chunk in edges.chunks(chunk) {
+                let uf = &uf;
+                s.spawn(move || {
+                    for &(v, w) in chunk {
+                        uf.union(v, w);
+                    }
+                });
+            }
+        });
+
+        // root -> component id, by the smallest vertex
+        let mut ids = vec![usize::MAX; n];
+        let mut id = vec![0; n];
+        let mut size = Vec::new();
+        for v in 0..n {
+            let root = uf.find(v);
+            if ids[root] == usize::MAX {
+                ids[root] = size.len();
+                size.push(0);
+            }
+            id[v] = ids[root];
+            size[id[v]] += 1;
+        }
+
+        Self {
+            count: size.len(),
+            id,
+            size,
+        }
+    }
+
+    /// Computes the components of g, by the threads
+    pub fn from_graph(g: &dyn IGraph, threads: usize) -> Self {
+        let mut edges = Vec::with_capacity(g.E());
+        for v in 0..g.V() {
+            // every edge once
+            edges.extend(g.adj(v).filter(|&&w| v <= w).map(|&w| (v, w)));
+        }
+        Self::new(g.V(), &edges, threads)
+    }
+
+    /// are v and w connected?
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    /// number of connected components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// component identifier for v
+    /// ( between 0 and count()-1 )
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Returns the number of vertices in the connected component containing vertex *v*.
+    pub fn size(&self, v: usize) -> usize {
+        self.size[self.id[v]]
+    }
+}
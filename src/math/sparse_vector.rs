@@ -27,12 +27,21 @@ impl SparseVector {
     /// Sets the ith coordinate of this vector to the specified value.
     pub fn put(&mut self, i: usize, v: f64) {
         if v == 0.0 {
-            self.st.delete(&i);
+            self.st.remove(&i);
         } else {
             self.st.insert(i, v);
         }
     }
 
+    /// Adds v to the ith coordinate of this vector.
+    fn add_to(&mut self, i: usize, v: f64) {
+        let x = self.st.entry(i).or_insert(0.0);
+        *x += v;
+        if *x == 0.0 {
+            self.st.remove(&i);
+        }
+    }
+
     /// Returns the ith coordinate of this vector
     pub fn get(&self, i: usize) -> f64 {
         *self.st.get(&i).unwrap_or(&0.0)
@@ -53,16 +62,16 @@ impl SparseVector {
         if self.d != that.d {
             Err(Err::Dimension)
         } else {
-            let keys = if self.nnz() <= that.nnz() {
-                self.st.keys()
+            let (small, large) = if self.nnz() <= that.nnz() {
+                (self, that)
             } else {
-                that.st.keys()
+                (that, self)
             };
 
-            let sum = keys.iter().fold(0.0, |acc, &i| {
-                let delta = match (self.st.get(i), that.st.get(i)) {
-                    (Some(a), Some(b)) => a * b,
-                    _ => 0.0,
+            let sum = small.st.iter().fold(0.0, |acc, (i, a)| {
+                let delta = match large.st.get(i) {
+                    Some(b) => a * b,
+                    None => 0.0,
                 };
                 acc + delta
             });
@@ -80,8 +89,8 @@ impl SparseVector {
     /// Returns the scalar-vector product of this vector with the specified scalar.
     pub fn scale(&self, alpha: f64) -> Self {
         let mut c = Self::new(self.d);
-        for &i in self.st.keys() {
-            c.put(i, alpha * self.get(i));
+        for (&i, v) in self.st.iter() {
+            c.put(i, alpha * v);
         }
         c
     }
@@ -92,8 +101,8 @@ impl Add for SparseVector {
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut c = self;
-        for &i in rhs.st.keys() {
-            c.put(i, c.get(i) + rhs.get(i));
+        for (&i, v) in rhs.st.iter() {
+            c.add_to(i, *v);
         }
         c
     }
@@ -104,8 +113,8 @@ impl Sub for SparseVector {
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut c = self;
-        for &i in rhs.st.keys() {
-            c.put(i, c.get(i) - rhs.get(i));
+        for (&i, v) in rhs.st.iter() {
+            c.add_to(i, -v);
         }
         c
     }
@@ -113,10 +122,9 @@ impl Sub for SparseVector {
 
 impl ToString for SparseVector {
     fn to_string(&self) -> String {
-        let keys = self.st.keys();
-        let mut v = Vec::with_capacity(keys.len());
-        for &i in keys {
-            v.push(format!("({}, {})", i, self.get(i)));
+        let mut v = Vec::with_capacity(self.nnz());
+        for (i, x) in self.st.iter() {
+            v.push(format!("({}, {})", i, x));
         }
         v.join("")
     }
@@ -125,8 +133,8 @@ impl ToString for SparseVector {
 impl Clone for SparseVector {
     fn clone(&self) -> Self {
         let mut c = Self::new(self.d);
-        for &i in self.st.keys() {
-            c.put(i, self.get(i));
+        for (&i, &v) in self.st.iter() {
+            c.put(i, v);
         }
         c
     }
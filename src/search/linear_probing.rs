@@ -0,0 +1,181 @@
+//! Hashing with linear probing
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! 3.4 Hash Tables
+//!
+//! Another approach to implementing hashing is to store N key-value pairs
+//! in a hash table of size M > N, relying on empty entries in the table to
+//! help with collision resolution. Such methods are called open-addressing
+//! hashing methods. The simplest open-addressing method is called linear
+//! probing: when there is a collision (when we hash to a table index that
+//! is already occupied with a key different from the search key), then we
+//! just check the next entry in the table (by incrementing the index).
+//!
+//! Deletion. Setting the key's table position to None will not work,
+//! because that might prematurely terminate the search for a key that was
+//! inserted into the table later. So we reinsert into the table all of the
+//! keys in the cluster to the right of the deleted key.
+//!
+//! Proposition M.
+//! In a linear-probing hash table with M lists and N = αM keys, the
+//! average number of probes (under Assumption J) required is
+//! ~ 1/2 (1 + 1 / (1 - α)) and ~ 1/2 (1 + 1 / (1 - α)^2)
+//! for search hits and search misses (or inserts), respectively.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const INIT_CAPACITY: usize = 4;
+
+/// The LinearProbingHashST represents a symbol table of generic
+/// key-value pairs.
+/// It supports the usual put, get, contains,
+/// delete, len, and is-empty methods.
+/// It also provides a keys method for iterating over all of the keys.
+/// A symbol table implements the *associative array* abstraction:
+/// when associating a value with a key that is already in the symbol table,
+/// the convention is to replace the old value with the new value.
+/// This implementation uses a linear probing hash table. It requires that
+/// the key type implements the Hash and Eq traits.
+/// The expected time per put, contains, or delete
+/// operation is constant, subject to the uniform hashing assumption.
+/// The table doubles when it becomes half full, and halves when it falls
+/// to one-eighth full, so the load factor N/M stays between 1/8 and 1/2.
+pub struct LinearProbingHashST<K, V> {
+    // number of key-value pairs in the symbol table
+    n: usize,
+    // the table, size M
+    table: Vec<Option<(K, V)>>,
+}
+
+impl<K, V> LinearProbingHashST<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Initializes an empty symbol table with the specified initial capacity.
+    pub fn new(capacity: usize) -> Self {
+        let m = capacity.max(1);
+        let mut table = Vec::with_capacity(m);
+        table.resize_with(m, || None);
+        Self { n: 0, table }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the fraction of the table occupied, N/M
+    pub fn load_factor(&self) -> f64 {
+        self.n as f64 / self.table.len() as f64
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let m = self.table.len();
+        let mut i = self.hash(key);
+        while let Some((k, v)) = &self.table[i] {
+            if k == key {
+                return Some(v);
+            }
+            i = (i + 1) % m;
+        }
+        None
+    }
+
+    /// Inserts the key-value pair into the symbol table, overwriting
+    /// the old value with the new value if the key is already in the
+    /// symbol table.
+    pub fn put(&mut self, key: K, val: V) {
+        // double table size if 50% full
+        if self.n >= self.table.len() / 2 {
+            self.resize(2 * self.table.len());
+        }
+
+        let m = self.table.len();
+        let mut i = self.hash(&key);
+        while let Some((k, v)) = &mut self.table[i] {
+            if *k == key {
+                *v = val;
+                return;
+            }
+            i = (i + 1) % m;
+        }
+        self.table[i] = Some((key, val));
+        self.n += 1;
+    }
+
+    /// Removes the key and associated value from the symbol table
+    /// (if the key is in the symbol table).
+    pub fn delete(&mut self, key: &K) {
+        let m = self.table.len();
+
+        // find position i of key
+        let mut i = self.hash(key);
+        loop {
+            match &self.table[i] {
+                None => return,
+                Some((k, _)) if k == key => break,
+                _ => i = (i + 1) % m,
+            }
+        }
+
+        // delete key and associated value
+        self.table[i] = None;
+        self.n -= 1;
+
+        // rehash all keys in same cluster
+        i = (i + 1) % m;
+        while let Some((k, v)) = self.table[i].take() {
+            self.n -= 1;
+            self.put(k, v);
+            i = (i + 1) % m;
+        }
+
+        // halves size of array if it's 12.5% full or less
+        if self.table.len() > INIT_CAPACITY && self.n <= self.table.len() / 8 {
+            self.resize(self.table.len() / 2);
+        }
+    }
+
+    /// Returns all keys in the symbol table
+    pub fn keys(&self) -> Vec<&K> {
+        self.table.iter().flatten().map(|(k, _)| k).collect()
+    }
+
+    /// resizes the hash table to the given capacity by re-hashing all of the keys
+    fn resize(&mut self, capacity: usize) {
+        let mut tmp = Self::new(capacity);
+        for (k, v) in self.table.drain(..).flatten() {
+            tmp.put(k, v);
+        }
+        *self = tmp;
+    }
+
+    /// hash value between 0 and m-1
+    fn hash(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() % self.table.len() as u64) as usize
+    }
+}
+
+impl<K, V> Default for LinearProbingHashST<K, V>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new(INIT_CAPACITY)
+    }
+}
@@ -1 +1,8 @@
 pub mod binary;
+mod linear_probing;
+mod separate_chaining;
+mod skip_list;
+
+pub use linear_probing::LinearProbingHashST;
+pub use separate_chaining::SeparateChainingHashST;
+pub use skip_list::SkipList;
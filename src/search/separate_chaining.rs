@@ -0,0 +1,153 @@
+//! Hashing with separate chaining
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! 3.4 Hash Tables
+//!
+//! A hash function converts keys into array indices. The second component
+//! of a hashing algorithm is collision resolution: a strategy for handling
+//! the case when two or more keys to be inserted hash to the same index.
+//! A straightforward and general approach to collision resolution is to
+//! build, for each of the M array indices, a linked list of the key-value
+//! pairs whose keys hash to that index.
+//!
+//! Proposition K.
+//! In a separate-chaining hash table with M lists and N keys, the
+//! probability (under Assumption J) that the number of keys in a list
+//! is within a small constant factor of N/M is extremely close to 1.
+//!
+//! Property L.
+//! In a separate-chaining hash table with M lists and N keys, the number
+//! of compares (equality tests) for search miss and insert is ~N/M.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const INIT_CAPACITY: usize = 4;
+
+/// The SeparateChainingHashST represents a symbol table of generic
+/// key-value pairs.
+/// It supports the usual put, get, contains,
+/// delete, len, and is-empty methods.
+/// It also provides a keys method for iterating over all of the keys.
+/// A symbol table implements the *associative array* abstraction:
+/// when associating a value with a key that is already in the symbol table,
+/// the convention is to replace the old value with the new value.
+/// This implementation uses a separate chaining hash table. It requires that
+/// the key type implements the Hash and Eq traits.
+/// The expected time per put, contains, or delete
+/// operation is constant, subject to the uniform hashing assumption.
+/// The table doubles when the average list length reaches 10, and halves
+/// when it falls to 2, so the load factor N/M stays between 2 and 10.
+pub struct SeparateChainingHashST<K, V> {
+    // number of key-value pairs
+    n: usize,
+    // array of linked-list symbol tables
+    st: Vec<Vec<(K, V)>>,
+}
+
+impl<K, V> SeparateChainingHashST<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Initializes an empty symbol table with m chains
+    pub fn new(m: usize) -> Self {
+        let m = m.max(1);
+        let mut st = Vec::with_capacity(m);
+        st.resize_with(m, Vec::new);
+        Self { n: 0, st }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the average length of the chains, N/M
+    pub fn load_factor(&self) -> f64 {
+        self.n as f64 / self.st.len() as f64
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let i = self.hash(key);
+        self.st[i].iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Inserts the key-value pair into the symbol table, overwriting
+    /// the old value with the new value if the key is already in the
+    /// symbol table.
+    pub fn put(&mut self, key: K, val: V) {
+        // double table size if average length of list >= 10
+        if self.n >= 10 * self.st.len() {
+            self.resize(2 * self.st.len());
+        }
+
+        let i = self.hash(&key);
+        match self.st[i].iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = val,
+            None => {
+                self.st[i].push((key, val));
+                self.n += 1;
+            }
+        }
+    }
+
+    /// Removes the key and associated value from the symbol table
+    /// (if the key is in the symbol table).
+    pub fn delete(&mut self, key: &K) {
+        let i = self.hash(key);
+        if let Some(j) = self.st[i].iter().position(|(k, _)| k == key) {
+            self.st[i].swap_remove(j);
+            self.n -= 1;
+        }
+
+        // halve table size if average length of list <= 2
+        if self.st.len() > INIT_CAPACITY && self.n <= 2 * self.st.len() {
+            self.resize(self.st.len() / 2);
+        }
+    }
+
+    /// Returns all keys in the symbol table
+    pub fn keys(&self) -> Vec<&K> {
+        self.st.iter().flatten().map(|(k, _)| k).collect()
+    }
+
+    /// resize the hash table to have the given number of chains,
+    /// rehashing all of the keys
+    fn resize(&mut self, chains: usize) {
+        let mut tmp = Self::new(chains);
+        for (k, v) in self.st.drain(..).flatten() {
+            let i = tmp.hash(&k);
+            tmp.st[i].push((k, v));
+        }
+        tmp.n = self.n;
+        *self = tmp;
+    }
+
+    /// hash value between 0 and m-1
+    fn hash(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() % self.st.len() as u64) as usize
+    }
+}
+
+impl<K, V> Default for SeparateChainingHashST<K, V>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new(INIT_CAPACITY)
+    }
+}
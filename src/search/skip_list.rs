@@ -0,0 +1,289 @@
+//! Skip list
+//!
+//! A skip list is a sorted linked list with express lanes. Every node
+//! is in the list of level 0, and a node in the list of level i is also
+//! in the list of level i + 1 with probability 1/2, so the list of level
+//! i + 1 skips about half of the nodes of level i.
+//!
+//!   level 2: head ------------------> 30 ---------------------> nil
+//!   level 1: head ------> 10 -------> 30 -------> 50 ---------> nil
+//!   level 0: head -> 5 -> 10 -> 20 -> 30 -> 40 -> 50 -> 60 ---> nil
+//!
+//! Search starts at the top level of head, goes right while the next key
+//! is less than the key, then goes one level down, until level 0.
+//! The expected number of levels is ~ lg N, and the expected number of
+//! steps on every level is 2, so search, insert and delete take expected
+//! time proportional to log N, like a randomized BST.
+
+use crate::tree::binary::bst::BSTree;
+use crate::tree::binary::entry::Entry;
+use std::ptr::NonNull;
+
+const MAX_LEVEL: usize = 32;
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+/// The SkipList represents an ordered symbol table of generic key-value pairs.
+pub struct SkipList<K, V> {
+    head: [Link<K, V>; MAX_LEVEL],
+    // number of levels in use
+    level: usize,
+    len: usize,
+}
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    next: Vec<Link<K, V>>,
+}
+
+pub struct Iter<'a, K, V> {
+    x: Option<&'a Node<K, V>>,
+    remaining: usize,
+}
+
+impl<K, V> SkipList<K, V> {
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of levels in use
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Gets an iterator over the entries, sorted by key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            x: self.head[0].map(|x| unsafe { &*x.as_ptr() }),
+            remaining: self.len,
+        }
+    }
+
+    // next node of prev on level, prev is None for head
+    fn next(&self, prev: Link<K, V>, level: usize) -> Link<K, V> {
+        match prev {
+            None => self.head[level],
+            Some(x) => unsafe { x.as_ref().next[level] },
+        }
+    }
+
+    fn set_next(&mut self, prev: Link<K, V>, level: usize, x: Link<K, V>) {
+        match prev {
+            None => self.head[level] = x,
+            Some(mut p) => unsafe { p.as_mut().next[level] = x },
+        }
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Is every level sorted, and a sublist of the level below?
+    pub fn is_valid(&self) -> bool {
+        let keys = |level: usize| {
+            let mut keys = Vec::new();
+            let mut x = self.head[level];
+            while let Some(node) = x {
+                let node = unsafe { &*node.as_ptr() };
+                keys.push(&node.key);
+                x = node.next[level];
+            }
+            keys
+        };
+
+        let mut below = keys(0);
+        if below.len() != self.len || self.head[self.level..].iter().any(|x| x.is_some()) {
+            return false;
+        }
+        for level in 0..self.level {
+            let cur = keys(level);
+            if !cur.windows(2).all(|w| w[0] < w[1]) || !cur.iter().all(|k| below.contains(k)) {
+                return false;
+            }
+            below = cur;
+        }
+        true
+    }
+
+    /// for every level, the last node with key less than key, None for head
+    fn preds(&self, key: &K) -> [Link<K, V>; MAX_LEVEL] {
+        let mut preds = [None; MAX_LEVEL];
+        let mut x = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next(x, level) {
+                if unsafe { &next.as_ref().key } < key {
+                    x = Some(next);
+                } else {
+                    break;
+                }
+            }
+            preds[level] = x;
+        }
+        preds
+    }
+
+    fn find(&self, key: &K) -> Link<K, V> {
+        let preds = self.preds(key);
+        self.next(preds[0], 0)
+            .filter(|x| unsafe { &x.as_ref().key } == key)
+    }
+
+    /// inserts the key which is not in the list, returns the new node
+    fn insert_new(&mut self, key: K, val: V) -> NonNull<Node<K, V>> {
+        let preds = self.preds(&key);
+        let level = random_level();
+        // preds above the levels in use are head
+        let next = (0..level).map(|i| self.next(preds[i], i)).collect();
+        let x = Box::leak(Box::new(Node { key, val, next })).into();
+        for (i, &prev) in preds.iter().enumerate().take(level) {
+            self.set_next(prev, i, Some(x));
+        }
+        self.level = self.level.max(level);
+        self.len += 1;
+        x
+    }
+}
+
+impl<K, V> BSTree<K, V> for SkipList<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V) {
+        match self.find(&key) {
+            Some(mut x) => unsafe { x.as_mut().val = val },
+            None => {
+                self.insert_new(key, val);
+            }
+        }
+    }
+
+    fn delete(&mut self, key: &K) {
+        let _ = self.remove(key);
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|x| unsafe { &(*x.as_ptr()).val })
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find(key).map(|x| unsafe { &mut (*x.as_ptr()).val })
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.find(&key) {
+            Some(x) => {
+                let Node { key, val, .. } = unsafe { &mut *x.as_ptr() };
+                Entry::occupied(key, val)
+            }
+            None => Entry::vacant(key, move |key, val| {
+                let x = self.insert_new(key, val);
+                unsafe { &mut (*x.as_ptr()).val }
+            }),
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let preds = self.preds(key);
+        let x = self
+            .next(preds[0], 0)
+            .filter(|x| unsafe { &x.as_ref().key } == key)?;
+        let x = unsafe { Box::from_raw(x.as_ptr()) };
+        for (i, &next) in x.next.iter().enumerate() {
+            self.set_next(preds[i], i, next);
+        }
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+        Some(x.val)
+    }
+
+    fn min(&self) -> Option<&K> {
+        self.head[0].map(|x| unsafe { &(*x.as_ptr()).key })
+    }
+
+    fn max(&self) -> Option<&K> {
+        let mut x = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next(x, level) {
+                x = Some(next);
+            }
+        }
+        x.map(|x| unsafe { &(*x.as_ptr()).key })
+    }
+
+    fn succ(&self, key: &K) -> Option<&K> {
+        self.find(key)
+            .and_then(|x| unsafe { x.as_ref().next[0] })
+            .map(|x| unsafe { &(*x.as_ptr()).key })
+    }
+
+    fn pred(&self, key: &K) -> Option<&K> {
+        let preds = self.preds(key);
+        self.next(preds[0], 0)
+            .filter(|x| unsafe { &x.as_ref().key } == key)
+            .and(preds[0])
+            .map(|x| unsafe { &(*x.as_ptr()).key })
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self {
+            head: [None; MAX_LEVEL],
+            level: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        let mut x = self.head[0];
+        while let Some(node) = x {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            x = node.next[0];
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.x?;
+        self.x = x.next[0].map(|x| unsafe { &*x.as_ptr() });
+        self.remaining -= 1;
+        Some((&x.key, &x.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// level of a new node, level i + 1 with probability 1/2^i
+fn random_level() -> usize {
+    let mut level = 1;
+    while level < MAX_LEVEL && rand::random::<bool>() {
+        level += 1;
+    }
+    level
+}
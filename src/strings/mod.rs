@@ -7,7 +7,9 @@ pub mod msd;
 pub mod palindrome;
 pub mod quick3;
 mod tries;
+mod tries_arena;
 mod tst;
+mod tst_arena;
 
 pub use alphabet::Alphabet;
 pub use count::Count;
@@ -16,4 +18,6 @@ pub use lsd::LSD;
 pub use msd::MSD;
 pub use quick3::{Quick3String, Quick3Way};
 pub use tries::TrieST;
+pub use tries_arena::ArenaTrieST;
 pub use tst::TST;
+pub use tst_arena::ArenaTST;
@@ -0,0 +1,245 @@
+//! TrieST with the nodes in an Arena
+//!
+//! The nodes are linked by index handles instead of pointers:
+//!   - dropping the trie frees all nodes at once, no recursion in
+//!     drop (the other walks still recurse, one call per character)
+//!   - the nodes are close to each other in memory
+//!
+//! See tries.rs for the algorithm.
+
+use crate::common;
+use crate::common::{Arena, Handle, Queue};
+
+const R: usize = 256;
+
+/// The same 256-way trie as TrieST, with the same API
+pub struct ArenaTrieST<T> {
+    nodes: Arena<Node<T>>,
+    root: Option<Handle>,
+    n: usize,
+}
+
+struct Node<T> {
+    val: Option<T>,
+    next: Vec<Option<Handle>>,
+}
+
+impl<T> ArenaTrieST<T> {
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &str) -> Option<&T> {
+        get_dth(&self.nodes, self.root, key, 0).and_then(|x| self.nodes[x].val.as_ref())
+    }
+
+    /// Inserts the key-value pair into the symbol table, overwriting
+    /// the old value with the new value if the key is already in the
+    /// symbol table. If the value is None, this effectively
+    /// deletes the key from the symbol table.
+    pub fn put(&mut self, key: &str, val: Option<T>) {
+        if val.is_none() {
+            self.delete(key);
+        } else {
+            let root = self.root;
+            self.root = Some(self.put_dth(root, key, val, 0));
+        }
+    }
+
+    /// Removes the key from the set if the key is present
+    pub fn delete(&mut self, key: &str) {
+        let root = self.root;
+        self.root = self.delete_dth(root, key, 0);
+    }
+
+    /// Returns all keys in the symbol table
+    pub fn keys(&self) -> Queue<String> {
+        self.keys_with_prefix("")
+    }
+
+    /// Returns all of the keys in the set that start with *prefix*
+    pub fn keys_with_prefix(&self, prefix: &str) -> Queue<String> {
+        let mut results = Queue::default();
+        let x = get_dth(&self.nodes, self.root, prefix, 0);
+        let mut prefix = prefix.to_string();
+        collect_prefix(&self.nodes, x, &mut prefix, &mut results);
+        results
+    }
+
+    /// Returns all of the keys in the symbol table that match *pattern*,
+    /// where the character '.' is interpreted as a wildcard character.
+    pub fn keys_that_match(&self, pattern: &str) -> Queue<String> {
+        let mut results = Queue::default();
+        let mut prefix = String::new();
+        collect_match(&self.nodes, self.root, &mut prefix, pattern, &mut results);
+        results
+    }
+
+    /// Returns the string in the symbol table that is the longest prefix of *query*,
+    /// or None, if no such string.
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let length = longest_prefix_of_dth(&self.nodes, self.root, query, 0, -1);
+        if length == -1 {
+            None
+        } else {
+            Some(&query[0..length as usize])
+        }
+    }
+
+    fn put_dth(&mut self, x: Option<Handle>, key: &str, val: Option<T>, d: usize) -> Handle {
+        let x = x.unwrap_or_else(|| self.nodes.alloc(Node::new(None)));
+
+        if d == key.len() {
+            if self.nodes[x].val.is_none() {
+                self.n += 1;
+            }
+            self.nodes[x].val = val;
+        } else {
+            let i = common::util::byte_at(key, d);
+            let next = self.nodes[x].next[i];
+            let next = self.put_dth(next, key, val, d + 1);
+            self.nodes[x].next[i] = Some(next);
+        }
+
+        x
+    }
+
+    fn delete_dth(&mut self, x: Option<Handle>, key: &str, d: usize) -> Option<Handle> {
+        let x = x?;
+        if d == key.len() {
+            let val = self.nodes[x].val.take();
+            if val.is_some() {
+                self.n -= 1;
+            }
+        } else {
+            let i = common::util::byte_at(key, d);
+            let next = self.nodes[x].next[i];
+            self.nodes[x].next[i] = self.delete_dth(next, key, d + 1);
+        }
+
+        // remove subtrie rooted at x if it is completely empty
+        let node = &self.nodes[x];
+        if node.val.is_some() || node.next.iter().any(|it| it.is_some()) {
+            return Some(x);
+        }
+
+        // x.val is None and next all None, just release x itself
+        self.nodes.free(x);
+        None
+    }
+}
+
+fn get_dth<T>(nodes: &Arena<Node<T>>, x: Option<Handle>, key: &str, d: usize) -> Option<Handle> {
+    x.and_then(|x| {
+        if d == key.len() {
+            Some(x)
+        } else {
+            let i = common::util::byte_at(key, d);
+            get_dth(nodes, nodes[x].next[i], key, d + 1)
+        }
+    })
+}
+
+fn collect_prefix<T>(
+    nodes: &Arena<Node<T>>,
+    x: Option<Handle>,
+    prefix: &mut String,
+    results: &mut Queue<String>,
+) {
+    if let Some(x) = x {
+        if nodes[x].val.is_some() {
+            results.enqueue(prefix.to_string());
+        }
+        for c in 0..R {
+            prefix.push(c as u8 as char);
+            collect_prefix(nodes, nodes[x].next[c], prefix, results);
+            let _ = prefix.pop();
+        }
+    }
+}
+
+fn collect_match<T>(
+    nodes: &Arena<Node<T>>,
+    x: Option<Handle>,
+    prefix: &mut String,
+    pattern: &str,
+    results: &mut Queue<String>,
+) {
+    if let Some(x) = x {
+        let d = prefix.len();
+        if d == pattern.len() && nodes[x].val.is_some() {
+            results.enqueue(prefix.clone());
+        }
+        if d == pattern.len() {
+            return;
+        }
+        let i = common::util::byte_at(pattern, d);
+        if i == b'.' as usize {
+            for ch in 0..R {
+                prefix.push(ch as u8 as char);
+                collect_match(nodes, nodes[x].next[ch], prefix, pattern, results);
+                prefix.pop();
+            }
+        } else {
+            prefix.push(i as u8 as char);
+            collect_match(nodes, nodes[x].next[i], prefix, pattern, results);
+            prefix.pop();
+        }
+    }
+}
+
+// returns the length of the longest string key in the subtrie
+// rooted at x that is a prefix of the query string,
+// assuming the first d character match and we have already
+// found a prefix match of given length (-1 if no such match)
+fn longest_prefix_of_dth<T>(
+    nodes: &Arena<Node<T>>,
+    x: Option<Handle>,
+    query: &str,
+    d: usize,
+    mut length: i32,
+) -> i32 {
+    if let Some(x) = x {
+        if nodes[x].val.is_some() {
+            length = d as i32;
+        }
+        if d == query.len() {
+            return length;
+        }
+        let i = common::util::byte_at(query, d);
+        longest_prefix_of_dth(nodes, nodes[x].next[i], query, d + 1, length)
+    } else {
+        length
+    }
+}
+
+impl<T> Default for ArenaTrieST<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Arena::default(),
+            root: None,
+            n: 0,
+        }
+    }
+}
+
+impl<T> Node<T> {
+    fn new(val: Option<T>) -> Self {
+        Self {
+            val,
+            next: vec![None; R],
+        }
+    }
+}
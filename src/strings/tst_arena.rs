@@ -0,0 +1,290 @@
+//! TST with the nodes in an Arena
+//!
+//! The nodes are linked by index handles instead of pointers:
+//!   - dropping the trie frees all nodes at once, no recursion in
+//!     drop (the other walks still recurse, one call per character)
+//!   - the nodes are close to each other in memory
+//!
+//! See tst.rs for the algorithm.
+
+use crate::common;
+use crate::common::{Arena, Handle, Queue};
+use std::cmp::Ordering;
+
+/// The same ternary search trie as TST, with the same API
+pub struct ArenaTST<T> {
+    nodes: Arena<Node<T>>,
+    root: Option<Handle>,
+    n: usize,
+}
+
+struct Node<T> {
+    c: usize,
+    subtries: [Option<Handle>; 3],
+    val: Option<T>,
+}
+
+impl<T> ArenaTST<T> {
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns all keys in the symbol table as an Queue.
+    pub fn keys(&self) -> Queue<String> {
+        let mut queue = Queue::default();
+        let mut prefix = String::new();
+        collect_prefix(&self.nodes, self.root, &mut prefix, &mut queue);
+        queue
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &str) -> Option<&T> {
+        if key.is_empty() {
+            None
+        } else {
+            get_dth(&self.nodes, self.root, key, 0).and_then(|x| self.nodes[x].val.as_ref())
+        }
+    }
+
+    /// Inserts the key-value pair into the symbol table, overwriting the old value
+    /// with the new value if the key is already in the symbol table.
+    /// If the value is None, this effectively deletes the key from the symbol table.
+    pub fn put(&mut self, key: &str, val: Option<T>) {
+        if !self.contains(key) {
+            self.n += 1;
+        } else if val.is_none() {
+            self.n -= 1;
+        }
+        self.root = put_dth(&mut self.nodes, self.root, key, val, 0);
+    }
+
+    /// Returns all of the keys in the set that start with prefix.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Queue<String> {
+        let mut queue = Queue::default();
+        if !prefix.is_empty() {
+            let x = get_dth(&self.nodes, self.root, prefix, 0);
+            if let Some(x) = x {
+                let mut prefix = prefix.to_string();
+                if self.nodes[x].val.is_some() {
+                    queue.enqueue(prefix.clone());
+                }
+                collect_prefix(&self.nodes, self.nodes[x].mid(), &mut prefix, &mut queue);
+            }
+        }
+        queue
+    }
+
+    /// Returns the string in the symbol table that is the longest prefix of
+    /// query, or None, if no such string.
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let mut length = -1;
+        let mut x = self.root;
+        let mut i = 0;
+        while x.is_some() && i < query.len() {
+            let p = &self.nodes[x.unwrap()];
+            let c = common::util::byte_at(query, i);
+            x = match c.cmp(&p.c) {
+                Ordering::Less => p.left(),
+                Ordering::Greater => p.right(),
+                Ordering::Equal => {
+                    i += 1;
+                    if p.val.is_some() {
+                        length = i as i32;
+                    }
+                    p.mid()
+                }
+            };
+        }
+        if length == -1 {
+            None
+        } else {
+            query.get(0..length as usize)
+        }
+    }
+
+    /// Returns all of the keys in the symbol table that match pattern,
+    /// where the character '.' is interpreted as a wildcard character.
+    pub fn keys_that_match(&self, pattern: &str) -> Queue<String> {
+        let mut results = Queue::default();
+        let mut prefix = String::new();
+        collect_match(
+            &self.nodes,
+            self.root,
+            &mut prefix,
+            0,
+            pattern,
+            &mut results,
+        );
+        results
+    }
+}
+
+// return subtrie corresponding to given key
+fn get_dth<T>(nodes: &Arena<Node<T>>, x: Option<Handle>, key: &str, d: usize) -> Option<Handle> {
+    x.and_then(|x| {
+        let c = common::util::byte_at(key, d);
+        let node = &nodes[x];
+        match c.cmp(&node.c) {
+            Ordering::Less => get_dth(nodes, node.left(), key, d),
+            Ordering::Greater => get_dth(nodes, node.right(), key, d),
+            Ordering::Equal => {
+                if d < key.len() - 1 {
+                    get_dth(nodes, node.mid(), key, d + 1)
+                } else {
+                    Some(x)
+                }
+            }
+        }
+    })
+}
+
+fn put_dth<T>(
+    nodes: &mut Arena<Node<T>>,
+    x: Option<Handle>,
+    key: &str,
+    val: Option<T>,
+    d: usize,
+) -> Option<Handle> {
+    let c = common::util::byte_at(key, d);
+    let x = x.unwrap_or_else(|| nodes.alloc(Node::new(c)));
+    match c.cmp(&nodes[x].c) {
+        Ordering::Less => {
+            let p = put_dth(nodes, nodes[x].left(), key, val, d);
+            nodes[x].set_left(p);
+        }
+        Ordering::Greater => {
+            let p = put_dth(nodes, nodes[x].right(), key, val, d);
+            nodes[x].set_right(p);
+        }
+        Ordering::Equal => {
+            if d < key.len() - 1 {
+                let p = put_dth(nodes, nodes[x].mid(), key, val, d + 1);
+                nodes[x].set_mid(p);
+            } else {
+                nodes[x].val = val;
+            }
+        }
+    }
+
+    if nodes[x].is_empty() {
+        // x.val is None and subtries all None, just release x itself
+        nodes.free(x);
+        None
+    } else {
+        Some(x)
+    }
+}
+
+fn collect_prefix<T>(
+    nodes: &Arena<Node<T>>,
+    x: Option<Handle>,
+    prefix: &mut String,
+    results: &mut Queue<String>,
+) {
+    if let Some(x) = x {
+        let node = &nodes[x];
+        collect_prefix(nodes, node.left(), prefix, results);
+        if node.val.is_some() {
+            let mut prefix = prefix.clone();
+            prefix.push(node.c as u8 as char);
+            results.enqueue(prefix);
+        }
+        prefix.push(node.c as u8 as char);
+        collect_prefix(nodes, node.mid(), prefix, results);
+        let _ = prefix.pop();
+        collect_prefix(nodes, node.right(), prefix, results);
+    }
+}
+
+fn collect_match<T>(
+    nodes: &Arena<Node<T>>,
+    x: Option<Handle>,
+    prefix: &mut String,
+    i: usize,
+    pattern: &str,
+    results: &mut Queue<String>,
+) {
+    if let Some(x) = x {
+        let node = &nodes[x];
+        let c = common::util::byte_at(pattern, i);
+        if c == b'.' as usize || c < node.c {
+            collect_match(nodes, node.left(), prefix, i, pattern, results);
+        }
+        if c == b'.' as usize || c == node.c {
+            if i == pattern.len() - 1 && node.val.is_some() {
+                prefix.push(node.c as u8 as char);
+                results.enqueue(prefix.clone());
+                let _ = prefix.pop();
+            }
+            if i < pattern.len() - 1 {
+                prefix.push(node.c as u8 as char);
+                collect_match(nodes, node.mid(), prefix, i + 1, pattern, results);
+                let _ = prefix.pop();
+            }
+        }
+        if c == b'.' as usize || c > node.c {
+            collect_match(nodes, node.right(), prefix, i, pattern, results);
+        }
+    }
+}
+
+impl<T> Default for ArenaTST<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Arena::default(),
+            root: None,
+            n: 0,
+        }
+    }
+}
+
+impl<T> Node<T> {
+    fn new(c: usize) -> Self {
+        Self {
+            c,
+            subtries: [None; 3],
+            val: None,
+        }
+    }
+
+    // test subtrie whether completely empty
+    fn is_empty(&self) -> bool {
+        self.val.is_none() && self.subtries.iter().all(|it| it.is_none())
+    }
+
+    // left subtries
+    fn left(&self) -> Option<Handle> {
+        self.subtries[0]
+    }
+
+    // middle subtries
+    fn mid(&self) -> Option<Handle> {
+        self.subtries[1]
+    }
+
+    // right subtries
+    fn right(&self) -> Option<Handle> {
+        self.subtries[2]
+    }
+
+    fn set_left(&mut self, x: Option<Handle>) {
+        self.subtries[0] = x;
+    }
+
+    fn set_mid(&mut self, x: Option<Handle>) {
+        self.subtries[1] = x;
+    }
+
+    fn set_right(&mut self, x: Option<Handle>) {
+        self.subtries[2] = x;
+    }
+}
@@ -0,0 +1,503 @@
+//! Left-leaning red-black tree with the nodes in an Arena
+//!
+//! The same algorithm as rb2, but the nodes are kept in a
+//! common::Arena and linked by index handles instead of pointers:
+//!   - dropping the tree frees all nodes at once, no recursion
+//!   - the nodes are close to each other, a search touches less cache
+//!     lines for large symbol tables
+//!   - a link takes 4 bytes instead of 8
+//!
+//! A node has no parent link, the rebalancing is done on the way
+//! back of the recursion, like rb2.
+//!
+//! It is a standalone map: it implements BSTree only, not OrderedST
+//! (rank, select, range), and check::check does not walk it, it has
+//! its own check with the same error messages.
+
+use crate::common::{Arena, Handle};
+use crate::tree::binary::bst::BSTree;
+use crate::tree::binary::entry::Entry;
+use crate::tree::binary::node::Color;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// The ArenaTree represents an ordered symbol table of generic key-value pairs.
+pub struct ArenaTree<K, V> {
+    nodes: Arena<Node<K, V>>,
+    root: Option<Handle>,
+}
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    left: Option<Handle>,
+    right: Option<Handle>,
+    color: Color,
+}
+
+pub struct Iter<'a, K, V> {
+    nodes: &'a Arena<Node<K, V>>,
+    stack: Vec<Handle>,
+    remaining: usize,
+}
+
+impl<K, V> ArenaTree<K, V> {
+    /// Initializes an empty tree with room for capacity nodes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Arena::with_capacity(capacity),
+            root: None,
+        }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(Handle, usize)> = self.root.map(|x| (x, 1)).into_iter().collect();
+        while let Some((x, d)) = stack.pop() {
+            height = height.max(d);
+            let node = &self.nodes[x];
+            stack.extend(node.left.map(|l| (l, d + 1)));
+            stack.extend(node.right.map(|r| (r, d + 1)));
+        }
+        height
+    }
+
+    /// Removes all key-value pairs
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    /// Gets an iterator over the entries, sorted by key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            nodes: &self.nodes,
+            stack: Vec::new(),
+            remaining: self.len(),
+        };
+        iter.push_left(self.root);
+        iter
+    }
+
+    fn is_red(&self, x: Option<Handle>) -> bool {
+        x.is_some_and(|x| self.nodes[x].color == Color::Red)
+    }
+
+    fn left(&self, x: Handle) -> Option<Handle> {
+        self.nodes[x].left
+    }
+
+    fn right(&self, x: Handle) -> Option<Handle> {
+        self.nodes[x].right
+    }
+
+    fn min_node(&self, mut x: Handle) -> Handle {
+        while let Some(l) = self.left(x) {
+            x = l;
+        }
+        x
+    }
+
+    /*
+         h                          x
+      /      \                  /       \
+     A        R(x)     =>     R(h)       C
+             /    \         /      \
+            B      C       A        B
+    */
+    /// make a right-leaning link lean to the left
+    fn rotate_left(&mut self, h: Handle) -> Handle {
+        let x = self.right(h).unwrap();
+        self.nodes[h].right = self.left(x);
+        self.nodes[x].left = Some(h);
+        self.nodes[x].color = self.nodes[h].color;
+        self.nodes[h].color = Color::Red;
+        x
+    }
+
+    /// make a left-leaning link lean to the right
+    fn rotate_right(&mut self, h: Handle) -> Handle {
+        let x = self.left(h).unwrap();
+        self.nodes[h].left = self.right(x);
+        self.nodes[x].right = Some(h);
+        self.nodes[x].color = self.nodes[h].color;
+        self.nodes[h].color = Color::Red;
+        x
+    }
+
+    /// flip the colors of a node and its two children
+    fn flip_colors(&mut self, h: Handle) {
+        for x in [Some(h), self.left(h), self.right(h)].iter().flatten() {
+            let node = &mut self.nodes[*x];
+            node.color = node.color.flip();
+        }
+    }
+
+    /// restore red-black tree invariant
+    fn balance(&mut self, mut h: Handle) -> Handle {
+        if self.is_red(self.right(h)) && !self.is_red(self.left(h)) {
+            h = self.rotate_left(h);
+        }
+        if self.is_red(self.left(h)) && self.is_red(self.left(h).and_then(|l| self.left(l))) {
+            h = self.rotate_right(h);
+        }
+        if self.is_red(self.left(h)) && self.is_red(self.right(h)) {
+            self.flip_colors(h);
+        }
+        h
+    }
+
+    /// Assuming that h is red and both h.left and h.left.left
+    /// are black, make h.left or one of its children red.
+    fn move_red_left(&mut self, mut h: Handle) -> Handle {
+        self.flip_colors(h);
+        let r = self.right(h).unwrap();
+        if self.is_red(self.left(r)) {
+            self.nodes[h].right = Some(self.rotate_right(r));
+            h = self.rotate_left(h);
+            self.flip_colors(h);
+        }
+        h
+    }
+
+    /// Assuming that h is red and both h.right and h.right.left
+    /// are black, make h.right or one of its children red.
+    fn move_red_right(&mut self, mut h: Handle) -> Handle {
+        self.flip_colors(h);
+        let l = self.left(h).unwrap();
+        if self.is_red(self.left(l)) {
+            h = self.rotate_right(h);
+            self.flip_colors(h);
+        }
+        h
+    }
+
+    /// unlink the min node rooted at h without freeing it, the node is stored in min
+    fn take_min(&mut self, mut h: Handle, min: &mut Option<Handle>) -> Option<Handle> {
+        let l = match self.left(h) {
+            None => {
+                *min = Some(h);
+                return self.right(h);
+            }
+            Some(l) => l,
+        };
+        if !self.is_red(Some(l)) && !self.is_red(self.left(l)) {
+            h = self.move_red_left(h);
+        }
+        let l = self.left(h).unwrap();
+        self.nodes[h].left = self.take_min(l, min);
+        Some(self.balance(h))
+    }
+}
+
+impl<K, V> ArenaTree<K, V>
+where
+    K: Ord,
+{
+    /// Checks that the tree is a left-leaning red-black BST, returns an
+    /// error naming the key of the first offending node, with the same
+    /// messages as check::check.
+    pub fn check(&self) -> Result<(), String>
+    where
+        K: Debug,
+    {
+        // black height of the subtree rooted at x
+        fn visit<K: Ord + Debug, V>(
+            t: &ArenaTree<K, V>,
+            x: Option<Handle>,
+            lo: Option<&K>,
+            hi: Option<&K>,
+        ) -> Result<usize, String> {
+            let x = match x {
+                None => return Ok(0),
+                Some(x) => x,
+            };
+            let node = &t.nodes[x];
+            let key = &node.key;
+            if lo.is_some_and(|lo| key <= lo) || hi.is_some_and(|hi| key >= hi) {
+                return Err(format!("key {:?} violates symmetric order", key));
+            }
+
+            // children first, the error names the lowest offending node
+            let l = visit(t, node.left, lo, Some(key))?;
+            let r = visit(t, node.right, Some(key), hi)?;
+
+            if t.is_red(Some(x)) && (t.is_red(node.left) || t.is_red(node.right)) {
+                return Err(format!("red {:?} has red child", key));
+            }
+            if t.is_red(node.right) {
+                return Err(format!("red link of {:?} leans right", key));
+            }
+            if l != r {
+                return Err(format!(
+                    "black heights of {:?} differ, left {}, right {}",
+                    key, l, r
+                ));
+            }
+            Ok(if t.is_red(Some(x)) { l } else { l + 1 })
+        }
+
+        if let Some(root) = self.root {
+            if self.is_red(Some(root)) {
+                return Err(format!("root {:?} is red", self.nodes[root].key));
+            }
+        }
+        visit(self, self.root, None, None).map(|_| ())
+    }
+
+    fn find(&self, key: &K) -> Option<Handle> {
+        let mut x = self.root;
+        while let Some(h) = x {
+            x = match key.cmp(&self.nodes[h].key) {
+                Ordering::Less => self.left(h),
+                Ordering::Greater => self.right(h),
+                Ordering::Equal => return Some(h),
+            };
+        }
+        None
+    }
+
+    /// inserts the key which is not in the tree, returns the new node
+    fn insert_new(&mut self, key: K, val: V) -> Handle {
+        let mut x = None;
+        let root = self.root;
+        let root = self.put(root, key, val, &mut x);
+        self.nodes[root].color = Color::Black;
+        self.root = Some(root);
+        x.unwrap()
+    }
+
+    /// insert the element in the subtree rooted at h,
+    /// the node holding the element is stored in x
+    fn put(&mut self, h: Option<Handle>, key: K, val: V, x: &mut Option<Handle>) -> Handle {
+        let h = match h {
+            None => {
+                let leaf = self.nodes.alloc(Node {
+                    key,
+                    val,
+                    left: None,
+                    right: None,
+                    color: Color::Red,
+                });
+                *x = Some(leaf);
+                return leaf;
+            }
+            Some(h) => h,
+        };
+
+        match key.cmp(&self.nodes[h].key) {
+            Ordering::Equal => {
+                self.nodes[h].val = val;
+                *x = Some(h);
+            }
+            Ordering::Less => {
+                let l = self.left(h);
+                self.nodes[h].left = Some(self.put(l, key, val, x));
+            }
+            Ordering::Greater => {
+                let r = self.right(h);
+                self.nodes[h].right = Some(self.put(r, key, val, x));
+            }
+        }
+
+        self.balance(h)
+    }
+
+    /// delete the key in the subtree rooted at h, which has the key,
+    /// the deleted node is stored in x
+    fn delete(&mut self, mut h: Handle, key: &K, x: &mut Option<Handle>) -> Option<Handle> {
+        if key < &self.nodes[h].key {
+            let l = self.left(h).unwrap();
+            if !self.is_red(Some(l)) && !self.is_red(self.left(l)) {
+                h = self.move_red_left(h);
+            }
+            let l = self.left(h).unwrap();
+            self.nodes[h].left = self.delete(l, key, x);
+        } else {
+            if self.is_red(self.left(h)) {
+                h = self.rotate_right(h);
+            }
+            if key == &self.nodes[h].key && self.right(h).is_none() {
+                *x = Some(h);
+                return None;
+            }
+            let r = self.right(h).unwrap();
+            if !self.is_red(Some(r)) && !self.is_red(self.left(r)) {
+                h = self.move_red_right(h);
+            }
+            let r = self.right(h).unwrap();
+            if key == &self.nodes[h].key {
+                // 用右子树的最小节点代替 h
+                let mut min = None;
+                let right = self.take_min(r, &mut min);
+                let min = min.unwrap();
+                let (left, color) = (self.left(h), self.nodes[h].color);
+                let m = &mut self.nodes[min];
+                m.left = left;
+                m.right = right;
+                m.color = color;
+                *x = Some(h);
+                h = min;
+            } else {
+                self.nodes[h].right = self.delete(r, key, x);
+            }
+        }
+
+        Some(self.balance(h))
+    }
+}
+
+impl<K, V> BSTree<K, V> for ArenaTree<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V) {
+        self.insert_new(key, val);
+    }
+
+    fn delete(&mut self, key: &K) {
+        let _ = self.remove(key);
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|x| &self.nodes[x].val)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find(key).map(move |x| &mut self.nodes[x].val)
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.find(&key) {
+            Some(x) => {
+                let Node { key, val, .. } = &mut self.nodes[x];
+                Entry::occupied(key, val)
+            }
+            None => Entry::vacant(key, move |key, val| {
+                let x = self.insert_new(key, val);
+                &mut self.nodes[x].val
+            }),
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.find(key)?;
+        let root = self.root.unwrap();
+        // if both children of root are black, set root to red
+        if !self.is_red(self.left(root)) && !self.is_red(self.right(root)) {
+            self.nodes[root].color = Color::Red;
+        }
+
+        let mut x = None;
+        self.root = self.delete(root, key, &mut x);
+        if let Some(root) = self.root {
+            self.nodes[root].color = Color::Black;
+        }
+        x.map(|x| self.nodes.free(x).val)
+    }
+
+    fn min(&self) -> Option<&K> {
+        self.root.map(|x| &self.nodes[self.min_node(x)].key)
+    }
+
+    fn max(&self) -> Option<&K> {
+        let mut x = self.root?;
+        while let Some(r) = self.right(x) {
+            x = r;
+        }
+        Some(&self.nodes[x].key)
+    }
+
+    fn succ(&self, key: &K) -> Option<&K> {
+        let mut succ = None;
+        let mut x = self.root;
+        while let Some(h) = x {
+            x = match key.cmp(&self.nodes[h].key) {
+                Ordering::Less => {
+                    succ = Some(h);
+                    self.left(h)
+                }
+                Ordering::Greater => self.right(h),
+                Ordering::Equal => {
+                    let succ = self.right(h).map(|r| self.min_node(r)).or(succ);
+                    return succ.map(|x| &self.nodes[x].key);
+                }
+            };
+        }
+        None
+    }
+
+    fn pred(&self, key: &K) -> Option<&K> {
+        let mut pred = None;
+        let mut x = self.root;
+        while let Some(h) = x {
+            x = match key.cmp(&self.nodes[h].key) {
+                Ordering::Less => self.left(h),
+                Ordering::Greater => {
+                    pred = Some(h);
+                    self.right(h)
+                }
+                Ordering::Equal => {
+                    let mut pred = pred;
+                    let mut l = self.left(h);
+                    while let Some(p) = l {
+                        pred = Some(p);
+                        l = self.right(p);
+                    }
+                    return pred.map(|x| &self.nodes[x].key);
+                }
+            };
+        }
+        None
+    }
+}
+
+impl<K, V> Default for ArenaTree<K, V> {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut x: Option<Handle>) {
+        while let Some(h) = x {
+            self.stack.push(h);
+            x = self.nodes[h].left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.stack.pop()?;
+        let node = &self.nodes[x];
+        self.push_left(node.right);
+        self.remaining -= 1;
+        Some((&node.key, &node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ArenaTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -98,3 +98,332 @@ delta(z) = -1
 delta(y) = 0
 
  */
+
+use crate::tree::binary::check::Balance;
+use crate::tree::binary::{bst, Node, NodeQuery, Tree};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::mem;
+use std::ptr::NonNull;
+
+pub trait AvlTree<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V);
+    fn delete(&mut self, key: &K);
+    fn get(&self, key: &K) -> Option<&V>;
+    fn min(&self) -> Option<&K>;
+    fn max(&self) -> Option<&K>;
+    /// 查找后继元素
+    fn succ(&self, key: &K) -> Option<&K>;
+    /// 寻找前驱元素
+    fn pred(&self, key: &K) -> Option<&K>;
+    /// Is this a BST whose every subtree satisfies |δ(T)| ≤ 1?
+    fn is_balanced(&self) -> bool
+    where
+        K: Debug;
+}
+
+impl<K, V> AvlTree<K, V> for Tree<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V) {
+        let mut added = false;
+        let (root, _) = put(self.root, key, val, &mut added);
+        self.root = detach(root);
+        if added {
+            self.set_size(self.size() + 1);
+        }
+    }
+
+    fn delete(&mut self, key: &K) {
+        if unsafe { bst::find(self.root, key) }.is_some() {
+            let (root, _) = delete(self.root, key);
+            self.root = detach(root);
+            self.set_size(self.size() - 1);
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        unsafe { bst::find(self.root, key).and_then(|p| p.as_ref().val.as_ref()) }
+    }
+
+    fn min(&self) -> Option<&K> {
+        unsafe { bst::find_min(self.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn max(&self) -> Option<&K> {
+        unsafe { bst::find_max(self.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn succ(&self, key: &K) -> Option<&K> {
+        unsafe { bst::succ(self.root, key).map(|p| &p.as_ref().key) }
+    }
+
+    fn pred(&self, key: &K) -> Option<&K> {
+        unsafe { bst::pred(self.root, key).map(|p| &p.as_ref().key) }
+    }
+
+    fn is_balanced(&self) -> bool
+    where
+        K: Debug,
+    {
+        self.check(Balance::Avl).is_ok()
+    }
+}
+
+/// insert the element in the subtree rooted at h
+///
+/// returns the new subtree root, and whether the height of subtree grew
+fn put<K, V>(
+    h: Option<NonNull<Node<K, V>>>,
+    key: K,
+    val: V,
+    added: &mut bool,
+) -> (Option<NonNull<Node<K, V>>>, bool)
+where
+    K: Ord,
+{
+    let mut h = NodeQuery::new(h);
+    let d = match h.get_key() {
+        None => {
+            *added = true;
+            return (Some(Node::new_leaf(key, Some(val), None)), true);
+        }
+        Some(h_key) => match key.cmp(h_key) {
+            Ordering::Equal => {
+                h.set_entry((key, Some(val))); // update val
+                0
+            }
+            Ordering::Less => {
+                let (l, grew) = put(h.left().node, key, val, added);
+                h.set_left(l);
+                if grew {
+                    -1
+                } else {
+                    0
+                }
+            }
+            Ordering::Greater => {
+                let (r, grew) = put(h.right().node, key, val, added);
+                h.set_right(r);
+                if grew {
+                    1
+                } else {
+                    0
+                }
+            }
+        },
+    };
+
+    h.update_size();
+    if d == 0 {
+        return (h.node, false);
+    }
+    h.set_delta(h.delta() + d);
+    match h.delta() {
+        0 => (h.node, false),
+        1 | -1 => (h.node, true),
+        // 插入后修复，子树恢复插入前的高度
+        _ => (balance(h.node), false),
+    }
+}
+
+/// delete the element in the subtree rooted at h, the key must exist
+///
+/// returns the new subtree root, and whether the height of subtree shrunk
+fn delete<K, V>(h: Option<NonNull<Node<K, V>>>, key: &K) -> (Option<NonNull<Node<K, V>>>, bool)
+where
+    K: Ord,
+{
+    let mut h = NodeQuery::new(h);
+    let d = match key.cmp(h.get_key().unwrap()) {
+        Ordering::Less => {
+            let (l, shrunk) = delete(h.left().node, key);
+            h.set_left(l);
+            if shrunk {
+                1
+            } else {
+                0
+            }
+        }
+        Ordering::Greater => {
+            let (r, shrunk) = delete(h.right().node, key);
+            h.set_right(r);
+            if shrunk {
+                -1
+            } else {
+                0
+            }
+        }
+        Ordering::Equal => {
+            // 没有孩子，或者只有一个孩子，直接将 h "切下"
+            if h.left().is_none() || h.right().is_none() {
+                let child = if h.left().is_some() {
+                    h.left()
+                } else {
+                    h.right()
+                };
+                Node::release(h.node.unwrap());
+                return (child.node, true);
+            }
+            // 有两个孩子，用右子树中的最小值替换 h，然后将这一最小值"切掉"
+            let (r, min, shrunk) = delete_min(h.right().node);
+            swap_entry(h.node.unwrap(), min);
+            Node::release(min);
+            h.set_right(r);
+            if shrunk {
+                -1
+            } else {
+                0
+            }
+        }
+    };
+
+    h.update_size();
+    shrink(h.node, d)
+}
+
+/// detach the min node of the subtree rooted at h
+///
+/// returns the new subtree root, the min node, and whether the height of subtree shrunk
+#[allow(clippy::type_complexity)]
+fn delete_min<K, V>(
+    h: Option<NonNull<Node<K, V>>>,
+) -> (Option<NonNull<Node<K, V>>>, NonNull<Node<K, V>>, bool) {
+    let mut h = NodeQuery::new(h);
+    if h.left().is_none() {
+        return (h.right().node, h.node.unwrap(), true);
+    }
+
+    let (l, min, shrunk) = delete_min(h.left().node);
+    h.set_left(l);
+    h.update_size();
+    let (h, shrunk) = shrink(h.node, if shrunk { 1 } else { 0 });
+    (h, min, shrunk)
+}
+
+/// one branch of h shrunk, d is the change of delta(h)
+fn shrink<K, V>(h: Option<NonNull<Node<K, V>>>, d: i32) -> (Option<NonNull<Node<K, V>>>, bool) {
+    let mut h = NodeQuery::new(h);
+    if d == 0 {
+        return (h.node, false);
+    }
+    h.set_delta(h.delta() + d);
+    match h.delta() {
+        0 => (h.node, true),
+        1 | -1 => (h.node, false),
+        _ => {
+            let h = NodeQuery::new(balance(h.node));
+            // 兄弟子树的 delta 为 0 时，旋转后高度不变
+            (h.node, h.delta() == 0)
+        }
+    }
+}
+
+/// restore avl tree invariant, |delta(h)| = 2 => |delta(h)| ≤ 1
+fn balance<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    let mut h = NodeQuery::new(h);
+    match h.delta() {
+        2 => {
+            // rl lean => rr lean
+            if h.right().delta() < 0 {
+                h.set_right(rotate_right(h.right().node));
+            }
+            rotate_left(h.node)
+        }
+        -2 => {
+            // lr lean => ll lean
+            if h.left().delta() > 0 {
+                h.set_left(rotate_left(h.left().node));
+            }
+            rotate_right(h.node)
+        }
+        _ => h.node,
+    }
+}
+
+/*
+        x                        y
+      /   \                   /     \
+     a     y        =>       x       c
+          /  \             /   \
+         b    c           a     b
+
+delta(x)' = delta(x) - 1 - max(delta(y), 0)
+delta(y)' = delta(y) - 1 + min(delta(x)', 0)
+ */
+fn rotate_left<K, V>(x: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    let mut x = NodeQuery::new(x);
+    let mut y = x.right();
+    x.set_right(y.left().node);
+    y.set_left(x.node);
+    x.update_size();
+    y.update_size();
+    let x_delta = x.delta() - 1 - y.delta().max(0);
+    let y_delta = y.delta() - 1 + x_delta.min(0);
+    x.set_delta(x_delta);
+    y.set_delta(y_delta);
+    y.node
+}
+
+/*
+         y                  x
+      /     \            /     \
+     x       c   =>     a       y
+   /   \                      /   \
+  a     b                    b     c
+
+delta(y)' = delta(y) + 1 - min(delta(x), 0)
+delta(x)' = delta(x) + 1 + max(delta(y)', 0)
+ */
+fn rotate_right<K, V>(y: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    let mut y = NodeQuery::new(y);
+    let mut x = y.left();
+    y.set_left(x.right().node);
+    x.set_right(y.node);
+    y.update_size();
+    x.update_size();
+    let y_delta = y.delta() + 1 - x.delta().min(0);
+    let x_delta = x.delta() + 1 + y_delta.max(0);
+    y.set_delta(y_delta);
+    x.set_delta(x_delta);
+    x.node
+}
+
+fn swap_entry<K, V>(mut a: NonNull<Node<K, V>>, mut b: NonNull<Node<K, V>>) {
+    unsafe {
+        mem::swap(&mut a.as_mut().key, &mut b.as_mut().key);
+        mem::swap(&mut a.as_mut().val, &mut b.as_mut().val);
+    }
+}
+
+/// the root has no parent
+fn detach<K, V>(root: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    if let Some(mut root) = root {
+        unsafe { root.as_mut().parent = None }
+    }
+    root
+}
+
+#[test]
+fn t_rotate() {
+    use crate::tree::binary::traverse::PreOrderVisitor;
+
+    // ll lean
+    let mut tree = Tree::default();
+    for v in [3, 2, 1] {
+        AvlTree::insert(&mut tree, v, v);
+    }
+    assert_eq!(vec![2, 1, 3], unsafe { PreOrderVisitor::recursive(&tree) });
+
+    // lr lean
+    let mut tree = Tree::default();
+    for v in [3, 1, 2] {
+        AvlTree::insert(&mut tree, v, v);
+    }
+    assert_eq!(vec![2, 1, 3], unsafe { PreOrderVisitor::recursive(&tree) });
+    assert_eq!(Ok(()), tree.check(Balance::Avl));
+}
@@ -2,8 +2,10 @@
 //!   1. 所有左侧分支的值都小于本节点的值
 //!   2. 本节点的值小于所有右侧分支的值
 
+use crate::tree::binary::entry::Entry;
 use crate::tree::binary::{Node, NodeQuery, Tree};
 use std::cmp::Ordering;
+use std::mem;
 use std::ptr::NonNull;
 
 pub trait BSTree<K, V>
@@ -13,6 +15,11 @@ where
     fn insert(&mut self, key: K, val: V);
     fn delete(&mut self, key: &K);
     fn get(&self, key: &K) -> Option<&V>;
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+    /// Gets the entry of the key for in-place manipulation
+    fn entry(&mut self, key: K) -> Entry<'_, K, V>;
+    /// Removes the key, returns the value if the key was in the tree
+    fn remove(&mut self, key: &K) -> Option<V>;
     fn min(&self) -> Option<&K>;
     fn max(&self) -> Option<&K>;
     /// 查找后继元素
@@ -43,6 +50,21 @@ where
         unsafe { find(self.root, key).and_then(|p| p.as_ref().val.as_ref()) }
     }
 
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { find(self.root, key).and_then(|mut p| p.as_mut().val.as_mut()) }
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(self, key, insert_new)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let mut node = unsafe { find(self.root, key) }?;
+        let val = unsafe { node.as_mut().val.take() };
+        self.root = unsafe { delete(self.root, Some(node)) };
+        val
+    }
+
     fn min(&self) -> Option<&K> {
         unsafe { find_min(self.root).map(|p| &p.as_ref().key) }
     }
@@ -97,11 +119,24 @@ where
             node.as_mut().right = Some(x);
         }
         x.as_mut().parent = parent;
+        update_size_upward(parent);
     }
 
     Ok(x)
 }
 
+/// inserts the key which is not in the tree, used by Entry
+fn insert_new<K, V>(tree: &mut Tree<K, V>, key: K, val: V) -> NonNull<Node<K, V>>
+where
+    K: Ord,
+{
+    let x = unsafe { insert(tree.root, key, val) }.expect("key exists");
+    if tree.root.is_none() {
+        tree.root = Some(x);
+    }
+    x
+}
+
 /// # Safety
 ///
 /// This is highly unsafe, due to pointer
@@ -143,7 +178,7 @@ where
 /// # Safety
 ///
 /// This is highly unsafe, due to pointer
-unsafe fn succ<'a, K: 'a, V: 'a>(
+pub unsafe fn succ<'a, K: 'a, V: 'a>(
     p: Option<NonNull<Node<K, V>>>,
     mut key: &'a K,
 ) -> Option<NonNull<Node<K, V>>>
@@ -172,7 +207,7 @@ where
 /// # Safety
 ///
 /// This is highly unsafe, due to pointer
-unsafe fn pred<'a, K: 'a, V: 'a>(
+pub unsafe fn pred<'a, K: 'a, V: 'a>(
     node: Option<NonNull<Node<K, V>>>,
     mut key: &'a K,
 ) -> Option<NonNull<Node<K, V>>>
@@ -221,7 +256,10 @@ where
             x = px.as_ref().left;
         } else {
             let min = find_min(px.as_ref().right).unwrap();
-            NodeQuery::new(x).copy_entry(min);
+            // 与最小值交换entry，被删除的entry随min一起释放
+            let mut min = min;
+            mem::swap(&mut px.as_mut().key, &mut min.as_mut().key);
+            mem::swap(&mut px.as_mut().val, &mut min.as_mut().val);
             let min_parent = min.as_ref().parent;
             if min_parent != x {
                 let min_right = min.as_ref().right;
@@ -230,9 +268,9 @@ where
                 px.as_mut().right = min.as_ref().right;
             }
             if let Some(mut min_right) = min.as_ref().right {
-                let min_parent = min.as_ref().parent;
                 min_right.as_mut().parent = min_parent;
             }
+            update_size_upward(min_parent);
             Node::release(min);
             return root;
         }
@@ -250,6 +288,7 @@ where
         } else {
             root = x;
         }
+        update_size_upward(parent);
 
         Node::release(old_x.unwrap());
     }
@@ -274,6 +313,15 @@ where
     })
 }
 
+/// recalculate the subtree size of x and all its ancestors
+pub fn update_size_upward<K, V>(x: Option<NonNull<Node<K, V>>>) {
+    let mut nq = NodeQuery::new(x);
+    while nq.is_some() {
+        nq.update_size();
+        nq = nq.parent();
+    }
+}
+
 /// Returns the number of key-value pairs
 pub fn calc_size<K, V>(x: Option<NonNull<Node<K, V>>>) -> usize {
     x.map_or(0, |x| unsafe {
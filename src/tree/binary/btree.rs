@@ -0,0 +1,520 @@
+//! B-tree
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! 6.1 B-trees
+//!
+//! A B-tree of order M generalizes the 2-3 tree: every node holds up to
+//! M - 1 keys and M links, and every node except the root holds at least
+//! M / 2 - 1 keys. All leaves are at the same depth, so a search or an
+//! insertion in a B-tree of order M with N keys requires between
+//! log(M, N) and log(M/2, N) probes. A node fits in a cache line or a disk
+//! page, that's why B-trees are used for large sorted indexes.
+//!
+//! This implementation follows CLRS, splits and merges nodes on the way
+//! down, so insert and delete make a single pass from the root:
+//!   - insert, split a full node (M - 1 keys) before descending into it
+//!   - delete, make sure the node descended into has at least M / 2 keys,
+//!     by borrowing a key from a sibling, or merging with a sibling
+
+/*
+Insert 'K' into a B-tree of order 4, the full node (J L M) is split before
+descending into it, the middle key L moves up
+
+           D   H                         D   H   L
+        /    |    \           =>      /    |   |    \
+     A B C  E F G  J L M            A B C E F G J K  M
+
+*/
+
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+/// default order, a 2-3-4 tree
+const DEFAULT_ORDER: usize = 4;
+
+/// The BTree represents an ordered symbol table of generic
+/// key-value pairs.
+/// It supports the insert, get, contains, delete,
+/// len, and is-empty methods.
+/// It also provides a range method for scanning the keys in order.
+/// A symbol table implements the *associative array* abstraction:
+/// when associating a value with a key that is already in the symbol table,
+/// the convention is to replace the old value with the new value.
+/// The order M (the max number of links of a node) is chosen at runtime,
+/// and must be even, at least 4.
+/// The get, insert and delete operations take time proportional to
+/// M * log(M, N) in the worst case.
+pub struct BTree<K, V> {
+    root: Box<Node<K, V>>,
+    // max children per B-tree node = M
+    m: usize,
+    // height of the B-tree
+    height: usize,
+    // number of key-value pairs in the B-tree
+    n: usize,
+}
+
+// leaf if children is empty, otherwise children.len() == keys.len() + 1
+struct Node<K, V> {
+    keys: Vec<K>,
+    vals: Vec<V>,
+    children: Vec<Node<K, V>>,
+}
+
+/// An iterator over a sub-range of entries of BTree, sorted by key
+pub struct Range<'a, K, V> {
+    // path from root, (node, index of the next key)
+    stack: Vec<(&'a Node<K, V>, usize)>,
+    // the position of the first key out of range
+    end: Option<(*const Node<K, V>, usize)>,
+}
+
+impl<K, V> BTree<K, V>
+where
+    K: Ord,
+{
+    /// Initializes an empty B-tree of order m
+    pub fn new(m: usize) -> Self {
+        assert!(
+            m >= 4 && m.is_multiple_of(2),
+            "order of B-tree must be even, >= 4"
+        );
+        Self {
+            root: Box::new(Node::new()),
+            m,
+            height: 0,
+            n: 0,
+        }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the order of this B-tree
+    pub fn order(&self) -> usize {
+        self.m
+    }
+
+    /// Returns the height of this B-tree, the number of links from
+    /// the root to a leaf. A B-tree with only the root node has height 0.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut x = &*self.root;
+        loop {
+            match x.keys.binary_search(key) {
+                Ok(i) => return Some(&x.vals[i]),
+                Err(_) if x.is_leaf() => return None,
+                Err(i) => x = &x.children[i],
+            }
+        }
+    }
+
+    /// Inserts the key-value pair into the symbol table, overwriting
+    /// the old value with the new value if the key is already in the
+    /// symbol table.
+    pub fn insert(&mut self, key: K, val: V) {
+        if self.root.keys.len() == self.m - 1 {
+            // split root, the only way the tree grows taller
+            let old = std::mem::replace(&mut *self.root, Node::new());
+            self.root.children.push(old);
+            self.root.split_child(0);
+            self.height += 1;
+        }
+        if self.root.insert(key, val, self.m) {
+            self.n += 1;
+        }
+    }
+
+    /// Removes the key and associated value from the symbol table
+    /// (if the key is in the symbol table).
+    pub fn delete(&mut self, key: &K) {
+        if !self.contains(key) {
+            return;
+        }
+
+        self.root.delete(key, self.m / 2);
+        self.n -= 1;
+
+        if self.root.keys.is_empty() && !self.root.is_leaf() {
+            // root emptied by merge, the only way the tree shrinks
+            let child = self.root.children.pop().unwrap();
+            *self.root = child;
+            self.height -= 1;
+        }
+    }
+
+    /// Returns the smallest key
+    pub fn min(&self) -> Option<&K> {
+        let mut x = &*self.root;
+        while !x.is_leaf() {
+            x = &x.children[0];
+        }
+        x.keys.first()
+    }
+
+    /// Returns the largest key
+    pub fn max(&self) -> Option<&K> {
+        let mut x = &*self.root;
+        while !x.is_leaf() {
+            x = x.children.last().unwrap();
+        }
+        x.keys.last()
+    }
+
+    /// Gets an iterator over the entries of the B-tree, sorted by key
+    pub fn iter(&self) -> Range<'_, K, V> {
+        self.range(..)
+    }
+
+    /// Gets an iterator over a sub-range of entries in the B-tree
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let mut stack = Vec::new();
+        // the path to the first key in range
+        let mut x = &*self.root;
+        loop {
+            let i = match range.start_bound() {
+                Bound::Included(key) => x.keys.partition_point(|k| k < key),
+                Bound::Excluded(key) => x.keys.partition_point(|k| k <= key),
+                Bound::Unbounded => 0,
+            };
+            stack.push((x, i));
+            if x.is_leaf() {
+                break;
+            }
+            x = &x.children[i];
+        }
+
+        // the first key out of range, the deepest one on the path
+        let mut end = None;
+        if !matches!(range.end_bound(), Bound::Unbounded) {
+            let mut x = &*self.root;
+            loop {
+                let i = match range.end_bound() {
+                    Bound::Included(key) => x.keys.partition_point(|k| k <= key),
+                    Bound::Excluded(key) => x.keys.partition_point(|k| k < key),
+                    Bound::Unbounded => unreachable!(),
+                };
+                if i < x.keys.len() {
+                    end = Some((x as *const Node<K, V>, i));
+                }
+                if x.is_leaf() {
+                    break;
+                }
+                x = &x.children[i];
+            }
+        }
+
+        // empty range, lo > hi
+        let empty = match (range.start_bound(), range.end_bound()) {
+            (Bound::Included(lo), Bound::Included(hi)) => lo > hi,
+            (Bound::Included(lo), Bound::Excluded(hi))
+            | (Bound::Excluded(lo), Bound::Included(hi))
+            | (Bound::Excluded(lo), Bound::Excluded(hi)) => lo >= hi,
+            _ => false,
+        };
+        if empty {
+            stack.clear();
+        }
+
+        Range { stack, end }
+    }
+
+    /// Does every node hold between M / 2 - 1 and M - 1 keys (except the root),
+    /// in symmetric order, with all leaves at the same depth?
+    pub fn is_valid(&self) -> bool {
+        self.root.keys.len() < self.m
+            && self.root.is_valid(None, None, self.height, self.m, true)
+            && self.iter().count() == self.n
+    }
+}
+
+impl<K, V> Default for BTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new(DEFAULT_ORDER)
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (x, i) = self.stack.pop()?;
+            if i == x.keys.len() {
+                // done with this node, back to parent
+                continue;
+            }
+
+            if self.end == Some((x as *const Node<K, V>, i)) {
+                self.stack.clear();
+                return None;
+            }
+
+            self.stack.push((x, i + 1));
+            if !x.is_leaf() {
+                // leftmost path of the next subtree
+                let mut child = &x.children[i + 1];
+                loop {
+                    self.stack.push((child, 0));
+                    if child.is_leaf() {
+                        break;
+                    }
+                    child = &child.children[0];
+                }
+            }
+            return Some((&x.keys[i], &x.vals[i]));
+        }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            vals: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<K, V> Node<K, V>
+where
+    K: Ord,
+{
+    /// split the full child i, its middle key moves up into self
+    fn split_child(&mut self, i: usize) {
+        let child = &mut self.children[i];
+        let mid = child.keys.len() / 2;
+        let mut right = Node::new();
+        right.keys = child.keys.split_off(mid + 1);
+        right.vals = child.vals.split_off(mid + 1);
+        if !child.is_leaf() {
+            right.children = child.children.split_off(mid + 1);
+        }
+        let key = child.keys.pop().unwrap();
+        let val = child.vals.pop().unwrap();
+
+        self.keys.insert(i, key);
+        self.vals.insert(i, val);
+        self.children.insert(i + 1, right);
+    }
+
+    /// insert into a node not full, returns true if a new key added
+    fn insert(&mut self, key: K, val: V, m: usize) -> bool {
+        let mut x = self;
+        loop {
+            let mut i = match x.keys.binary_search(&key) {
+                Ok(i) => {
+                    x.vals[i] = val; // update val
+                    return false;
+                }
+                Err(i) => i,
+            };
+
+            if x.is_leaf() {
+                x.keys.insert(i, key);
+                x.vals.insert(i, val);
+                return true;
+            }
+
+            if x.children[i].keys.len() == m - 1 {
+                x.split_child(i);
+                match key.cmp(&x.keys[i]) {
+                    Ordering::Equal => {
+                        x.vals[i] = val;
+                        return false;
+                    }
+                    Ordering::Greater => i += 1,
+                    Ordering::Less => (),
+                }
+            }
+            x = &mut x.children[i];
+        }
+    }
+
+    /// delete the key from the subtree rooted at self, the key must exist,
+    /// and self has at least t keys (or self is root)
+    fn delete(&mut self, key: &K, t: usize) {
+        match self.keys.binary_search(key) {
+            Ok(i) if self.is_leaf() => {
+                self.keys.remove(i);
+                self.vals.remove(i);
+            }
+            Ok(i) => {
+                if self.children[i].keys.len() >= t {
+                    // replace by predecessor
+                    let (k, v) = self.children[i].delete_max(t);
+                    self.keys[i] = k;
+                    self.vals[i] = v;
+                } else if self.children[i + 1].keys.len() >= t {
+                    // replace by successor
+                    let (k, v) = self.children[i + 1].delete_min(t);
+                    self.keys[i] = k;
+                    self.vals[i] = v;
+                } else {
+                    // both children have t - 1 keys, merge them with the key
+                    self.merge(i);
+                    self.children[i].delete(key, t);
+                }
+            }
+            Err(i) => {
+                let i = self.fill(i, t);
+                self.children[i].delete(key, t);
+            }
+        }
+    }
+
+    fn delete_min(&mut self, t: usize) -> (K, V) {
+        if self.is_leaf() {
+            (self.keys.remove(0), self.vals.remove(0))
+        } else {
+            let i = self.fill(0, t);
+            self.children[i].delete_min(t)
+        }
+    }
+
+    fn delete_max(&mut self, t: usize) -> (K, V) {
+        if self.is_leaf() {
+            (self.keys.pop().unwrap(), self.vals.pop().unwrap())
+        } else {
+            let i = self.fill(self.children.len() - 1, t);
+            self.children[i].delete_max(t)
+        }
+    }
+
+    /// make sure child i has at least t keys before descending into it,
+    /// returns the index of the child (changed if merged with left sibling)
+    fn fill(&mut self, i: usize, t: usize) -> usize {
+        if self.children[i].keys.len() >= t {
+            i
+        } else if i > 0 && self.children[i - 1].keys.len() >= t {
+            self.borrow_from_left(i);
+            i
+        } else if i + 1 < self.children.len() && self.children[i + 1].keys.len() >= t {
+            self.borrow_from_right(i);
+            i
+        } else if i + 1 < self.children.len() {
+            self.merge(i);
+            i
+        } else {
+            self.merge(i - 1);
+            i - 1
+        }
+    }
+
+    /*
+       borrow from left sibling, rotate right
+
+             D                    C
+           /   \        =>      /   \
+        A B C   E             A B   D E
+    */
+    fn borrow_from_left(&mut self, i: usize) {
+        let (left, right) = self.children.split_at_mut(i);
+        let left = &mut left[i - 1];
+        let child = &mut right[0];
+
+        let k = std::mem::replace(&mut self.keys[i - 1], left.keys.pop().unwrap());
+        let v = std::mem::replace(&mut self.vals[i - 1], left.vals.pop().unwrap());
+        child.keys.insert(0, k);
+        child.vals.insert(0, v);
+        if !left.is_leaf() {
+            child.children.insert(0, left.children.pop().unwrap());
+        }
+    }
+
+    /*
+       borrow from right sibling, rotate left
+
+             B                    C
+           /   \        =>      /   \
+          A   C D E           A B   D E
+    */
+    fn borrow_from_right(&mut self, i: usize) {
+        let (left, right) = self.children.split_at_mut(i + 1);
+        let child = &mut left[i];
+        let right = &mut right[0];
+
+        let k = std::mem::replace(&mut self.keys[i], right.keys.remove(0));
+        let v = std::mem::replace(&mut self.vals[i], right.vals.remove(0));
+        child.keys.push(k);
+        child.vals.push(v);
+        if !right.is_leaf() {
+            child.children.push(right.children.remove(0));
+        }
+    }
+
+    /// merge child i, key i and child i + 1 into child i
+    fn merge(&mut self, i: usize) {
+        let right = self.children.remove(i + 1);
+        let k = self.keys.remove(i);
+        let v = self.vals.remove(i);
+        let child = &mut self.children[i];
+        child.keys.push(k);
+        child.vals.push(v);
+        child.keys.extend(right.keys);
+        child.vals.extend(right.vals);
+        child.children.extend(right.children);
+    }
+
+    fn is_valid(
+        &self,
+        lo: Option<&K>,
+        hi: Option<&K>,
+        height: usize,
+        m: usize,
+        root: bool,
+    ) -> bool {
+        let n = self.keys.len();
+        if n >= m || (!root && n < m / 2 - 1) || self.vals.len() != n {
+            return false;
+        }
+        if self.keys.windows(2).any(|w| w[0] >= w[1]) {
+            return false;
+        }
+        if lo.is_some_and(|lo| self.keys.first() <= Some(lo))
+            || hi.is_some_and(|hi| self.keys.last() >= Some(hi))
+        {
+            return false;
+        }
+
+        if height == 0 {
+            return self.is_leaf();
+        }
+        if self.children.len() != n + 1 {
+            return false;
+        }
+        self.children.iter().enumerate().all(|(i, child)| {
+            let lo = if i == 0 { lo } else { Some(&self.keys[i - 1]) };
+            let hi = if i == n { hi } else { Some(&self.keys[i]) };
+            child.is_valid(lo, hi, height - 1, m, false)
+        })
+    }
+}
@@ -10,6 +10,8 @@ use crate::common::binary_tree;
 use crate::tree::binary::builder::TreeBuilder;
 use crate::tree::binary::node::Node;
 use crate::tree::binary::tree::Tree;
+use crate::tree::binary::NodeQuery;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ptr::NonNull;
 
 pub trait BuildTreeInLevel<K, V> {
@@ -22,6 +24,71 @@ impl<K: std::str::FromStr, V> BuildTreeInLevel<K, V> for TreeBuilder {
     }
 }
 
+impl<K, V> Tree<K, V>
+where
+    K: ToString,
+{
+    /// Serializes the tree to the level notation read by BuildTreeInLevel,
+    /// the trailing "#" are omitted.
+    ///
+    /// The notation numbers nodes as in a complete binary tree, the
+    /// height of the tree must be less than the bits of usize.
+    pub fn to_level(&self) -> Vec<String> {
+        fn visit<K: ToString, V>(
+            x: Option<NonNull<Node<K, V>>>,
+            i: usize,
+            slots: &mut BTreeMap<usize, String>,
+        ) {
+            if let Some(node) = x {
+                let node = unsafe { node.as_ref() };
+                slots.insert(i, node.key.to_string());
+                if node.left.is_some() || node.right.is_some() {
+                    let l = i
+                        .checked_mul(2)
+                        .and_then(|i| i.checked_add(1))
+                        .expect("tree is too deep for the level notation");
+                    visit(node.left, l, slots);
+                    visit(node.right, l + 1, slots);
+                }
+            }
+        }
+
+        let mut slots = BTreeMap::new();
+        visit(self.root, 0, &mut slots);
+        from_heap(slots)
+    }
+}
+
+/// keys in heap order to the level notation, the children of "#" are
+/// omitted, build fills them when it reads the next key
+pub(crate) fn from_heap(slots: BTreeMap<usize, String>) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let last = match slots.keys().next_back() {
+        Some(&last) => last,
+        None => return tokens,
+    };
+
+    // the root, the keys and their children, the positions
+    // between them are children of "#"
+    let mut positions: BTreeSet<usize> = slots.keys().copied().collect();
+    for &i in slots.keys().take_while(|&&i| i < last / 2 + last % 2) {
+        positions.extend([binary_tree::left(i), binary_tree::right(i)]);
+    }
+    positions.insert(0);
+
+    let mut prev = None;
+    for &i in positions.range(..=last) {
+        let skipped = prev.map_or(i, |prev| i - prev - 1);
+        match slots.get(&i) {
+            Some(key) => tokens.push(key.clone()),
+            // "#" is not skipped by build
+            None => tokens.extend(std::iter::repeat_n("#".to_string(), skipped + 1)),
+        }
+        prev = Some(i);
+    }
+    tokens
+}
+
 // ?? why K: std::str::FromStr
 fn build<K: std::str::FromStr, V>(vec: &[&str]) -> Tree<K, V> {
     let tokens = expand_sharp(vec);
@@ -56,6 +123,10 @@ fn build<K: std::str::FromStr, V>(vec: &[&str]) -> Tree<K, V> {
         }
     }
 
+    // children always come after parent in level order
+    for node in aux.iter().rev().flatten() {
+        NodeQuery::new(Some(*node)).update_size();
+    }
     tree.set_size(tree_size);
 
     tree
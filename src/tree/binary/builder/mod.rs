@@ -1,4 +1,5 @@
 pub mod level;
+pub mod order;
 pub mod tournament;
 
 pub struct TreeBuilder;
@@ -0,0 +1,117 @@
+//! 由遍历序列重建tree
+//!
+//! A binary tree with distinct keys is determined by its inorder
+//! sequence together with its preorder or postorder sequence.
+//!
+//! For example: preorder [3, 9, 20, 15, 7], inorder [9, 3, 15, 20, 7]
+//!     3
+//!    / \
+//!   9  20
+//!     / \
+//!    15  7
+//!
+//! The first key of preorder is the root, it splits inorder into the
+//! left and right subtrees, and the next keys of preorder are the
+//! left subtree, then the right subtree. Postorder is read backwards:
+//! the root, the right subtree, then the left subtree.
+
+use crate::tree::binary::builder::TreeBuilder;
+use crate::tree::binary::node::Node;
+use crate::tree::binary::tree::Tree;
+use crate::tree::binary::NodeQuery;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ptr::NonNull;
+
+pub trait BuildTreeInOrder<K, V> {
+    fn build_from_pre_in(preorder: &[K], inorder: &[K]) -> Result<Tree<K, V>, String>;
+    fn build_from_post_in(postorder: &[K], inorder: &[K]) -> Result<Tree<K, V>, String>;
+}
+
+impl<K, V> BuildTreeInOrder<K, V> for TreeBuilder
+where
+    K: Clone + Eq + Hash + Debug,
+{
+    fn build_from_pre_in(preorder: &[K], inorder: &[K]) -> Result<Tree<K, V>, String> {
+        build(preorder.iter(), inorder, false)
+    }
+
+    fn build_from_post_in(postorder: &[K], inorder: &[K]) -> Result<Tree<K, V>, String> {
+        build(postorder.iter().rev(), inorder, true)
+    }
+}
+
+// roots: the roots of subtrees, in the order they are built
+fn build<'a, K, V, I>(roots: I, inorder: &'a [K], right_first: bool) -> Result<Tree<K, V>, String>
+where
+    K: Clone + Eq + Hash + Debug + 'a,
+    I: ExactSizeIterator<Item = &'a K>,
+{
+    if roots.len() != inorder.len() {
+        return Err(format!(
+            "lengths of sequences differ, {} and {}",
+            roots.len(),
+            inorder.len()
+        ));
+    }
+
+    let mut pos = HashMap::new();
+    for (i, key) in inorder.iter().enumerate() {
+        if pos.insert(key, i).is_some() {
+            return Err(format!("key {:?} is duplicated", key));
+        }
+    }
+
+    let mut builder = Builder {
+        roots,
+        pos,
+        right_first,
+    };
+    let mut tree = Tree::default();
+    tree.root = builder.build(0, inorder.len())?;
+    tree.set_size(inorder.len());
+    Ok(tree)
+}
+
+struct Builder<'a, K, I> {
+    roots: I,
+    pos: HashMap<&'a K, usize>,
+    right_first: bool,
+}
+
+impl<'a, K, I> Builder<'a, K, I>
+where
+    K: Clone + Eq + Hash + Debug + 'a,
+    I: Iterator<Item = &'a K>,
+{
+    /// builds the subtree of inorder[lo..hi]
+    fn build<V>(&mut self, lo: usize, hi: usize) -> Result<Option<NonNull<Node<K, V>>>, String> {
+        if lo == hi {
+            return Ok(None);
+        }
+
+        // lengths are equal, every call takes one key
+        let key = self.roots.next().unwrap();
+        let i = match self.pos.get(key) {
+            Some(&i) if lo <= i && i < hi => i,
+            Some(_) => return Err(format!("key {:?} is out of its subtree", key)),
+            None => return Err(format!("key {:?} is not in inorder", key)),
+        };
+
+        let mut h = NodeQuery::new(Some(Node::new_key(key.clone())));
+        // the subtree is released by the drop of Tree, if fails
+        let mut subtree = Tree::default();
+        subtree.root = h.node;
+        if self.right_first {
+            h.set_right(self.build(i + 1, hi)?);
+            h.set_left(self.build(lo, i)?);
+        } else {
+            h.set_left(self.build(lo, i)?);
+            h.set_right(self.build(i + 1, hi)?);
+        }
+        h.update_size();
+        subtree.root = None;
+        Ok(h.node)
+    }
+}
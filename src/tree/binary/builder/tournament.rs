@@ -129,6 +129,81 @@ where
     node
 }
 
+/// 锦标赛树 of the players 0..n, for merging streams lazily
+///
+/// Unlike the tree built by build_tournament_tree, the nodes hold the
+/// players (indexes) instead of the keys, and who wins a match is told
+/// by the caller, so the keys need not be Copy nor Minimal, and may
+/// change: when the key of a player changes, replay the matches on the
+/// path from its leaf to the root, O(log2(n)).
+pub struct Tournament {
+    tree: Tree<usize, ()>,
+    leaves: Vec<NonNull<Node<usize, ()>>>,
+}
+
+impl Tournament {
+    /// Plays the tournament of the players 0..n, beats(a, b) tells if
+    /// player a wins player b
+    pub fn new<F>(n: usize, mut beats: F) -> Self
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let leaves: Vec<NonNull<Node<usize, ()>>> = (0..n).map(Node::new_key).collect();
+        let mut nodes = leaves.clone();
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(2)
+                .map(|chunk| match *chunk {
+                    [t1, t2] => {
+                        let (a, b) = unsafe { (t1.as_ref().key, t2.as_ref().key) };
+                        let winner = if beats(b, a) { b } else { a };
+                        let mut nq = NodeQuery::new(Some(Node::new_key(winner)));
+                        nq.set_left(Some(t1));
+                        nq.set_right(Some(t2));
+                        nq.node.unwrap()
+                    }
+                    [t] => t,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        let mut tree = Tree::default();
+        tree.root = nodes.first().cloned();
+        tree.set_size((2 * n).saturating_sub(1));
+        Self { tree, leaves }
+    }
+
+    /// Returns the number of players
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns the winner of the tournament
+    pub fn winner(&self) -> Option<usize> {
+        NodeQuery::new(self.tree.root).get_key().copied()
+    }
+
+    /// The key of player i changed, replays its matches
+    pub fn replay<F>(&mut self, i: usize, mut beats: F)
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let mut nq = NodeQuery::new_parent(Some(self.leaves[i]));
+        while nq.is_some() {
+            let a = *nq.left_key().unwrap();
+            let b = *nq.right_key().unwrap();
+            let winner = if beats(b, a) { b } else { a };
+            nq.set_entry((winner, None));
+            nq = nq.parent();
+        }
+    }
+}
+
 #[test]
 fn t_build_tree() {
     /*
@@ -0,0 +1,158 @@
+//! Structural integrity check of the binary trees
+//!
+//! All trees are checked for
+//!   - symmetric order: keys in the left subtree < key < keys in the right subtree
+//!   - parent links: the parent of a child is the node, the root has no parent
+//!   - subtree sizes: size = 1 + size(left) + size(right)
+//!
+//! and optionally for the invariants of a balanced tree
+//!   - RedBlack: the root is black, a red node has no red child, and every
+//!     path from a node to a null link has the same number of black nodes
+//!   - LeftLeaningRedBlack: RedBlack, and red links lean left
+//!   - Avl: |δ| ≤ 1 for every node, and δ = height(right) - height(left)
+
+use crate::tree::binary::node::Color;
+use crate::tree::binary::{Node, NodeQuery, Tree};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ptr::NonNull;
+
+/// The balance invariants to check
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Balance {
+    /// plain BST
+    None,
+    /// rb
+    RedBlack,
+    /// rb2
+    LeftLeaningRedBlack,
+    /// avl
+    Avl,
+}
+
+impl<K, V> Tree<K, V>
+where
+    K: Ord + Debug,
+{
+    /// Checks the structure of the tree, returns an error naming
+    /// the key of the first offending node.
+    pub fn check(&self, balance: Balance) -> Result<(), String> {
+        check(self.root, balance)
+    }
+}
+
+/// Checks the structure of the tree rooted at root
+pub fn check<K, V>(root: Option<NonNull<Node<K, V>>>, balance: Balance) -> Result<(), String>
+where
+    K: Ord + Debug,
+{
+    let root = NodeQuery::new(root);
+    if let Some(key) = root.get_key() {
+        if root.parent().is_some() {
+            return Err(format!("root {:?} has parent", key));
+        }
+        let red_black = balance == Balance::RedBlack || balance == Balance::LeftLeaningRedBlack;
+        if red_black && root.is_red() {
+            return Err(format!("root {:?} is red", key));
+        }
+    }
+    visit(root, None, None, balance).map(|_| ())
+}
+
+// height and black height of the subtree rooted at x
+struct Heights {
+    height: usize,
+    black: usize,
+}
+
+fn visit<K, V>(
+    x: NodeQuery<K, V>,
+    lo: Option<&K>,
+    hi: Option<&K>,
+    balance: Balance,
+) -> Result<Heights, String>
+where
+    K: Ord + Debug,
+{
+    let key = match x.get_key() {
+        None => {
+            return Ok(Heights {
+                height: 0,
+                black: 0,
+            })
+        }
+        Some(key) => key,
+    };
+
+    if lo.is_some_and(|lo| key.cmp(lo) != Ordering::Greater)
+        || hi.is_some_and(|hi| key.cmp(hi) != Ordering::Less)
+    {
+        return Err(format!("key {:?} violates symmetric order", key));
+    }
+
+    for child in [x.left(), x.right()].iter() {
+        if child.is_some() && child.parent().node != x.node {
+            return Err(format!(
+                "parent link of {:?} does not point to {:?}",
+                child.get_key().unwrap(),
+                key
+            ));
+        }
+    }
+
+    // children first, the error names the lowest offending node
+    let l = visit(x.left(), lo, Some(key), balance)?;
+    let r = visit(x.right(), Some(key), hi, balance)?;
+
+    let expect = 1 + x.left().size() + x.right().size();
+    if x.size() != expect {
+        return Err(format!(
+            "size of {:?} is {}, expected {}",
+            key,
+            x.size(),
+            expect
+        ));
+    }
+
+    match balance {
+        Balance::RedBlack | Balance::LeftLeaningRedBlack => {
+            if x.is_red() && (x.left().is_red() || x.right().is_red()) {
+                return Err(format!("red {:?} has red child", key));
+            }
+            if balance == Balance::LeftLeaningRedBlack && x.right().is_red() {
+                return Err(format!("red link of {:?} leans right", key));
+            }
+            if l.black != r.black {
+                return Err(format!(
+                    "black heights of {:?} differ, left {}, right {}",
+                    key, l.black, r.black
+                ));
+            }
+        }
+        Balance::Avl => {
+            let delta = r.height as i32 - l.height as i32;
+            if delta.abs() > 1 {
+                return Err(format!("{:?} is unbalanced, δ = {}", key, delta));
+            }
+            if x.delta() != delta {
+                return Err(format!(
+                    "δ of {:?} is {}, expected {}",
+                    key,
+                    x.delta(),
+                    delta
+                ));
+            }
+        }
+        Balance::None => (),
+    }
+
+    let black = if x.color() == Some(Color::Black) {
+        1
+    } else {
+        0
+    };
+    Ok(Heights {
+        height: 1 + l.height.max(r.height),
+        black: l.black + black,
+    })
+}
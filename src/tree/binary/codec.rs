@@ -0,0 +1,165 @@
+//! Compact binary encoding of the tree shape and keys, to save the
+//! test fixtures and reload them.
+//!
+//! The nodes are written in preorder, every node is
+//!   - a tag byte: bit 0 has left child, bit 1 has right child,
+//!     bit 2 is black
+//!   - the length of the key, in LEB128
+//!   - the key, key.to_string() in UTF-8
+//!
+//! The empty tree is encoded as no bytes. The values are not encoded,
+//! the decoded nodes only have keys, like the ones built by TreeBuilder.
+
+use crate::tree::binary::node::Color;
+use crate::tree::binary::{Node, NodeQuery, Tree};
+use std::ptr::NonNull;
+use std::str::FromStr;
+
+const HAS_LEFT: u8 = 1;
+const HAS_RIGHT: u8 = 1 << 1;
+const BLACK: u8 = 1 << 2;
+
+impl<K, V> Tree<K, V>
+where
+    K: ToString,
+{
+    /// Encodes the shape, the keys and the colors of the tree
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut stack: Vec<NonNull<Node<K, V>>> = self.root.into_iter().collect();
+        while let Some(x) = stack.pop() {
+            let node = unsafe { x.as_ref() };
+            let mut tag = 0;
+            if node.left.is_some() {
+                tag |= HAS_LEFT;
+            }
+            if node.right.is_some() {
+                tag |= HAS_RIGHT;
+            }
+            if node.color == Color::Black {
+                tag |= BLACK;
+            }
+            buf.push(tag);
+
+            let key = node.key.to_string();
+            let mut len = key.len();
+            loop {
+                let byte = (len & 0x7f) as u8;
+                len >>= 7;
+                if len == 0 {
+                    buf.push(byte);
+                    break;
+                }
+                buf.push(byte | 0x80);
+            }
+            buf.extend_from_slice(key.as_bytes());
+
+            stack.extend(node.right);
+            stack.extend(node.left);
+        }
+        buf
+    }
+}
+
+impl<K, V> Tree<K, V>
+where
+    K: FromStr,
+{
+    /// Decodes the tree encoded by Tree::encode
+    pub fn decode(buf: &[u8]) -> Result<Self, String> {
+        let mut tree = Tree::default();
+        if buf.is_empty() {
+            return Ok(tree);
+        }
+
+        let mut reader = Reader { buf, pos: 0 };
+        // (node, tag), the nodes whose children are not read yet
+        let mut stack = Vec::new();
+        let mut size = 0;
+        loop {
+            // the nodes read are released by the drop of tree, if fails
+            let (tag, key) = reader.node()?;
+            let x = Node::new_key(key);
+            if tag & BLACK != 0 {
+                NodeQuery::new(Some(x)).set_color(Color::Black);
+            }
+            size += 1;
+
+            // attach to the deepest node waiting for a child
+            match stack.last_mut() {
+                None => tree.root = Some(x),
+                Some((p, ptag)) => {
+                    let mut p = NodeQuery::new(Some(*p));
+                    if *ptag & HAS_LEFT != 0 {
+                        *ptag &= !HAS_LEFT;
+                        p.set_left(Some(x));
+                    } else {
+                        *ptag &= !HAS_RIGHT;
+                        p.set_right(Some(x));
+                    }
+                }
+            }
+            stack.push((x, tag & (HAS_LEFT | HAS_RIGHT)));
+
+            // the nodes whose children are all read are done
+            while let Some(&(x, 0)) = stack.last() {
+                stack.pop();
+                NodeQuery::new(Some(x)).update_size();
+            }
+            if stack.is_empty() {
+                break;
+            }
+        }
+        tree.set_size(size);
+
+        if reader.pos != buf.len() {
+            return Err(format!("{} trailing bytes", buf.len() - reader.pos));
+        }
+        Ok(tree)
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self.buf.get(self.pos).ok_or("unexpected end of bytes")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn node<K: FromStr>(&mut self) -> Result<(u8, K), String> {
+        let tag = self.byte()?;
+        if tag & !(HAS_LEFT | HAS_RIGHT | BLACK) != 0 {
+            return Err(format!("bad tag {:#x} at {}", tag, self.pos - 1));
+        }
+
+        let mut len = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            if shift >= usize::BITS {
+                return Err("key length overflows".to_string());
+            }
+            len |= ((byte & 0x7f) as usize) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or("unexpected end of bytes")?;
+        let key = std::str::from_utf8(&self.buf[self.pos..end])
+            .map_err(|_| format!("key at {} is not UTF-8", self.pos))?;
+        let key = key.parse().map_err(|_| format!("bad key {:?}", key))?;
+        self.pos = end;
+        Ok((tag, key))
+    }
+}
@@ -0,0 +1,169 @@
+//! Entry API of the ordered maps, a view into a single entry, which
+//! may either be vacant or occupied.
+//!
+//! The entry is located with one descent, updating an occupied entry
+//! does not search the map again.
+//!
+//! The maps (BSTree, RedBlackTreeV2, Treap, SkipList...) differ in how
+//! a new key is inserted, so a vacant entry holds the insert fn of the map.
+
+use crate::tree::binary::{bst, Node, Tree};
+use std::ptr::NonNull;
+
+/// Inserts a key which is not in the tree, returns the new node
+pub type InsertFn<K, V> = fn(&mut Tree<K, V>, K, V) -> NonNull<Node<K, V>>;
+
+pub enum Entry<'a, K, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    insert: Box<dyn FnOnce(K, V) -> &'a mut V + 'a>,
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    key: &'a K,
+    val: &'a mut V,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord,
+{
+    /// Makes the entry of the key in a binary tree
+    pub(crate) fn new(tree: &'a mut Tree<K, V>, key: K, insert: InsertFn<K, V>) -> Self {
+        match unsafe { bst::find(tree.root, &key) } {
+            Some(node) => {
+                let Node { key: k, val: v, .. } = unsafe { &mut *node.as_ptr() };
+                match v {
+                    Some(v) => Entry::occupied(k, v),
+                    // the key has no value (e.g. built by TreeBuilder)
+                    None => Entry::vacant(key, move |_, val| v.get_or_insert(val)),
+                }
+            }
+            None => Entry::vacant(key, move |key, val| {
+                let node = insert(tree, key, val);
+                unsafe { (*node.as_ptr()).val.as_mut().unwrap() }
+            }),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub(crate) fn occupied(key: &'a K, val: &'a mut V) -> Self {
+        Entry::Occupied(OccupiedEntry { key, val })
+    }
+
+    /// insert puts the key-value pair into the map, and
+    /// returns the value in the map
+    pub(crate) fn vacant<F>(key: K, insert: F) -> Self
+    where
+        F: FnOnce(K, V) -> &'a mut V + 'a,
+    {
+        Entry::Vacant(VacantEntry {
+            key,
+            insert: Box::new(insert),
+        })
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Returns a reference to this entry's key
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(e) => e.key(),
+            Entry::Occupied(e) => e.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the
+    /// default function if empty, and returns a mutable reference to
+    /// the value in the entry.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Vacant(e) => e.insert(default()),
+            Entry::Occupied(e) => e.into_mut(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the tree.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            e => e,
+        }
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Gets a reference to the key that would be used when inserting a
+    /// value through the VacantEntry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry, and returns a mutable reference to it.
+    pub fn insert(self, val: V) -> &'a mut V {
+        (self.insert)(self.key, val)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        self.key
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.val
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.val
+    }
+
+    /// Converts the entry into a mutable reference to its value,
+    /// with a lifetime bound to the map itself.
+    pub fn into_mut(self) -> &'a mut V {
+        self.val
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, val: V) -> V {
+        std::mem::replace(self.val, val)
+    }
+}
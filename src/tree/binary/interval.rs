@@ -0,0 +1,242 @@
+//! Interval search tree
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! Geometric applications of BSTs, 1d interval search
+//!
+//! Create BST, where each node stores an interval (lo, hi).
+//!   - Use left endpoint as BST key.
+//!   - Store max endpoint in subtree rooted at node.
+//!
+//! To search for any one interval that intersects query interval (lo, hi):
+//!   - If interval in node intersects query interval, return it.
+//!   - Else if left subtree is null, go right.
+//!   - Else if max endpoint in left subtree is less than lo, go right.
+//!   - Else go left.
+//!
+//! The tree is a left-leaning red-black tree (rb2), the max endpoint is
+//! kept in sync by rotations through rb2::Augment, so insert, delete and
+//! search any take time proportional to log N, search all takes time
+//! proportional to R log N, where R is the number of intervals found.
+//!
+//! 1d range search (count or find the keys between lo and hi) is
+//! provided for all binary trees by OrderedST and Tree::range.
+
+use crate::tree::binary::rb2::{self, Augment};
+use crate::tree::binary::{bst, Node, NodeQuery, Tree};
+use std::ptr::NonNull;
+
+/// Closed interval [lo, hi], ordered by lo, then by hi
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+/// The IntervalST represents a symbol table of intervals with generic values.
+/// It supports the usual insert, get, contains, delete, len, and is-empty
+/// methods, and also search for the intervals that intersect a given interval.
+pub struct IntervalST<T, V> {
+    tree: Tree<Interval<T>, Slot<T, V>>,
+}
+
+// value stored in node
+struct Slot<T, V> {
+    val: V,
+    // max endpoint in subtree rooted at this node
+    max: T,
+}
+
+// keeps Slot::max and node size in sync with children
+struct MaxEndpoint;
+
+type Link<T, V> = Option<NonNull<Node<Interval<T>, Slot<T, V>>>>;
+
+impl<T> Interval<T>
+where
+    T: Ord,
+{
+    pub fn new(lo: T, hi: T) -> Self {
+        assert!(lo <= hi, "illegal interval");
+        Self { lo, hi }
+    }
+
+    /// Does this interval intersect that interval?
+    pub fn intersects(&self, that: &Self) -> bool {
+        self.lo <= that.hi && that.lo <= self.hi
+    }
+
+    /// Does this interval contain x?
+    pub fn contains(&self, x: &T) -> bool {
+        &self.lo <= x && x <= &self.hi
+    }
+}
+
+impl<T, V> IntervalST<T, V>
+where
+    T: Ord + Copy,
+{
+    /// Returns the number of intervals in this symbol table.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Does this symbol table contain the given interval?
+    pub fn contains(&self, interval: &Interval<T>) -> bool {
+        self.get(interval).is_some()
+    }
+
+    /// Returns the value associated with the given interval.
+    pub fn get(&self, interval: &Interval<T>) -> Option<&V> {
+        unsafe {
+            bst::find(self.tree.root, interval)
+                .and_then(|p| p.as_ref().val.as_ref())
+                .map(|slot| &slot.val)
+        }
+    }
+
+    /// Inserts the interval-value pair, overwriting the old value with
+    /// the new value if the interval is already in the symbol table.
+    pub fn insert(&mut self, interval: Interval<T>, val: V) {
+        let max = interval.hi;
+        let slot = Slot { val, max };
+        self.tree.root = rb2::put_root::<_, _, MaxEndpoint>(self.tree.root, interval, slot);
+        self.tree.set_size(Node::size(self.tree.root));
+    }
+
+    /// Removes the interval from the symbol table (if it is present)
+    pub fn delete(&mut self, interval: &Interval<T>) {
+        if self.contains(interval) {
+            self.tree.root = rb2::delete_root::<_, _, MaxEndpoint>(self.tree.root, interval);
+            self.tree.set_size(Node::size(self.tree.root));
+        }
+    }
+
+    /// Returns an interval that intersects the query interval
+    pub fn search(&self, query: &Interval<T>) -> Option<(&Interval<T>, &V)> {
+        let mut x = NodeQuery::new(self.tree.root);
+        while let Some(key) = x.get_key() {
+            if key.intersects(query) {
+                return entry(x.node.unwrap());
+            }
+            match max(x.left().node) {
+                Some(max) if max >= query.lo => x = x.left(),
+                _ => x = x.right(),
+            }
+        }
+        None
+    }
+
+    /// Returns all intervals that intersect the query interval,
+    /// sorted by interval
+    pub fn search_all(&self, query: &Interval<T>) -> Vec<(&Interval<T>, &V)> {
+        let mut results = Vec::new();
+        search_all(self.tree.root, query, &mut results);
+        results
+    }
+}
+
+impl<T, V> Default for IntervalST<T, V> {
+    fn default() -> Self {
+        Self {
+            tree: Tree::default(),
+        }
+    }
+}
+
+impl<T, V> Augment<Interval<T>, Slot<T, V>> for MaxEndpoint
+where
+    T: Ord + Copy,
+{
+    fn update(h: &mut NodeQuery<Interval<T>, Slot<T, V>>) {
+        h.update_size();
+        if let Some(mut node) = h.node {
+            let mut m = h.get_key().unwrap().hi;
+            for child in [h.left().node, h.right().node].iter() {
+                if let Some(v) = max(*child) {
+                    m = m.max(v);
+                }
+            }
+            if let Some(slot) = unsafe { node.as_mut().val.as_mut() } {
+                slot.max = m;
+            }
+        }
+    }
+}
+
+/// max endpoint in subtree rooted at x
+fn max<T, V>(x: Link<T, V>) -> Option<T>
+where
+    T: Copy,
+{
+    unsafe { x.and_then(|x| x.as_ref().val.as_ref()).map(|slot| slot.max) }
+}
+
+fn entry<'a, T, V>(x: NonNull<Node<Interval<T>, Slot<T, V>>>) -> Option<(&'a Interval<T>, &'a V)> {
+    let x = unsafe { &*x.as_ptr() };
+    x.val.as_ref().map(|slot| (&x.key, &slot.val))
+}
+
+fn search_all<'a, T, V>(
+    x: Link<T, V>,
+    query: &Interval<T>,
+    results: &mut Vec<(&'a Interval<T>, &'a V)>,
+) where
+    T: Ord + Copy,
+{
+    let x = NodeQuery::new(x);
+    if let Some(key) = x.get_key() {
+        // no interval in left subtree ends after query.lo
+        if matches!(max(x.left().node), Some(max) if max >= query.lo) {
+            search_all(x.left().node, query, results);
+        }
+        if key.intersects(query) {
+            results.extend(entry(x.node.unwrap()));
+        }
+        // all intervals in right subtree start after query.hi
+        if key.lo <= query.hi {
+            search_all(x.right().node, query, results);
+        }
+    }
+}
+
+/// is the max endpoint of every node correct?
+#[cfg(test)]
+fn is_max_consistent<T, V>(x: Link<T, V>) -> bool
+where
+    T: Ord + Copy,
+{
+    let x = NodeQuery::new(x);
+    match x.get_key() {
+        None => true,
+        Some(key) => {
+            let mut m = key.hi;
+            for child in [x.left().node, x.right().node].iter() {
+                if let Some(v) = max(*child) {
+                    m = m.max(v);
+                }
+            }
+            max(x.node) == Some(m)
+                && is_max_consistent(x.left().node)
+                && is_max_consistent(x.right().node)
+        }
+    }
+}
+
+#[test]
+fn t_max_endpoint() {
+    let mut st = IntervalST::default();
+    for i in 0..100 {
+        st.insert(Interval::new(i, i + (i * 7) % 13), i);
+        assert!(is_max_consistent(st.tree.root));
+    }
+    for i in (0..100).step_by(3) {
+        st.delete(&Interval::new(i, i + (i * 7) % 13));
+        assert!(is_max_consistent(st.tree.root));
+    }
+}
@@ -0,0 +1,236 @@
+//! Borrowing iterators over Tree, in symmetric order (inorder)
+//!
+//! Both ends keep a stack of the nodes still to visit, like the iterative
+//! InOrderVisitor, so the iterators are lazy and double-ended. The number
+//! of nodes between the two ends comes from the subtree sizes, which tells
+//! when the two ends meet.
+//!
+//! Nodes without value (e.g. built by TreeBuilder) are skipped.
+
+use crate::tree::binary::{bst, ordered, Node};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::ptr::NonNull;
+
+/// An iterator over the entries of a Tree, yields (&K, &V)
+pub struct Iter<'a, K, V> {
+    inner: Stacks<K, V>,
+    marker: PhantomData<&'a Node<K, V>>,
+}
+
+/// A mutable iterator over the entries of a Tree, yields (&K, &mut V)
+pub struct IterMut<'a, K, V> {
+    inner: Stacks<K, V>,
+    marker: PhantomData<&'a mut Node<K, V>>,
+}
+
+struct Stacks<K, V> {
+    front: Vec<NonNull<Node<K, V>>>,
+    back: Vec<NonNull<Node<K, V>>>,
+    // nodes between front and back
+    remaining: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    pub(crate) fn new(root: Option<NonNull<Node<K, V>>>) -> Self {
+        Self {
+            inner: Stacks::new(root),
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn range<R>(root: Option<NonNull<Node<K, V>>>, range: R) -> Self
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        Self {
+            inner: Stacks::range(root, range),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(root: Option<NonNull<Node<K, V>>>) -> Self {
+        Self {
+            inner: Stacks::new(root),
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn range<R>(root: Option<NonNull<Node<K, V>>>, range: R) -> Self
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        Self {
+            inner: Stacks::range(root, range),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = unsafe { &*self.inner.next()?.as_ptr() };
+            if let Some(val) = node.val.as_ref() {
+                return Some((&node.key, val));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = unsafe { &*self.inner.next_back()?.as_ptr() };
+            if let Some(val) = node.val.as_ref() {
+                return Some((&node.key, val));
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // every node is visited only once, so no aliasing &mut
+            let node = unsafe { &mut *self.inner.next()?.as_ptr() };
+            if let Some(val) = node.val.as_mut() {
+                return Some((&node.key, val));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = unsafe { &mut *self.inner.next_back()?.as_ptr() };
+            if let Some(val) = node.val.as_mut() {
+                return Some((&node.key, val));
+            }
+        }
+    }
+}
+
+impl<K, V> Stacks<K, V> {
+    fn new(root: Option<NonNull<Node<K, V>>>) -> Self {
+        let mut stacks = Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: Node::size(root),
+        };
+        stacks.push_left(root);
+        stacks.push_right(root);
+        stacks
+    }
+
+    fn range<R>(root: Option<NonNull<Node<K, V>>>, range: R) -> Self
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        let contains = |key| unsafe { bst::find(root, key).is_some() as usize };
+        let lo = match range.start_bound() {
+            Bound::Included(key) => ordered::rank(root, key),
+            Bound::Excluded(key) => ordered::rank(root, key) + contains(key),
+            Bound::Unbounded => 0,
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(key) => ordered::rank(root, key) + contains(key),
+            Bound::Excluded(key) => ordered::rank(root, key),
+            Bound::Unbounded => Node::size(root),
+        };
+
+        let mut stacks = Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: hi.saturating_sub(lo),
+        };
+
+        // the path to the first key in range
+        let mut x = root;
+        while let Some(node) = x {
+            let node_ref = unsafe { node.as_ref() };
+            let above = match range.start_bound() {
+                Bound::Included(key) => &node_ref.key >= key,
+                Bound::Excluded(key) => &node_ref.key > key,
+                Bound::Unbounded => true,
+            };
+            if above {
+                stacks.front.push(node);
+                x = node_ref.left;
+            } else {
+                x = node_ref.right;
+            }
+        }
+
+        // the path to the last key in range
+        let mut x = root;
+        while let Some(node) = x {
+            let node_ref = unsafe { node.as_ref() };
+            let below = match range.end_bound() {
+                Bound::Included(key) => &node_ref.key <= key,
+                Bound::Excluded(key) => &node_ref.key < key,
+                Bound::Unbounded => true,
+            };
+            if below {
+                stacks.back.push(node);
+                x = node_ref.right;
+            } else {
+                x = node_ref.left;
+            }
+        }
+
+        stacks
+    }
+
+    fn next(&mut self) -> Option<NonNull<Node<K, V>>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.pop()?;
+        self.remaining -= 1;
+        self.push_left(unsafe { node.as_ref().right });
+        Some(node)
+    }
+
+    fn next_back(&mut self) -> Option<NonNull<Node<K, V>>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.pop()?;
+        self.remaining -= 1;
+        self.push_right(unsafe { node.as_ref().left });
+        Some(node)
+    }
+
+    fn push_left(&mut self, mut x: Option<NonNull<Node<K, V>>>) {
+        while let Some(node) = x {
+            self.front.push(node);
+            x = unsafe { node.as_ref().left };
+        }
+    }
+
+    fn push_right(&mut self, mut x: Option<NonNull<Node<K, V>>>) {
+        while let Some(node) = x {
+            self.back.push(node);
+            x = unsafe { node.as_ref().right };
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! 2d-tree
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! Geometric applications of BSTs, 2d orthogonal range search
+//!
+//! A 2d-tree is a generalization of a BST to two-dimensional keys.
+//! The idea is to build a BST with points in the nodes, using the x- and
+//! y-coordinates of the points as keys in strictly alternating sequence.
+//!   - at the root, points with smaller x go left, the others go right
+//!   - at the next level, points with smaller y go left, the others go right
+//!
+//! So every node splits the plane of its subtree by a vertical (x) or
+//! horizontal (y) line through its point, the left subtree is on the
+//! smaller side of the line, the right subtree on the other side.
+//!
+//! Range search. To find all points contained in a given query rectangle,
+//! start at the root and recursively search for points in both subtrees
+//! using the following pruning rule: if the query rectangle lies entirely
+//! on one side of the splitting line of a node, there is no need to
+//! explore the subtree on the other side.
+//!
+//! Nearest-neighbor search. To find a closest point to a given query point,
+//! start at the root and always choose the subtree that is on the same side
+//! of the splitting line as the query point as the first subtree to explore.
+//! Every point of the other subtree is at least the distance between the
+//! query point and the splitting line away, so if the closest point
+//! discovered so far is closer than that, there is no need to explore it.
+//!
+//! The tree is not balanced, insert in random order to get logarithmic height.
+
+/*
+  insert (7, 2), (5, 4), (9, 6), (2, 3), (4, 7)
+
+            (7, 2)          split by x
+           /      \
+       (5, 4)    (9, 6)     split by y
+       /    \
+   (2, 3)  (4, 7)           split by x
+*/
+
+use crate::tree::binary::{bst, Node, NodeQuery, Tree};
+use std::ptr::NonNull;
+
+/// Point in the plane
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Axis-aligned rectangle [xmin, xmax] x [ymin, ymax]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RectHV {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+/// The KdTree represents a set of points in the plane, with generic values.
+/// It supports insert, get, contains, len, and is-empty methods, and also
+/// range search and nearest-neighbor search.
+pub struct KdTree<V> {
+    tree: Tree<Point2D, V>,
+}
+
+impl Point2D {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the square of the Euclidean distance between this point and that point.
+    pub fn distance_squared_to(&self, that: &Self) -> f64 {
+        let dx = self.x - that.x;
+        let dy = self.y - that.y;
+        dx * dx + dy * dy
+    }
+
+    // coordinate used as key at the given depth
+    fn coord(&self, depth: usize) -> f64 {
+        if depth.is_multiple_of(2) {
+            self.x
+        } else {
+            self.y
+        }
+    }
+}
+
+impl RectHV {
+    pub fn new(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Self {
+        assert!(xmin <= xmax && ymin <= ymax, "invalid rectangle");
+        Self {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+        }
+    }
+
+    /// Is the point inside this rectangle (including the boundary)?
+    pub fn contains(&self, p: &Point2D) -> bool {
+        self.xmin <= p.x && p.x <= self.xmax && self.ymin <= p.y && p.y <= self.ymax
+    }
+
+    /// Does this rectangle intersect that rectangle (at one or more points)?
+    pub fn intersects(&self, that: &Self) -> bool {
+        self.xmax >= that.xmin
+            && self.ymax >= that.ymin
+            && that.xmax >= self.xmin
+            && that.ymax >= self.ymin
+    }
+
+    /// Returns the square of the Euclidean distance between this rectangle
+    /// and the point, 0 if the point is inside.
+    pub fn distance_squared_to(&self, p: &Point2D) -> f64 {
+        let dx = (self.xmin - p.x).max(p.x - self.xmax).max(0.0);
+        let dy = (self.ymin - p.y).max(p.y - self.ymax).max(0.0);
+        dx * dx + dy * dy
+    }
+}
+
+impl<V> KdTree<V> {
+    /// Returns the number of points in this set.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Is this set empty
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Does this set contain the point?
+    pub fn contains(&self, p: &Point2D) -> bool {
+        self.get(p).is_some()
+    }
+
+    /// Returns the value associated with the point.
+    pub fn get(&self, p: &Point2D) -> Option<&V> {
+        let mut x = NodeQuery::new(self.tree.root);
+        let mut depth = 0;
+        while let Some(key) = x.get_key() {
+            if key == p {
+                let node = unsafe { &*x.node.unwrap().as_ptr() };
+                return node.val.as_ref();
+            }
+            x = if p.coord(depth) < key.coord(depth) {
+                x.left()
+            } else {
+                x.right()
+            };
+            depth += 1;
+        }
+        None
+    }
+
+    /// Adds the point to the set (if it is not already in the set),
+    /// overwriting the old value with the new value if it is.
+    pub fn insert(&mut self, p: Point2D, val: V) {
+        let mut x = NodeQuery::new(self.tree.root);
+        let mut parent = NodeQuery::new(None);
+        let mut go_left = false;
+        let mut depth = 0;
+        while let Some(key) = x.get_key() {
+            if key == &p {
+                x.set_entry((p, Some(val))); // update val
+                return;
+            }
+            parent = NodeQuery::new(x.node);
+            go_left = p.coord(depth) < key.coord(depth);
+            x = if go_left { x.left() } else { x.right() };
+            depth += 1;
+        }
+
+        let node = Some(Node::new_entry(p, val));
+        if parent.is_none() {
+            self.tree.root = node;
+        } else if go_left {
+            parent.set_left(node);
+        } else {
+            parent.set_right(node);
+        }
+        bst::update_size_upward(parent.node);
+        self.tree.set_size(self.tree.size() + 1);
+    }
+
+    /// Returns all points that are inside the rectangle (or on the boundary).
+    pub fn range(&self, rect: &RectHV) -> Vec<(&Point2D, &V)> {
+        let mut results = Vec::new();
+        range(self.tree.root, rect, 0, &mut results);
+        results
+    }
+
+    /// Returns a nearest neighbor in the set to point p; None if the set is empty.
+    pub fn nearest(&self, p: &Point2D) -> Option<(&Point2D, &V)> {
+        let mut best = None;
+        let mut best_dist = f64::INFINITY;
+        nearest(self.tree.root, p, 0, &mut best, &mut best_dist);
+        best.and_then(|x| {
+            let x = unsafe { &*x.as_ptr() };
+            x.val.as_ref().map(|v| (&x.key, v))
+        })
+    }
+}
+
+impl<V> Default for KdTree<V> {
+    fn default() -> Self {
+        Self {
+            tree: Tree::default(),
+        }
+    }
+}
+
+fn range<'a, V>(
+    x: Option<NonNull<Node<Point2D, V>>>,
+    rect: &RectHV,
+    depth: usize,
+    results: &mut Vec<(&'a Point2D, &'a V)>,
+) {
+    if let Some(x) = x {
+        let x = unsafe { &*x.as_ptr() };
+        let p = &x.key;
+        if rect.contains(p) {
+            results.extend(x.val.as_ref().map(|v| (p, v)));
+        }
+
+        let (lo, hi) = if depth.is_multiple_of(2) {
+            (rect.xmin, rect.xmax)
+        } else {
+            (rect.ymin, rect.ymax)
+        };
+        // left subtree is on the smaller side of the splitting line
+        if lo < p.coord(depth) {
+            range(x.left, rect, depth + 1, results);
+        }
+        if hi >= p.coord(depth) {
+            range(x.right, rect, depth + 1, results);
+        }
+    }
+}
+
+fn nearest<V>(
+    x: Option<NonNull<Node<Point2D, V>>>,
+    p: &Point2D,
+    depth: usize,
+    best: &mut Option<NonNull<Node<Point2D, V>>>,
+    best_dist: &mut f64,
+) {
+    if let Some(node) = x {
+        let key = unsafe { &node.as_ref().key };
+        let dist = key.distance_squared_to(p);
+        if dist < *best_dist {
+            *best = x;
+            *best_dist = dist;
+        }
+
+        let x = NodeQuery::new(x);
+        let diff = p.coord(depth) - key.coord(depth);
+        // explore the side of the splitting line the query point is on first
+        let (first, second) = if diff < 0.0 {
+            (x.left(), x.right())
+        } else {
+            (x.right(), x.left())
+        };
+        nearest(first.node, p, depth + 1, best, best_dist);
+        // the other side is at least diff away
+        if diff * diff < *best_dist {
+            nearest(second.node, p, depth + 1, best, best_dist);
+        }
+    }
+}
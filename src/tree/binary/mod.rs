@@ -2,11 +2,25 @@ pub use node::Node;
 pub use node::NodeQuery;
 pub use tree::Tree;
 
+pub mod arena_tree;
 pub mod avl;
 pub mod bst;
+pub mod btree;
 pub mod builder;
+pub mod check;
+pub mod codec;
+pub mod entry;
+pub mod interval;
+pub mod iter;
+pub mod kd_tree;
 mod node;
+pub mod ordered;
+pub mod persistent;
 pub mod rb;
 pub mod rb2;
+pub mod render;
+pub mod rope;
+pub mod splay;
 pub mod traverse;
+pub mod treap;
 mod tree;
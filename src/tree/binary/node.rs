@@ -24,8 +24,10 @@ pub struct Node<K, V> {
     pub left: Option<NonNull<Node<K, V>>>,
     pub right: Option<NonNull<Node<K, V>>>,
     pub parent: Option<NonNull<Node<K, V>>>,
-    pub color: Color, // used by red black tree
-    pub delta: i32,   // 平衡因子, used by avl tree
+    pub color: Color,  // used by red black tree
+    pub delta: i32,    // 平衡因子, used by avl tree
+    pub size: usize,   // 子树节点个数, used by ordered symbol table
+    pub priority: u32, // 随机优先级, used by treap
 }
 
 impl<K, V> Node<K, V> {
@@ -36,6 +38,7 @@ impl<K, V> Node<K, V> {
         right: Option<NonNull<Node<K, V>>>,
         parent: Option<NonNull<Node<K, V>>>,
     ) -> NonNull<Self> {
+        let size = 1 + Node::size(left) + Node::size(right);
         let v = Box::new(Node {
             key,
             val,
@@ -44,6 +47,8 @@ impl<K, V> Node<K, V> {
             parent,
             color: Color::Red,
             delta: 0,
+            size,
+            priority: 0,
         });
         Box::leak(v).into()
     }
@@ -66,6 +71,11 @@ impl<K, V> Node<K, V> {
         }
     }
 
+    /// 子树节点个数，空树为0
+    pub fn size(node: Option<NonNull<Self>>) -> usize {
+        unsafe { node.map_or(0, |node| node.as_ref().size) }
+    }
+
     /// 直接子节点个数，不包括孙子...
     pub fn children_count(node: NonNull<Self>) -> usize {
         unsafe { node.as_ref().left.map_or(0, |_| 1) + node.as_ref().right.map_or(0, |_| 1) }
@@ -204,6 +214,27 @@ impl<'a, K: 'a, V: 'a> NodeQuery<K, V> {
         }
     }
 
+    pub fn set_delta(&mut self, v: i32) {
+        if let Some(mut node) = self.node {
+            unsafe { node.as_mut().delta = v }
+        }
+    }
+
+    pub fn delta(&self) -> i32 {
+        unsafe { self.node.map_or(0, |node| node.as_ref().delta) }
+    }
+
+    pub fn size(&self) -> usize {
+        Node::size(self.node)
+    }
+
+    /// recalculate subtree size from children
+    pub fn update_size(&mut self) {
+        if let Some(mut node) = self.node {
+            unsafe { node.as_mut().size = 1 + self.left().size() + self.right().size() }
+        }
+    }
+
     pub fn color(&self) -> Option<Color> {
         unsafe { self.node.map(|node| node.as_ref().color) }
     }
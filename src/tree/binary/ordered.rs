@@ -0,0 +1,135 @@
+//! Ordered symbol table
+//!
+//! Algorithms 4th Edition by Robert Sedgewick, Kevin Wayne
+//!
+//! 3.1 Symbol Tables, Ordered operations
+//!
+//! Every node keeps the number of nodes in the subtree rooted at it,
+//! so rank() and select() take time proportional to the tree height,
+//! instead of walking through all keys.
+//!
+//!   size(x) = size(x.left) + size(x.right) + 1
+//!
+//! The operations only read the tree, BSTree, RedBlackTree, RedBlackTreeV2
+//! and AvlTree all maintain the subtree sizes, so they share this trait.
+
+use crate::tree::binary::{bst, Node, NodeQuery, Tree};
+use std::cmp::Ordering;
+use std::ptr::NonNull;
+
+pub trait OrderedST<K, V> {
+    /// Returns the number of keys strictly less than key
+    fn rank(&self, key: &K) -> usize;
+    /// Returns the key of the given rank (the k-th smallest key, from 0)
+    fn select(&self, k: usize) -> Option<&K>;
+    /// Returns the largest key less than or equal to key
+    fn floor(&self, key: &K) -> Option<&K>;
+    /// Returns the smallest key greater than or equal to key
+    fn ceiling(&self, key: &K) -> Option<&K>;
+    /// Returns the number of keys in [lo, hi]
+    fn size_between(&self, lo: &K, hi: &K) -> usize;
+    /// Returns all keys in [lo, hi], in sorted order
+    fn keys_between(&self, lo: &K, hi: &K) -> Vec<&K>;
+}
+
+impl<K, V> OrderedST<K, V> for Tree<K, V>
+where
+    K: Ord,
+{
+    fn rank(&self, key: &K) -> usize {
+        rank(self.root, key)
+    }
+
+    fn select(&self, k: usize) -> Option<&K> {
+        unsafe { select(self.root, k).map(|p| &p.as_ref().key) }
+    }
+
+    fn floor(&self, key: &K) -> Option<&K> {
+        unsafe { floor(self.root, key).map(|p| &p.as_ref().key) }
+    }
+
+    fn ceiling(&self, key: &K) -> Option<&K> {
+        unsafe { ceiling(self.root, key).map(|p| &p.as_ref().key) }
+    }
+
+    fn size_between(&self, lo: &K, hi: &K) -> usize {
+        if lo > hi {
+            0
+        } else if unsafe { bst::find(self.root, hi) }.is_some() {
+            rank(self.root, hi) - rank(self.root, lo) + 1
+        } else {
+            rank(self.root, hi) - rank(self.root, lo)
+        }
+    }
+
+    fn keys_between(&self, lo: &K, hi: &K) -> Vec<&K> {
+        let mut queue = Vec::new();
+        if lo <= hi {
+            bst::keys(self.root, &mut queue, lo, hi);
+        }
+        queue
+    }
+}
+
+/// Number of keys in the subtree less than key
+pub fn rank<K, V>(x: Option<NonNull<Node<K, V>>>, key: &K) -> usize
+where
+    K: Ord,
+{
+    let x = NodeQuery::new(x);
+    match x.get_key() {
+        None => 0,
+        Some(x_key) => match key.cmp(x_key) {
+            Ordering::Less => rank(x.left().node, key),
+            Ordering::Greater => 1 + x.left().size() + rank(x.right().node, key),
+            Ordering::Equal => x.left().size(),
+        },
+    }
+}
+
+/// Return the node of rank k in the subtree
+pub fn select<K, V>(x: Option<NonNull<Node<K, V>>>, k: usize) -> Option<NonNull<Node<K, V>>> {
+    let x = NodeQuery::new(x);
+    x.node?;
+    let t = x.left().size();
+    match t.cmp(&k) {
+        Ordering::Greater => select(x.left().node, k),
+        Ordering::Less => select(x.right().node, k - t - 1),
+        Ordering::Equal => x.node,
+    }
+}
+
+/// the node with the largest key in the subtree less than or equal to key
+pub fn floor<K, V>(x: Option<NonNull<Node<K, V>>>, key: &K) -> Option<NonNull<Node<K, V>>>
+where
+    K: Ord,
+{
+    let x = NodeQuery::new(x);
+    match key.cmp(x.get_key()?) {
+        Ordering::Equal => x.node,
+        Ordering::Less => floor(x.left().node, key),
+        Ordering::Greater => floor(x.right().node, key).or(x.node),
+    }
+}
+
+/// the node with the smallest key in the subtree greater than or equal to key
+pub fn ceiling<K, V>(x: Option<NonNull<Node<K, V>>>, key: &K) -> Option<NonNull<Node<K, V>>>
+where
+    K: Ord,
+{
+    let x = NodeQuery::new(x);
+    match key.cmp(x.get_key()?) {
+        Ordering::Equal => x.node,
+        Ordering::Greater => ceiling(x.right().node, key),
+        Ordering::Less => ceiling(x.left().node, key).or(x.node),
+    }
+}
+
+/// are the subtree sizes correct?
+pub fn is_size_consistent<K, V>(x: Option<NonNull<Node<K, V>>>) -> bool {
+    let x = NodeQuery::new(x);
+    x.is_none()
+        || (x.size() == 1 + x.left().size() + x.right().size()
+            && is_size_consistent(x.left().node)
+            && is_size_consistent(x.right().node))
+}
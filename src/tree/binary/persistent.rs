@@ -0,0 +1,407 @@
+//! Persistent ordered map, a left-leaning red-black tree with path copying
+//!
+//! A persistent data structure preserves the previous version of itself
+//! when it is modified. Nodes are never changed after creation, an update
+//! copies the nodes on the search path (and the nodes touched by rotations
+//! and color flips) and shares all other subtrees with the old version.
+//!
+//! So insert and delete take time and extra space proportional to log N,
+//! and return a new version, the old versions are still readable.
+//! A snapshot is a clone of the handle, which takes constant time.
+//!
+//! The entries are shared by Rc too, so K and V need not be Clone.
+//!
+//! The rebalancing is the same as rb2 (Algorithms 4th Edition, 3.3),
+//! except that a node is copied before being changed.
+
+use crate::tree::binary::node::Color;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+type Link<K, V> = Option<Rc<Node<K, V>>>;
+
+/// The PersistentMap represents an immutable ordered symbol table
+pub struct PersistentMap<K, V> {
+    root: Link<K, V>,
+}
+
+struct Node<K, V> {
+    entry: Rc<(K, V)>,
+    left: Link<K, V>,
+    right: Link<K, V>,
+    color: Color,
+    size: usize,
+}
+
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    remaining: usize,
+}
+
+impl<K, V> PersistentMap<K, V> {
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns the number of entries in this version
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns a handle of this version in constant time,
+    /// which is not affected by later updates
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Returns the smallest key
+    pub fn min(&self) -> Option<&K> {
+        let mut x = self.root.as_ref()?;
+        while let Some(l) = x.left.as_ref() {
+            x = l;
+        }
+        Some(&x.entry.0)
+    }
+
+    /// Returns the largest key
+    pub fn max(&self) -> Option<&K> {
+        let mut x = self.root.as_ref()?;
+        while let Some(r) = x.right.as_ref() {
+            x = r;
+        }
+        Some(&x.entry.0)
+    }
+
+    /// Gets an iterator over the entries of this version, sorted by key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut it = Iter {
+            stack: Vec::new(),
+            remaining: self.len(),
+        };
+        it.push_left(self.root.as_deref());
+        it
+    }
+
+    /// Is this version a valid left-leaning red-black tree?
+    pub fn is_valid(&self) -> bool
+    where
+        K: Ord,
+    {
+        !is_red(&self.root) && is_llrb(&self.root, None, None).is_some()
+    }
+}
+
+impl<K, V> PersistentMap<K, V>
+where
+    K: Ord,
+{
+    /// Returns the value associated with the key
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut x = self.root.as_ref();
+        while let Some(h) = x {
+            match key.cmp(&h.entry.0) {
+                Ordering::Less => x = h.left.as_ref(),
+                Ordering::Greater => x = h.right.as_ref(),
+                Ordering::Equal => return Some(&h.entry.1),
+            }
+        }
+        None
+    }
+
+    /// Does this version contain the key
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a new version with the key-value pair, overwriting
+    /// the old value if the key is already in this version
+    pub fn insert(&self, key: K, val: V) -> Self {
+        let root = put(&self.root, key, val);
+        Self {
+            root: blacken(Some(Rc::new(root))),
+        }
+    }
+
+    /// Returns a new version without the key
+    pub fn delete(&self, key: &K) -> Self {
+        if !self.contains(key) {
+            return self.clone();
+        }
+
+        let mut root = copy(&self.root);
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.color = Color::Red;
+        }
+        Self {
+            root: blacken(delete(root, key)),
+        }
+    }
+}
+
+impl<K, V> Clone for PersistentMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for PersistentMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a PersistentMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut x: Option<&'a Node<K, V>>) {
+        while let Some(h) = x {
+            self.stack.push(h);
+            x = h.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let h = self.stack.pop()?;
+        self.push_left(h.right.as_deref());
+        self.remaining -= 1;
+        Some((&h.entry.0, &h.entry.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn update_size(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+// shallow copy, the entry and the children are shared
+impl<K, V> Clone for Node<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entry: self.entry.clone(),
+            left: self.left.clone(),
+            right: self.right.clone(),
+            color: self.color,
+            size: self.size,
+        }
+    }
+}
+
+fn size<K, V>(x: &Link<K, V>) -> usize {
+    x.as_ref().map_or(0, |x| x.size)
+}
+
+fn is_red<K, V>(x: &Link<K, V>) -> bool {
+    x.as_ref().is_some_and(|x| x.color == Color::Red)
+}
+
+fn is_left_red<K, V>(x: &Link<K, V>) -> bool {
+    x.as_ref().is_some_and(|x| is_red(&x.left))
+}
+
+/// copy of the node to be changed, x must not be None
+fn copy<K, V>(x: &Link<K, V>) -> Node<K, V> {
+    Node::clone(x.as_ref().unwrap())
+}
+
+fn blacken<K, V>(x: Link<K, V>) -> Link<K, V> {
+    match x {
+        Some(h) if h.color == Color::Red => {
+            let mut h = Node::clone(&h);
+            h.color = Color::Black;
+            Some(Rc::new(h))
+        }
+        x => x,
+    }
+}
+
+fn put<K, V>(h: &Link<K, V>, key: K, val: V) -> Node<K, V>
+where
+    K: Ord,
+{
+    match h {
+        None => Node {
+            entry: Rc::new((key, val)),
+            left: None,
+            right: None,
+            color: Color::Red,
+            size: 1,
+        },
+        Some(x) => {
+            let mut h = Node::clone(x);
+            match key.cmp(&x.entry.0) {
+                Ordering::Less => h.left = Some(Rc::new(put(&x.left, key, val))),
+                Ordering::Greater => h.right = Some(Rc::new(put(&x.right, key, val))),
+                Ordering::Equal => h.entry = Rc::new((key, val)),
+            }
+            balance(h)
+        }
+    }
+}
+
+fn del_min<K, V>(mut h: Node<K, V>) -> Link<K, V> {
+    // h is the min if it has no left child
+    h.left.as_ref()?;
+    if !is_red(&h.left) && !is_left_red(&h.left) {
+        h = move_red_left(h);
+    }
+    h.left = del_min(copy(&h.left));
+    Some(Rc::new(balance(h)))
+}
+
+/// the key must exist in the subtree rooted at h
+fn delete<K, V>(mut h: Node<K, V>, key: &K) -> Link<K, V>
+where
+    K: Ord,
+{
+    if key < &h.entry.0 {
+        if !is_red(&h.left) && !is_left_red(&h.left) {
+            h = move_red_left(h);
+        }
+        h.left = delete(copy(&h.left), key);
+    } else {
+        if is_red(&h.left) {
+            h = rotate_right(h);
+        }
+        if key == &h.entry.0 && h.right.is_none() {
+            return None;
+        }
+        if !is_red(&h.right) && !is_left_red(&h.right) {
+            h = move_red_right(h);
+        }
+        if key == &h.entry.0 {
+            // 用右子树的最小entry替换，再删除这一最小节点
+            let mut min = h.right.as_ref().unwrap();
+            while let Some(l) = min.left.as_ref() {
+                min = l;
+            }
+            h.entry = min.entry.clone();
+            h.right = del_min(copy(&h.right));
+        } else {
+            h.right = delete(copy(&h.right), key);
+        }
+    }
+    Some(Rc::new(balance(h)))
+}
+
+/// make a right-leaning link lean to the left
+fn rotate_left<K, V>(mut h: Node<K, V>) -> Node<K, V> {
+    let mut x = copy(&h.right);
+    h.right = x.left.take();
+    x.color = h.color;
+    h.color = Color::Red;
+    h.update_size();
+    x.left = Some(Rc::new(h));
+    x.update_size();
+    x
+}
+
+/// make a left-leaning link lean to the right
+fn rotate_right<K, V>(mut h: Node<K, V>) -> Node<K, V> {
+    let mut x = copy(&h.left);
+    h.left = x.right.take();
+    x.color = h.color;
+    h.color = Color::Red;
+    h.update_size();
+    x.right = Some(Rc::new(h));
+    x.update_size();
+    x
+}
+
+/// flip the colors of a node and its two children
+fn flip_colors<K, V>(h: &mut Node<K, V>) {
+    h.color = h.color.flip();
+    for child in [&mut h.left, &mut h.right] {
+        if let Some(x) = child.as_ref() {
+            let mut x = Node::clone(x);
+            x.color = x.color.flip();
+            *child = Some(Rc::new(x));
+        }
+    }
+}
+
+/// restore red-black tree invariant
+fn balance<K, V>(mut h: Node<K, V>) -> Node<K, V> {
+    if is_red(&h.right) && !is_red(&h.left) {
+        h = rotate_left(h);
+    }
+    if is_red(&h.left) && is_left_red(&h.left) {
+        h = rotate_right(h);
+    }
+    if is_red(&h.left) && is_red(&h.right) {
+        flip_colors(&mut h);
+    }
+    h.update_size();
+    h
+}
+
+/// Assuming that h is red and both h.left and h.left.left
+/// are black, make h.left or one of its children red.
+fn move_red_left<K, V>(mut h: Node<K, V>) -> Node<K, V> {
+    flip_colors(&mut h);
+    if is_left_red(&h.right) {
+        h.right = Some(Rc::new(rotate_right(copy(&h.right))));
+        h = rotate_left(h);
+        flip_colors(&mut h);
+    }
+    h
+}
+
+/// Assuming that h is red and both h.right and h.right.left
+/// are black, make h.right or one of its children red.
+fn move_red_right<K, V>(mut h: Node<K, V>) -> Node<K, V> {
+    flip_colors(&mut h);
+    if is_left_red(&h.left) {
+        h = rotate_right(h);
+        flip_colors(&mut h);
+    }
+    h
+}
+
+/// returns the black height of the subtree rooted at x if it is a valid
+/// llrb with keys in (lo, hi) and correct sizes, None otherwise
+fn is_llrb<K, V>(x: &Link<K, V>, lo: Option<&K>, hi: Option<&K>) -> Option<usize>
+where
+    K: Ord,
+{
+    match x {
+        None => Some(0),
+        Some(h) => {
+            let key = &h.entry.0;
+            let ordered = lo.is_none_or(|lo| lo < key) && hi.is_none_or(|hi| key < hi);
+            // red right link, or two red links in a row
+            let red_violated = is_red(&h.right) || (h.color == Color::Red && is_red(&h.left));
+            if !ordered || red_violated || h.size != 1 + size(&h.left) + size(&h.right) {
+                return None;
+            }
+            let lb = is_llrb(&h.left, lo, Some(key))?;
+            let rb = is_llrb(&h.right, Some(key), hi)?;
+            match (lb == rb, h.color) {
+                (false, _) => None,
+                (true, Color::Black) => Some(lb + 1),
+                (true, Color::Red) => Some(lb),
+            }
+        }
+    }
+}
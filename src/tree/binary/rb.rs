@@ -120,13 +120,26 @@ Chris Okasaki 指出，共有四种情况会违反红黑树的第四条性质。
 
 */
 
+use crate::tree::binary::check::Balance;
 use crate::tree::binary::node::Color;
 use crate::tree::binary::{bst, Node, NodeQuery, Tree};
+use std::fmt::Debug;
 use std::ptr::NonNull;
 
-//todo: rb tree delete, Chris Okasaki insert-fix
+//todo: Chris Okasaki insert-fix
 pub trait RedBlackTree<K, V> {
     fn insert(&mut self, key: K, val: V);
+    /// Removes the specified element
+    fn delete(&mut self, key: &K);
+    fn get(&self, key: &K) -> Option<&V>;
+    /// Returns the smallest key
+    fn min(&self) -> Option<&K>;
+    /// Returns the largest key
+    fn max(&self) -> Option<&K>;
+    /// Does the tree satisfy symmetric order and all red-black properties?
+    fn is_valid(&self) -> bool
+    where
+        K: Debug;
 }
 
 impl<K, V> RedBlackTree<K, V> for Tree<K, V>
@@ -134,21 +147,42 @@ where
     K: Ord,
 {
     fn insert(&mut self, key: K, val: V) {
-        self.root = insert(self.root, key, val);
+        if let Ok(x) = unsafe { bst::insert(self.root, key, val) } {
+            let root = if self.root.is_none() {
+                Some(x)
+            } else {
+                self.root
+            };
+            // 修正，使树恢复平衡
+            self.root = insert_fix(root, Some(x));
+            self.set_size(self.size() + 1);
+        }
     }
-}
 
-fn insert<K, V>(root: Option<NonNull<Node<K, V>>>, key: K, val: V) -> Option<NonNull<Node<K, V>>>
-where
-    K: Ord,
-{
-    // 插入过程与bst是一样的
-    if let Ok(x) = unsafe { bst::insert(root, key, val) } {
-        let root = if root.is_none() { Some(x) } else { root };
-        // 修正，使树恢复平衡
-        insert_fix(root, Some(x))
-    } else {
-        None
+    fn delete(&mut self, key: &K) {
+        if let Some(z) = unsafe { bst::find(self.root, key) } {
+            self.root = delete(self.root, z);
+            self.set_size(self.size() - 1);
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        unsafe { bst::find(self.root, key).and_then(|p| p.as_ref().val.as_ref()) }
+    }
+
+    fn min(&self) -> Option<&K> {
+        unsafe { bst::find_min(self.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn max(&self) -> Option<&K> {
+        unsafe { bst::find_max(self.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn is_valid(&self) -> bool
+    where
+        K: Debug,
+    {
+        self.check(Balance::RedBlack).is_ok()
     }
 }
 
@@ -191,6 +225,143 @@ fn insert_fix<K, V>(
     t.node
 }
 
+/// 从rb tree中删除节点 z 的过程与bst相同:
+///   如果 z 没有子节点，或者只有一个孩子，用孩子 x 替换 z;
+///   否则，用 z 的后继 y 替换 z，y 继承 z 的颜色，y 的右孩子 x 替换 y 原来的位置。
+/// 如果被移走的节点(z 或 y)是黑色，x 所在路径上少了一个黑色节点，需要修正。
+fn delete<K, V>(
+    mut root: Option<NonNull<Node<K, V>>>,
+    z: NonNull<Node<K, V>>,
+) -> Option<NonNull<Node<K, V>>>
+where
+    K: Ord,
+{
+    let z = NodeQuery::new(Some(z));
+    let mut removed_color = z.color();
+    let x;
+    let x_parent;
+    if z.left().is_none() {
+        x = z.right().node;
+        x_parent = z.parent().node;
+        root = transplant(root, z.node, x);
+    } else if z.right().is_none() {
+        x = z.left().node;
+        x_parent = z.parent().node;
+        root = transplant(root, z.node, x);
+    } else {
+        let mut y = NodeQuery::new(unsafe { bst::find_min(z.right().node) });
+        removed_color = y.color();
+        x = y.right().node;
+        if y.parent().node == z.node {
+            x_parent = y.node;
+        } else {
+            x_parent = y.parent().node;
+            root = transplant(root, y.node, x);
+            y.set_right(z.right().node);
+        }
+        root = transplant(root, z.node, y.node);
+        y.set_left(z.left().node);
+        y.set_color(z.color().unwrap());
+    }
+    Node::release(z.node.unwrap());
+    bst::update_size_upward(x_parent);
+
+    if removed_color == Some(Color::Black) {
+        root = delete_fix(root, x, x_parent);
+    }
+    root
+}
+
+/// x 所在的路径上少了一个黑色节点，x 可能为 NIL，所以同时给出 x 的 parent
+fn delete_fix<K, V>(
+    root: Option<NonNull<Node<K, V>>>,
+    x: Option<NonNull<Node<K, V>>>,
+    parent: Option<NonNull<Node<K, V>>>,
+) -> Option<NonNull<Node<K, V>>> {
+    let mut t = NodeQuery::new(root);
+    let mut x = NodeQuery::new(x);
+    let mut parent = NodeQuery::new(parent);
+    while x.node != t.node && x.is_black() {
+        if parent.left().node == x.node {
+            let mut w = parent.right();
+            if w.is_red() {
+                // case 1: 兄弟为红色 => 兄弟为黑色
+                w.set_color(Color::Black);
+                parent.set_color(Color::Red);
+                t.node = rotate_left(t.node, parent.node.unwrap());
+                w = parent.right();
+            }
+            if w.left().is_black() && w.right().is_black() {
+                // case 2: 兄弟为黑色，两个侄子都是黑色 => 把缺少的黑色上移
+                w.set_color(Color::Red);
+                x = NodeQuery::new(parent.node);
+                parent = x.parent();
+            } else {
+                if w.right().is_black() {
+                    // case 3: 兄弟为黑色，近侄子为红色，远侄子为黑色 => case 4
+                    w.left().set_color(Color::Black);
+                    w.set_color(Color::Red);
+                    t.node = rotate_right(t.node, w.node.unwrap());
+                    w = parent.right();
+                }
+                // case 4: 兄弟为黑色，远侄子为红色
+                w.set_color(parent.color().unwrap());
+                parent.set_color(Color::Black);
+                w.right().set_color(Color::Black);
+                t.node = rotate_left(t.node, parent.node.unwrap());
+                x = NodeQuery::new(t.node);
+            }
+        } else {
+            let mut w = parent.left();
+            if w.is_red() {
+                // case 1'
+                w.set_color(Color::Black);
+                parent.set_color(Color::Red);
+                t.node = rotate_right(t.node, parent.node.unwrap());
+                w = parent.left();
+            }
+            if w.left().is_black() && w.right().is_black() {
+                // case 2'
+                w.set_color(Color::Red);
+                x = NodeQuery::new(parent.node);
+                parent = x.parent();
+            } else {
+                if w.left().is_black() {
+                    // case 3'
+                    w.right().set_color(Color::Black);
+                    w.set_color(Color::Red);
+                    t.node = rotate_left(t.node, w.node.unwrap());
+                    w = parent.left();
+                }
+                // case 4'
+                w.set_color(parent.color().unwrap());
+                parent.set_color(Color::Black);
+                w.left().set_color(Color::Black);
+                t.node = rotate_right(t.node, parent.node.unwrap());
+                x = NodeQuery::new(t.node);
+            }
+        }
+    }
+    x.set_color(Color::Black);
+    t.node
+}
+
+/// replace the subtree rooted at u with the subtree rooted at v
+fn transplant<K, V>(
+    root: Option<NonNull<Node<K, V>>>,
+    u: Option<NonNull<Node<K, V>>>,
+    v: Option<NonNull<Node<K, V>>>,
+) -> Option<NonNull<Node<K, V>>> {
+    let mut u = NodeQuery::new(u);
+    let p = u.parent();
+    u.replace(v);
+    if p.is_none() {
+        v
+    } else {
+        root
+    }
+}
+
 /*
 左旋操作变换为:
 
@@ -201,7 +372,7 @@ fn insert_fix<K, V>(
          b    c           a     b
 
  */
-fn rotate_left<K, V>(
+pub fn rotate_left<K, V>(
     mut root: Option<NonNull<Node<K, V>>>,
     x: NonNull<Node<K, V>>,
 ) -> Option<NonNull<Node<K, V>>> {
@@ -214,6 +385,8 @@ fn rotate_left<K, V>(
     x.replace(y.node);
     x.set_children(a.node, b.node);
     y.set_children(x.node, c.node);
+    x.update_size();
+    y.update_size();
     if p.is_none() {
         root = y.node;
     }
@@ -230,7 +403,7 @@ fn rotate_left<K, V>(
   a     b                    b     c
 
  */
-fn rotate_right<K, V>(
+pub fn rotate_right<K, V>(
     mut root: Option<NonNull<Node<K, V>>>,
     y: NonNull<Node<K, V>>,
 ) -> Option<NonNull<Node<K, V>>> {
@@ -243,6 +416,8 @@ fn rotate_right<K, V>(
     y.replace(x.node);
     y.set_children(b.node, c.node);
     x.set_children(a.node, y.node);
+    y.update_size();
+    x.update_size();
     if p.is_none() {
         root = x.node;
     }
@@ -268,7 +443,7 @@ fn t_insert() {
     */
     let mut tree = Tree::default();
     for v in vec![15, 14, 11, 2, 1, 7, 5, 4, 8] {
-        tree.root = insert(tree.root, v, v);
+        tree.insert(v, v);
     }
     unsafe {
         assert_eq!(
@@ -294,7 +469,7 @@ fn t_insert() {
     */
     let mut tree = Tree::default();
     for v in 1..9 {
-        tree.root = insert(tree.root, v, v);
+        tree.insert(v, v);
     }
     unsafe {
         assert_eq!(
@@ -93,15 +93,23 @@
 //          left       right       color
 //         rotate  => rotate  =>   flip    =>
 //
+use crate::tree::binary::check::{self, Balance};
+use crate::tree::binary::entry::Entry;
 use crate::tree::binary::node::Color;
 use crate::tree::binary::{bst, Node, NodeQuery, Tree};
 use std::cmp::Ordering;
-use std::ptr;
+use std::fmt::Debug;
+use std::mem;
 use std::ptr::NonNull;
 
 pub trait RedBlackTreeV2<K, V> {
     fn insert(&mut self, key: K, val: V);
     fn get(&self, key: &K) -> Option<&V>;
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+    /// Gets the entry of the key for in-place manipulation
+    fn entry(&mut self, key: K) -> Entry<'_, K, V>;
+    /// Removes the specified element, returns its value
+    fn remove(&mut self, k: &K) -> Option<V>;
     /// Removes the smallest element
     fn delete_min(&mut self);
     /// Removes the largest element
@@ -116,6 +124,29 @@ pub trait RedBlackTreeV2<K, V> {
     fn max(&self) -> Option<&K>;
     /// Returns all keys in the symbol table
     fn keys(&self) -> Vec<&K>;
+    /// Splits the tree into two trees, the first one has the keys less
+    /// than key, the second one has the rest
+    fn split(self, key: &K) -> (Self, Self)
+    where
+        Self: Sized;
+    /// Concatenates two trees, all keys of left must be less than
+    /// the keys of right
+    fn join(left: Self, right: Self) -> Self
+    where
+        Self: Sized;
+    /// Returns the elements in either tree, the value in self is
+    /// kept if the key is in both trees
+    fn union(self, other: Self) -> Self
+    where
+        Self: Sized;
+    /// Returns the elements of self whose key is in other
+    fn intersection(self, other: Self) -> Self
+    where
+        Self: Sized;
+    /// Returns the elements of self whose key is not in other
+    fn difference(self, other: Self) -> Self
+    where
+        Self: Sized;
 }
 
 impl<K, V> RedBlackTreeV2<K, V> for Tree<K, V>
@@ -123,25 +154,39 @@ where
     K: Ord,
 {
     fn insert(&mut self, key: K, val: V) {
-        let new_root = put(self.root, key, val);
-        NodeQuery::new(new_root).set_color(Color::Black);
-        self.root = new_root;
-        self.set_size(bst::calc_size(self.root));
+        self.root = put_root::<K, V, SizeOnly>(self.root, key, val);
+        self.set_size(Node::size(self.root));
     }
 
     fn get(&self, key: &K) -> Option<&V> {
         unsafe { bst::find(self.root, key).and_then(|p| p.as_ref().val.as_ref()) }
     }
 
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { bst::find(self.root, key).and_then(|mut p| p.as_mut().val.as_mut()) }
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(self, key, insert_new)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let mut node = unsafe { bst::find(self.root, key) }?;
+        let val = unsafe { node.as_mut().val.take() };
+        self.root = delete_root::<K, V, SizeOnly>(self.root, key);
+        self.set_size(self.size() - 1);
+        val
+    }
+
     fn delete_min(&mut self) {
         let mut root = NodeQuery::new(self.root);
         if root.is_some() {
             if !root.left().is_red() && !root.right().is_red() {
                 root.set_color(Color::Red);
             }
-            root.node = del_min(root.node);
+            root.node = del_min::<K, V, SizeOnly>(root.node);
             root.set_color(Color::Black);
-            self.root = root.node;
+            self.root = detach(root.node);
             self.set_size(self.size() - 1);
         }
     }
@@ -152,22 +197,16 @@ where
             if !root.left().is_red() && !root.right().is_red() {
                 root.set_color(Color::Red);
             }
-            root.node = del_max(root.node);
+            root.node = del_max::<K, V, SizeOnly>(root.node);
             root.set_color(Color::Black);
-            self.root = root.node;
+            self.root = detach(root.node);
             self.set_size(self.size() - 1);
         }
     }
 
     fn delete(&mut self, key: &K) {
         if self.contains(key) {
-            let mut root = NodeQuery::new(self.root);
-            if !root.left().is_red() && !root.right().is_red() {
-                root.set_color(Color::Red);
-            }
-            root.node = delete(root.node, key);
-            root.set_color(Color::Black);
-            self.root = root.node;
+            self.root = delete_root::<K, V, SizeOnly>(self.root, key);
             self.set_size(self.size() - 1);
         }
     }
@@ -195,52 +234,537 @@ where
             queue
         }
     }
+
+    fn split(mut self, key: &K) -> (Self, Self) {
+        let root = self.root.take();
+        let (l, _, mid, r, rb) = split::<K, V, SizeOnly>(root, black_height(root), key);
+        let r = match mid {
+            Some(mid) => join3::<K, V, SizeOnly>(None, 0, mid, r, rb).0,
+            None => r,
+        };
+        (from_root(l), from_root(r))
+    }
+
+    fn join(mut left: Self, mut right: Self) -> Self {
+        if let (Some(max), Some(min)) = (left.max(), right.min()) {
+            assert!(max < min, "keys of left must be less than keys of right");
+        }
+        let (l, r) = (left.root.take(), right.root.take());
+        let (root, _) = join2::<K, V, SizeOnly>(l, black_height(l), r, black_height(r));
+        from_root(root)
+    }
+
+    fn union(mut self, mut other: Self) -> Self {
+        let (t1, t2) = (self.root.take(), other.root.take());
+        let (root, _) = union::<K, V, SizeOnly>(t1, black_height(t1), t2, black_height(t2));
+        from_root(root)
+    }
+
+    fn intersection(mut self, mut other: Self) -> Self {
+        let (t1, t2) = (self.root.take(), other.root.take());
+        let (root, _) = intersection::<K, V, SizeOnly>(t1, black_height(t1), t2, black_height(t2));
+        from_root(root)
+    }
+
+    fn difference(mut self, mut other: Self) -> Self {
+        let (t1, t2) = (self.root.take(), other.root.take());
+        let (root, _) = difference::<K, V, SizeOnly>(t1, black_height(t1), t2, black_height(t2));
+        from_root(root)
+    }
+}
+
+/// Keeps the augmented fields of a node in sync with its children,
+/// called bottom-up whenever the children of the node changed.
+///
+/// It lets other trees (e.g. interval search tree) reuse the rb tree
+/// machinery while maintaining their own fields.
+pub trait Augment<K, V> {
+    fn update(h: &mut NodeQuery<K, V>);
+}
+
+/// Maintains the subtree size only
+pub struct SizeOnly;
+
+impl<K, V> Augment<K, V> for SizeOnly {
+    fn update(h: &mut NodeQuery<K, V>) {
+        h.update_size();
+    }
+}
+
+/// inserts the key which is not in the tree, used by Entry
+fn insert_new<K, V>(tree: &mut Tree<K, V>, key: K, val: V) -> NonNull<Node<K, V>>
+where
+    K: Ord,
+{
+    let (root, x) = put_entry::<K, V, SizeOnly>(tree.root, key, val);
+    tree.root = root;
+    tree.set_size(tree.size() + 1);
+    x
+}
+
+/// insert the element in the tree rooted at root, returns the new root
+pub(crate) fn put_root<K, V, A>(
+    root: Option<NonNull<Node<K, V>>>,
+    key: K,
+    val: V,
+) -> Option<NonNull<Node<K, V>>>
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    put_entry::<K, V, A>(root, key, val).0
+}
+
+/// insert the element in the tree rooted at root,
+/// returns the new root and the node holding the element
+#[allow(clippy::type_complexity)]
+pub(crate) fn put_entry<K, V, A>(
+    root: Option<NonNull<Node<K, V>>>,
+    key: K,
+    val: V,
+) -> (Option<NonNull<Node<K, V>>>, NonNull<Node<K, V>>)
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    let mut x = None;
+    let new_root = put::<K, V, A>(root, key, val, &mut x);
+    NodeQuery::new(new_root).set_color(Color::Black);
+    (detach(new_root), x.unwrap())
+}
+
+/// delete the element from the tree rooted at root, returns the new root
+///
+/// the key must exist
+pub(crate) fn delete_root<K, V, A>(
+    root: Option<NonNull<Node<K, V>>>,
+    key: &K,
+) -> Option<NonNull<Node<K, V>>>
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    let mut root = NodeQuery::new(root);
+    if !root.left().is_red() && !root.right().is_red() {
+        root.set_color(Color::Red);
+    }
+    root.node = delete::<K, V, A>(root.node, key);
+    root.set_color(Color::Black);
+    detach(root.node)
+}
+
+/// the root has no parent
+fn detach<K, V>(root: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    if let Some(mut root) = root {
+        unsafe { root.as_mut().parent = None }
+    }
+    root
+}
+
+/*
+Split and join
+
+join3(l, k, r) concatenates l, the node k and r, where the keys of l are
+less than k, and the keys of r are greater than k. Let bh be the black
+height, if bh(l) > bh(r), walk down the right spine of l to the black node
+h with bh(h) == bh(r), and replace h by R(k) with children h and r. This is
+the same as inserting k into a 2-3 tree node at that level, so the tree is
+fixed up the same way as put on the way back. The bh(l) < bh(r) case walks
+down the left spine of r. It takes time proportional to |bh(l) - bh(r)| + 1,
+as long as bh(l) and bh(r) are given, counting them walks the spines.
+
+So the black heights are passed along with the trees: computed once at the
+root, one less for a black child, and join3 returns the black height of the
+result, one more if the root came out red.
+
+split(h, key) cuts the tree along the search path of key, and joins the
+pieces on the left side and the right side of the path bottom-up. Each side
+joins trees of increasing black heights, the cost of a join is the
+difference of the black heights (+1), so the costs telescope to the black
+height of h, and split takes time proportional to log N.
+
+union, intersection and difference split one tree by the root of the other,
+and join the results of the subproblems.
+*/
+
+/// make a tree of the root
+fn from_root<K, V>(root: Option<NonNull<Node<K, V>>>) -> Tree<K, V> {
+    let mut tree = Tree::default();
+    tree.root = root;
+    tree.set_size(Node::size(root));
+    tree
+}
+
+/// the root of a tree is black and has no parent
+pub(crate) fn make_root<K, V>(root: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+    NodeQuery::new(root).set_color(Color::Black);
+    detach(root)
+}
+
+/// black height of the tree rooted at root, with the root black
+pub(crate) fn black_height<K, V>(root: Option<NonNull<Node<K, V>>>) -> usize {
+    let red = NodeQuery::new(root).is_red();
+    calc_blacks(root) + red as usize
+}
+
+/// black height of the child, with the child black, its parent has
+/// black height hb
+fn child_height<K, V>(hb: usize, child: Option<NonNull<Node<K, V>>>) -> usize {
+    // a red child is under a black parent: hb - 1 blacks below it,
+    // one more when it becomes black; a black child has hb - 1
+    let red = NodeQuery::new(child).is_red();
+    hb - 1 + red as usize
+}
+
+/// concatenate l, k and r, all keys of l < k < all keys of r,
+/// lb and rb are the black heights of l and r, returns the new root
+/// and its black height
+pub(crate) fn join3<K, V, A>(
+    l: Option<NonNull<Node<K, V>>>,
+    lb: usize,
+    k: NonNull<Node<K, V>>,
+    r: Option<NonNull<Node<K, V>>>,
+    rb: usize,
+) -> (Option<NonNull<Node<K, V>>>, usize)
+where
+    A: Augment<K, V>,
+{
+    debug_assert_eq!(lb, black_height(l));
+    debug_assert_eq!(rb, black_height(r));
+    let l = make_root(l);
+    let r = make_root(r);
+    let root = if lb >= rb {
+        join_right::<K, V, A>(l, lb, k, r, rb)
+    } else {
+        join_left::<K, V, A>(l, lb, k, r, rb)
+    };
+    // the root is red if the 4-node split up to the top, one level more
+    let red = NodeQuery::new(root).is_red();
+    (make_root(root), lb.max(rb) + red as usize)
+}
+
+/// h is on the right spine of the taller tree, so it is black
+fn join_right<K, V, A>(
+    h: Option<NonNull<Node<K, V>>>,
+    hb: usize,
+    k: NonNull<Node<K, V>>,
+    r: Option<NonNull<Node<K, V>>>,
+    rb: usize,
+) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
+    let mut h = NodeQuery::new(h);
+    if hb == rb {
+        new_red::<K, V, A>(h.node, k, r)
+    } else {
+        h.set_right(join_right::<K, V, A>(h.right().node, hb - 1, k, r, rb));
+        balance::<K, V, A>(h.node)
+    }
+}
+
+/// h is on the left spine of the taller tree, it may be red
+fn join_left<K, V, A>(
+    l: Option<NonNull<Node<K, V>>>,
+    lb: usize,
+    k: NonNull<Node<K, V>>,
+    h: Option<NonNull<Node<K, V>>>,
+    hb: usize,
+) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
+    let mut h = NodeQuery::new(h);
+    if hb == lb && !h.is_red() {
+        new_red::<K, V, A>(l, k, h.node)
+    } else {
+        let left_b = if h.is_red() { hb } else { hb - 1 };
+        h.set_left(join_left::<K, V, A>(l, lb, k, h.left().node, left_b));
+        balance::<K, V, A>(h.node)
+    }
+}
+
+/// link l and r to k with a red link
+fn new_red<K, V, A>(
+    l: Option<NonNull<Node<K, V>>>,
+    k: NonNull<Node<K, V>>,
+    r: Option<NonNull<Node<K, V>>>,
+) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
+    let mut k = NodeQuery::new(Some(k));
+    k.set_left(l);
+    k.set_right(r);
+    k.set_color(Color::Red);
+    A::update(&mut k);
+    k.node
+}
+
+/// concatenate l and r, all keys of l < all keys of r,
+/// lb and rb are the black heights of l and r, returns the new root
+/// and its black height
+pub(crate) fn join2<K, V, A>(
+    l: Option<NonNull<Node<K, V>>>,
+    lb: usize,
+    r: Option<NonNull<Node<K, V>>>,
+    rb: usize,
+) -> (Option<NonNull<Node<K, V>>>, usize)
+where
+    A: Augment<K, V>,
+{
+    if r.is_none() {
+        return (make_root(l), lb);
+    }
+
+    // the min of r links l and the rest of r
+    let mut r = NodeQuery::new(r);
+    let mut rb = rb;
+    if !r.left().is_red() && !r.right().is_red() {
+        // the black root becomes red, one level less
+        r.set_color(Color::Red);
+        rb -= 1;
+    }
+    let mut min = None;
+    let r = take_min::<K, V, A>(r.node, &mut min);
+    // rotations and color flips keep the black height below the root,
+    // a red root becomes black in join3
+    let red = NodeQuery::new(r).is_red();
+    join3::<K, V, A>(l, lb, min.unwrap(), r, rb + red as usize)
+}
+
+/// split the tree rooted at h of black height hb into
+/// (keys < key, its black height, node of key, keys > key, its black height)
+#[allow(clippy::type_complexity)]
+fn split<K, V, A>(
+    h: Option<NonNull<Node<K, V>>>,
+    hb: usize,
+    key: &K,
+) -> (
+    Option<NonNull<Node<K, V>>>,
+    usize,
+    Option<NonNull<Node<K, V>>>,
+    Option<NonNull<Node<K, V>>>,
+    usize,
+)
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    match h {
+        None => (None, 0, None, None, 0),
+        Some(root) => {
+            let (l, lb, node, r, rb) = expose(root, hb);
+            match key.cmp(unsafe { &node.as_ref().key }) {
+                Ordering::Equal => (make_root(l), lb, h, make_root(r), rb),
+                Ordering::Less => {
+                    let (ll, llb, mid, lr, lrb) = split::<K, V, A>(l, lb, key);
+                    let (r, rb) = join3::<K, V, A>(lr, lrb, node, r, rb);
+                    (ll, llb, mid, r, rb)
+                }
+                Ordering::Greater => {
+                    let (rl, rlb, mid, rr, rrb) = split::<K, V, A>(r, rb, key);
+                    let (l, lb) = join3::<K, V, A>(l, lb, node, rl, rlb);
+                    (l, lb, mid, rr, rrb)
+                }
+            }
+        }
+    }
+}
+
+/// cut the root of black height hb off the tree,
+/// returns (left, its black height, root, right, its black height)
+#[allow(clippy::type_complexity)]
+pub(crate) fn expose<K, V>(
+    mut root: NonNull<Node<K, V>>,
+    hb: usize,
+) -> (
+    Option<NonNull<Node<K, V>>>,
+    usize,
+    NonNull<Node<K, V>>,
+    Option<NonNull<Node<K, V>>>,
+    usize,
+) {
+    let (l, r) = unsafe {
+        let root = root.as_mut();
+        (root.left.take(), root.right.take())
+    };
+    let (lb, rb) = (child_height(hb, l), child_height(hb, r));
+    (detach(l), lb, root, detach(r), rb)
+}
+
+fn union<K, V, A>(
+    t1: Option<NonNull<Node<K, V>>>,
+    b1: usize,
+    t2: Option<NonNull<Node<K, V>>>,
+    b2: usize,
+) -> (Option<NonNull<Node<K, V>>>, usize)
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    match (t1, t2) {
+        (None, t2) => (make_root(t2), b2),
+        (t1, None) => (make_root(t1), b1),
+        (Some(root), t2) => {
+            let (l1, lb1, k, r1, rb1) = expose(root, b1);
+            let (l2, lb2, dup, r2, rb2) = split::<K, V, A>(t2, b2, unsafe { &k.as_ref().key });
+            if let Some(dup) = dup {
+                Node::release(dup);
+            }
+            let (l, lb) = union::<K, V, A>(l1, lb1, l2, lb2);
+            let (r, rb) = union::<K, V, A>(r1, rb1, r2, rb2);
+            join3::<K, V, A>(l, lb, k, r, rb)
+        }
+    }
+}
+
+fn intersection<K, V, A>(
+    t1: Option<NonNull<Node<K, V>>>,
+    b1: usize,
+    t2: Option<NonNull<Node<K, V>>>,
+    b2: usize,
+) -> (Option<NonNull<Node<K, V>>>, usize)
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    match (t1, t2) {
+        (Some(root), Some(_)) => {
+            let (l1, lb1, k, r1, rb1) = expose(root, b1);
+            let (l2, lb2, dup, r2, rb2) = split::<K, V, A>(t2, b2, unsafe { &k.as_ref().key });
+            let (l, lb) = intersection::<K, V, A>(l1, lb1, l2, lb2);
+            let (r, rb) = intersection::<K, V, A>(r1, rb1, r2, rb2);
+            match dup {
+                Some(dup) => {
+                    Node::release(dup);
+                    join3::<K, V, A>(l, lb, k, r, rb)
+                }
+                None => {
+                    Node::release(k);
+                    join2::<K, V, A>(l, lb, r, rb)
+                }
+            }
+        }
+        (t1, t2) => {
+            drop(from_root(t1));
+            drop(from_root(t2));
+            (None, 0)
+        }
+    }
+}
+
+fn difference<K, V, A>(
+    t1: Option<NonNull<Node<K, V>>>,
+    b1: usize,
+    t2: Option<NonNull<Node<K, V>>>,
+    b2: usize,
+) -> (Option<NonNull<Node<K, V>>>, usize)
+where
+    K: Ord,
+    A: Augment<K, V>,
+{
+    match (t1, t2) {
+        (None, t2) => {
+            drop(from_root(t2));
+            (None, 0)
+        }
+        (t1, None) => (make_root(t1), b1),
+        (t1, Some(root)) => {
+            let (l2, lb2, k, r2, rb2) = expose(root, b2);
+            let (l1, lb1, dup, r1, rb1) = split::<K, V, A>(t1, b1, unsafe { &k.as_ref().key });
+            if let Some(dup) = dup {
+                Node::release(dup);
+            }
+            Node::release(k);
+            let (l, lb) = difference::<K, V, A>(l1, lb1, l2, lb2);
+            let (r, rb) = difference::<K, V, A>(r1, rb1, r2, rb2);
+            join2::<K, V, A>(l, lb, r, rb)
+        }
+    }
 }
 
 /// insert the element in the subtree rooted at h
-fn put<K, V>(h: Option<NonNull<Node<K, V>>>, key: K, val: V) -> Option<NonNull<Node<K, V>>>
+///
+/// the node holding the element is stored in x, nodes never move
+/// while rebalancing, so it is still valid after put returns
+fn put<K, V, A>(
+    h: Option<NonNull<Node<K, V>>>,
+    key: K,
+    val: V,
+    x: &mut Option<NonNull<Node<K, V>>>,
+) -> Option<NonNull<Node<K, V>>>
 where
     K: Ord,
+    A: Augment<K, V>,
 {
     let mut h = NodeQuery::new(h);
 
     match h.get_key() {
-        None => return Some(Node::new_leaf(key, Some(val), None)),
+        None => {
+            let mut leaf = NodeQuery::new(Some(Node::new_leaf(key, Some(val), None)));
+            A::update(&mut leaf);
+            *x = leaf.node;
+            return leaf.node;
+        }
         Some(h_key) => match key.cmp(h_key) {
-            Ordering::Equal => h.set_entry((key, Some(val))), // update val
-            Ordering::Less => h.set_left(put(h.left().node, key, val)),
-            Ordering::Greater => h.set_right(put(h.right().node, key, val)),
+            Ordering::Equal => {
+                h.set_entry((key, Some(val))); // update val
+                *x = h.node;
+            }
+            Ordering::Less => h.set_left(put::<K, V, A>(h.left().node, key, val, x)),
+            Ordering::Greater => h.set_right(put::<K, V, A>(h.right().node, key, val, x)),
         },
     }
 
-    balance(h.node)
+    balance::<K, V, A>(h.node)
 }
 
 /// delete the min element rooted at h
-fn del_min<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn del_min<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
+    let mut min = None;
+    let h = take_min::<K, V, A>(h, &mut min);
+    if let Some(min) = min {
+        Node::release(min);
+    }
+    h
+}
+
+/// unlink the min node rooted at h without releasing it, the node is stored in min
+fn take_min<K, V, A>(
+    h: Option<NonNull<Node<K, V>>>,
+    min: &mut Option<NonNull<Node<K, V>>>,
+) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
 
     if h.left().is_none() {
-        if let Some(h) = h.node.take() {
-            Node::release(h);
-        }
+        *min = h.node.take();
         None
     } else {
         if !h.left().is_red() && !h.left().left().is_red() {
-            h.node = move_red_left(h.node);
+            h.node = move_red_left::<K, V, A>(h.node);
         }
-        let new_left = del_min(h.left().node);
+        let new_left = take_min::<K, V, A>(h.left().node, min);
         h.set_left(new_left);
-        balance(h.node)
+        balance::<K, V, A>(h.node)
     }
 }
 
 /// delete the max element rooted at h
-fn del_max<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn del_max<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
 
     if h.left().is_red() {
-        h.node = rotate_right(h.node);
+        h.node = rotate_right::<K, V, A>(h.node);
     }
 
     if h.right().is_none() {
@@ -250,45 +774,55 @@ fn del_max<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
         None
     } else {
         if !h.right().is_red() && !h.right().left().is_red() {
-            h.node = move_red_right(h.node);
+            h.node = move_red_right::<K, V, A>(h.node);
         }
-        let new_right = del_max(h.right().node);
+        let new_right = del_max::<K, V, A>(h.right().node);
         h.set_right(new_right);
-        balance(h.node)
+        balance::<K, V, A>(h.node)
     }
 }
 
-fn delete<K, V>(h: Option<NonNull<Node<K, V>>>, key: &K) -> Option<NonNull<Node<K, V>>>
+fn delete<K, V, A>(h: Option<NonNull<Node<K, V>>>, key: &K) -> Option<NonNull<Node<K, V>>>
 where
     K: Ord,
+    A: Augment<K, V>,
 {
     let mut h = NodeQuery::new(h);
     if key < h.get_key().unwrap() {
         if !h.left().is_red() && !h.left().left().is_red() {
-            h.node = move_red_left(h.node);
+            h.node = move_red_left::<K, V, A>(h.node);
         }
-        h.set_left(delete(h.left().node, key));
+        h.set_left(delete::<K, V, A>(h.left().node, key));
     } else {
         if h.left().is_red() {
-            h.node = rotate_right(h.node);
+            h.node = rotate_right::<K, V, A>(h.node);
         }
         if key == h.get_key().unwrap() && h.right().is_none() {
             Node::release(h.node.unwrap());
             return None;
         }
         if !h.right().is_red() && !h.right().left().is_red() {
-            h.node = move_red_right(h.node);
+            h.node = move_red_right::<K, V, A>(h.node);
         }
         if key == h.get_key().unwrap() {
+            // 与右子树的最小节点交换entry，再删除这一最小节点，
+            // 被删除的entry随最小节点一起释放
             let x = unsafe { bst::find_min(h.right().node) };
-            h.copy_entry(x.unwrap());
-            h.set_right(del_min(h.right().node));
+            swap_entry(h.node.unwrap(), x.unwrap());
+            h.set_right(del_min::<K, V, A>(h.right().node));
         } else {
-            h.set_right(delete(h.right().node, key));
+            h.set_right(delete::<K, V, A>(h.right().node, key));
         }
     }
 
-    balance(h.node)
+    balance::<K, V, A>(h.node)
+}
+
+fn swap_entry<K, V>(mut a: NonNull<Node<K, V>>, mut b: NonNull<Node<K, V>>) {
+    unsafe {
+        mem::swap(&mut a.as_mut().key, &mut b.as_mut().key);
+        mem::swap(&mut a.as_mut().val, &mut b.as_mut().val);
+    }
 }
 
 /*
@@ -302,13 +836,18 @@ where
 旋转操作可以保持rb tree的两个重要性质：有序性(中序)和完美平衡性
 */
 /// make a right-leaning link lean to the left
-fn rotate_left<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn rotate_left<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
     let mut x = h.right();
     h.set_right(x.left().node);
     x.set_left(h.node);
     x.set_color(h.color().unwrap());
     h.set_color(Color::Red);
+    A::update(&mut h);
+    A::update(&mut x);
     x.node
 }
 
@@ -321,13 +860,18 @@ fn rotate_left<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V
 
 */
 /// make a left-leaning link lean to the right
-fn rotate_right<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn rotate_right<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
     let mut x = h.left();
     h.set_left(x.right().node);
     x.set_right(h.node);
     x.set_color(h.color().unwrap());
     h.set_color(Color::Red);
+    A::update(&mut h);
+    A::update(&mut x);
     x.node
 }
 
@@ -340,46 +884,35 @@ fn flip_colors<K, V>(h: Option<NonNull<Node<K, V>>>) {
 }
 
 /// restore red-black tree invariant
-fn balance<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn balance<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
     if h.right().is_red() && !h.left().is_red() {
-        h.node = rotate_left(h.node);
+        h.node = rotate_left::<K, V, A>(h.node);
     }
     if h.left().is_red() && h.left().left().is_red() {
-        h.node = rotate_right(h.node);
+        h.node = rotate_right::<K, V, A>(h.node);
     }
     if h.left().is_red() && h.right().is_red() {
         flip_colors(h.node);
     }
+    A::update(&mut h);
     h.node
 }
 
-/// does every path from the root to a leaf have the given number of black links?
-fn is_balance<K, V>(root: Option<NonNull<Node<K, V>>>) -> bool {
-    fn counter<K, V>(h: Option<NonNull<Node<K, V>>>, mut black: usize) -> bool {
-        if h.is_none() {
-            0 == black
-        } else {
-            let h = NodeQuery::new(h);
-            if !h.is_red() {
-                black -= 1;
-            }
-            counter(h.left().node, black) && counter(h.right().node, black)
-        }
-    }
-
-    let black = calc_blacks(root);
-    counter(root, black)
-}
-
 /// Assuming that h is red and both h.left and h.left.left
 /// are black, make h.left or one of its children red.
-fn move_red_left<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn move_red_left<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
     flip_colors(h.node);
     if h.right().left().is_red() {
-        h.set_right(rotate_right(h.right().node));
-        h.node = rotate_left(h.node);
+        h.set_right(rotate_right::<K, V, A>(h.right().node));
+        h.node = rotate_left::<K, V, A>(h.node);
         flip_colors(h.node);
     }
     h.node
@@ -387,30 +920,25 @@ fn move_red_left<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K,
 
 /// Assuming that h is red and both h.right and h.right.left
 /// are black, make h.right or one of its children red.
-fn move_red_right<K, V>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>> {
+fn move_red_right<K, V, A>(h: Option<NonNull<Node<K, V>>>) -> Option<NonNull<Node<K, V>>>
+where
+    A: Augment<K, V>,
+{
     let mut h = NodeQuery::new(h);
     flip_colors(h.node);
     if h.left().left().is_red() {
-        h.node = rotate_right(h.node);
+        h.node = rotate_right::<K, V, A>(h.node);
         flip_colors(h.node);
     }
     h.node
 }
 
-/// Does the tree have no red right links, and at most one (left)
-/// red links in a row on any path?
-fn is23<K, V>(root: Option<NonNull<Node<K, V>>>, x: Option<NonNull<Node<K, V>>>) -> bool {
-    if x.is_none() {
-        true
-    } else {
-        let x = NodeQuery::new(x);
-        let root = NodeQuery::new(root);
-        if x.right().is_red() || (x.node != root.node && x.is_red() && x.left().is_red()) {
-            false
-        } else {
-            is23(root.node, x.left().node) && is23(root.node, x.right().node)
-        }
-    }
+/// Is the tree rooted at root a valid left-leaning red-black tree?
+pub fn is_llrb<K, V>(root: Option<NonNull<Node<K, V>>>) -> bool
+where
+    K: Ord + Debug,
+{
+    check::check(root, Balance::LeftLeaningRedBlack).is_ok()
 }
 
 fn calc_blacks<K, V>(x: Option<NonNull<Node<K, V>>>) -> usize {
@@ -432,9 +960,7 @@ fn t_verify() {
         tree.insert(v, v);
     }
 
-    assert!(bst::is_bst(tree.root, None, None));
-    assert!(is23(tree.root, tree.root));
-    assert!(is_balance(tree.root));
+    assert_eq!(Ok(()), tree.check(Balance::LeftLeaningRedBlack));
 }
 
 #[test]
@@ -0,0 +1,257 @@
+//! Rendering binary trees, to Graphviz DOT and to ASCII art
+//!
+//! Both renderings can be read back into the level notation of
+//! builder::level::BuildTreeInLevel, so a tree can be drawn, edited and
+//! rebuilt:
+//!
+//!   let tokens = render::ascii_to_level(&tree.to_ascii())?;
+//!   let tokens: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+//!   let tree: Tree<i32, ()> = TreeBuilder::build_in_level(&tokens);
+//!
+//! The level notation numbers nodes as in a complete binary tree, so it
+//! is meant for small trees, e.g. the test fixtures.
+
+/*
+ASCII art
+
+Every node has its own column, ordered by key (in-order), and a row
+for every level. A left child is followed by '/', a right child is
+preceded by '\', on the line above it.
+
+         4
+        /     \
+       3       8
+  /           /           \
+ 1           7             16
+  \                    /
+   2                 10
+                    /  \
+                   9    14
+*/
+
+use crate::tree::binary::builder::level;
+use crate::tree::binary::node::Color;
+use crate::tree::binary::{Node, NodeQuery, Tree};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Write};
+use std::ptr::NonNull;
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+impl<K, V> Tree<K, V>
+where
+    K: Display,
+{
+    /// Renders the tree in Graphviz DOT, the nodes are filled with
+    /// their color, and the parent links are drawn as dashed edges.
+    ///
+    /// The id of a node is its path from the root, "n" for the root,
+    /// "nl" for the left child of the root, "nlr"...
+    pub fn to_dot(&self) -> String {
+        let mut ids = HashMap::new();
+        let mut nodes = Vec::new();
+        walk(self.root, "n".to_string(), &mut |x, id| {
+            ids.insert(x, id.clone());
+            nodes.push((x, id));
+        });
+
+        let mut s = String::new();
+        s.push_str("digraph {\n");
+        s.push_str("    node [shape=circle, style=filled, fontcolor=white];\n");
+        for (x, id) in nodes.iter() {
+            let h = NodeQuery::new(Some(*x));
+            let key = escape(&h.get_key().unwrap().to_string());
+            let color = match h.color() {
+                Some(Color::Black) => "black",
+                _ => "red",
+            };
+            let _ = writeln!(s, "    {} [label=\"{}\", fillcolor={}];", id, key, color);
+
+            // the nil child is drawn, so that a single child leans to its side
+            let one_child = h.left().is_none() != h.right().is_none();
+            for (c, side) in [(h.left(), 'l'), (h.right(), 'r')].iter() {
+                if c.is_some() {
+                    let _ = writeln!(s, "    {} -> {}{};", id, id, side);
+                } else if one_child {
+                    let _ = writeln!(s, "    {}{} [label=\"#\", shape=point];", id, side);
+                    let _ = writeln!(s, "    {} -> {}{};", id, id, side);
+                }
+            }
+
+            if let Some(p) = h.parent().node.and_then(|p| ids.get(&p)) {
+                let _ = writeln!(
+                    s,
+                    "    {} -> {} [style=dashed, color=gray, constraint=false];",
+                    id, p
+                );
+            }
+        }
+        s.push_str("}\n");
+        s
+    }
+
+    /// Renders the tree in ASCII art, a row for every level
+    pub fn to_ascii(&self) -> String {
+        // (node, depth), in order
+        let mut nodes: Vec<(NonNull<Node<K, V>>, usize)> = Vec::new();
+        in_order(self.root, 0, &mut nodes);
+        let keys: Vec<String> = nodes
+            .iter()
+            .map(|(x, _)| unsafe { x.as_ref().key.to_string() })
+            .collect();
+
+        // cell of the i-th node: [i * (w + 1), i * (w + 1) + w),
+        // the columns between the cells hold the links
+        let w = keys.iter().map(|k| k.chars().count()).max().unwrap_or(0);
+        let height = nodes.iter().map(|(_, d)| d + 1).max().unwrap_or(0);
+        let width = nodes.len() * (w + 1);
+        let mut rows = vec![vec![' '; width]; (2 * height).saturating_sub(1)];
+        for (i, ((x, depth), key)) in nodes.iter().zip(keys.iter()).enumerate() {
+            let h = NodeQuery::new(Some(*x));
+            let start = i * (w + 1);
+            let len = key.chars().count();
+            // a child hugs its link
+            let at = if h.parent().is_some() && h.i_am_left() {
+                rows[2 * depth - 1][start + w] = '/';
+                start + w - len
+            } else if h.parent().is_some() {
+                rows[2 * depth - 1][start - 1] = '\\';
+                start
+            } else {
+                start + (w - len) / 2
+            };
+            for (j, c) in key.chars().enumerate() {
+                rows[2 * depth][at + j] = c;
+            }
+        }
+
+        let mut s = String::new();
+        for row in rows.iter() {
+            let line: String = row.iter().collect();
+            s.push_str(line.trim_end());
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// Reads back the output of Tree::to_dot, returns the tree in
+/// the level notation
+pub fn dot_to_level(dot: &str) -> Result<Vec<String>, String> {
+    let mut slots = BTreeMap::new();
+    for line in dot.lines().map(str::trim) {
+        // n.. [label="..", ..];
+        let (id, rest) = match line.split_once(" [label=\"") {
+            Some(v) => v,
+            None => continue,
+        };
+        let label = unescape(rest.rsplit_once("\",").map_or(rest, |(l, _)| l));
+        if label == "#" {
+            continue;
+        }
+        let mut pos: usize = 0;
+        for c in id.chars().skip(1) {
+            pos = match c {
+                'l' => pos.checked_mul(2).and_then(|p| p.checked_add(1)),
+                'r' => pos.checked_mul(2).and_then(|p| p.checked_add(2)),
+                _ => return Err(format!("bad node id {}", id)),
+            }
+            .ok_or("tree is too deep for the level notation")?;
+        }
+        slots.insert(pos, label);
+    }
+    Ok(level::from_heap(slots))
+}
+
+/// Reads back the output of Tree::to_ascii, returns the tree in
+/// the level notation
+pub fn ascii_to_level(ascii: &str) -> Result<Vec<String>, String> {
+    let lines: Vec<Vec<char>> = ascii.lines().map(|l| l.chars().collect()).collect();
+    // the first line is the root, then a line of links and
+    // a line of keys for every level
+    let mut levels: Vec<Vec<(usize, usize, usize)>> = Vec::new();
+    let mut slots = BTreeMap::new();
+    for (n, line) in lines.iter().enumerate().step_by(2) {
+        let mut level = Vec::new();
+        for (start, end) in tokens(line) {
+            let key: String = line[start..end].iter().collect();
+            let pos = if n == 0 {
+                0
+            } else {
+                let links = &lines[n - 1];
+                let above = &levels[levels.len() - 1];
+                let link = |col: usize| links.get(col).copied();
+                let parent = if link(end) == Some('/') {
+                    above
+                        .iter()
+                        .find(|(s, _, _)| *s > end)
+                        .map(|&(_, _, p)| p.checked_mul(2).and_then(|p| p.checked_add(1)))
+                } else if start > 0 && link(start - 1) == Some('\\') {
+                    above
+                        .iter()
+                        .rev()
+                        .find(|(_, e, _)| *e < start)
+                        .map(|&(_, _, p)| p.checked_mul(2).and_then(|p| p.checked_add(2)))
+                } else {
+                    return Err(format!("key {} has no link", key));
+                };
+                parent
+                    .ok_or(format!("key {} has no parent", key))?
+                    .ok_or("tree is too deep for the level notation")?
+            };
+            level.push((start, end, pos));
+            slots.insert(pos, key);
+        }
+        levels.push(level);
+    }
+    Ok(level::from_heap(slots))
+}
+
+// preorder, with the path of the node
+fn walk<K, V, F>(x: Link<K, V>, id: String, f: &mut F)
+where
+    F: FnMut(NonNull<Node<K, V>>, String),
+{
+    if let Some(node) = x {
+        let h = NodeQuery::new(x);
+        f(node, id.clone());
+        walk(h.left().node, format!("{}l", id), f);
+        walk(h.right().node, format!("{}r", id), f);
+    }
+}
+
+fn in_order<K, V>(x: Link<K, V>, depth: usize, nodes: &mut Vec<(NonNull<Node<K, V>>, usize)>) {
+    if let Some(node) = x {
+        let h = NodeQuery::new(x);
+        in_order(h.left().node, depth + 1, nodes);
+        nodes.push((node, depth));
+        in_order(h.right().node, depth + 1, nodes);
+    }
+}
+
+// [start, end) of the keys in a line
+fn tokens(line: &[char]) -> Vec<(usize, usize)> {
+    let is_key = |c: char| !c.is_whitespace() && c != '/' && c != '\\';
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < line.len() {
+        if is_key(line[i]) {
+            let start = i;
+            while i < line.len() && is_key(line[i]) {
+                i += 1;
+            }
+            tokens.push((start, i));
+        } else {
+            i += 1;
+        }
+    }
+    tokens
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("\\\"", "\"").replace("\\\\", "\\")
+}
@@ -0,0 +1,595 @@
+//! Rope, a balanced binary tree of string chunks for editing large text
+//!
+//! Every node holds a chunk of the text, the text is the chunks in
+//! symmetric order. Every node also keeps the number of chars and
+//! newlines in the subtree rooted at it, so a char index or a line
+//! number is found by walking down from the root:
+//!
+//!   - go left, if the index is in the left subtree
+//!   - stay, if it is in the chunk of the node
+//!   - go right with index - (chars of left subtree + chars of chunk)
+//!
+//! The tree is a left-leaning red-black tree (rb2), the counts are kept
+//! in sync through rb2::Augment. Concat is rb2 join2, and split cuts the
+//! tree along the search path of the index, cutting the chunk on the
+//! path in two if needed, then joins the pieces on both sides bottom-up
+//! (like rb2 split by key). The black heights of the pieces are carried
+//! along instead of counted again, so both take time proportional to
+//! log N.
+//!
+//! From text, the tree is built bottom-up from the list of chunks in
+//! linear time: a 2-3 tree whose every level is full, a 3-node is a
+//! black node with a red left child.
+//!
+//! insert = split + concat, delete = split + split + concat. A small
+//! insert or delete inside one chunk edits the chunk in place and
+//! updates the counts on the path to the root.
+//!
+//! Indexes are char indexes, a char is a Unicode scalar value.
+
+use crate::tree::binary::node::Color;
+use crate::tree::binary::rb2::{self, Augment};
+use crate::tree::binary::{Node, NodeQuery, Tree};
+use std::fmt;
+use std::ops::Range;
+use std::ptr::NonNull;
+
+/// max bytes of a chunk built from text, an edit in place may not
+/// grow a chunk over it either
+const MAX_CHUNK: usize = 1024;
+
+pub struct Rope {
+    tree: Tree<Chunk, Summary>,
+}
+
+// key stored in node, never empty
+struct Chunk {
+    text: String,
+    chars: usize,
+    newlines: usize,
+}
+
+// value stored in node, counts of the subtree rooted at this node
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+struct Summary {
+    bytes: usize,
+    chars: usize,
+    newlines: usize,
+}
+
+// keeps Summary and node size in sync with children
+struct Weight;
+
+type Link = Option<NonNull<Node<Chunk, Summary>>>;
+
+// a tree and its black height
+type Part = (Link, usize);
+
+impl Rope {
+    pub fn new() -> Self {
+        Self {
+            tree: Tree::default(),
+        }
+    }
+
+    /// Returns the number of chars
+    pub fn len(&self) -> usize {
+        summary(self.tree.root).chars
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.root.is_none()
+    }
+
+    /// Returns the number of bytes in UTF-8
+    pub fn len_bytes(&self) -> usize {
+        summary(self.tree.root).bytes
+    }
+
+    /// Returns the number of lines, it is the number of '\n' + 1
+    pub fn len_lines(&self) -> usize {
+        summary(self.tree.root).newlines + 1
+    }
+
+    /// Returns the char at index i
+    pub fn char_at(&self, i: usize) -> Option<char> {
+        if i >= self.len() {
+            return None;
+        }
+        let (x, offset) = locate(self.tree.root, i);
+        chunk(x).text.chars().nth(offset)
+    }
+
+    /// Returns the chars in range as a String
+    pub fn slice(&self, range: Range<usize>) -> String {
+        self.check_range(&range);
+        let mut s = String::with_capacity(range.end - range.start);
+        collect(self.tree.root, range.start, range.end, &mut s);
+        s
+    }
+
+    /// Inserts text at char index i
+    pub fn insert(&mut self, i: usize, text: &str) {
+        assert!(
+            i <= self.len(),
+            "index {} out of bounds, len is {}",
+            i,
+            self.len()
+        );
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(root) = self.tree.root {
+            let (mut x, offset) = locate(Some(root), i);
+            let c = unsafe { &mut x.as_mut().key };
+            if c.text.len() + text.len() <= MAX_CHUNK {
+                let at = byte_offset(&c.text, offset);
+                c.text.insert_str(at, text);
+                *c = Chunk::new(std::mem::take(&mut c.text));
+                fix_up(x);
+                return;
+            }
+        }
+
+        let (l, r) = split(self.take_root(), i);
+        let l = join2(l, build(text));
+        self.set_root(join2(l, r).0);
+    }
+
+    /// Removes the chars in range
+    pub fn delete(&mut self, range: Range<usize>) {
+        self.check_range(&range);
+        if range.start == range.end {
+            return;
+        }
+
+        // the chars are inside one chunk, which is not emptied
+        let (mut x, offset) = locate(self.tree.root, range.start);
+        let c = unsafe { &mut x.as_mut().key };
+        if offset + (range.end - range.start) < c.chars {
+            let lo = byte_offset(&c.text, offset);
+            let hi = lo + byte_offset(&c.text[lo..], range.end - range.start);
+            c.text.replace_range(lo..hi, "");
+            *c = Chunk::new(std::mem::take(&mut c.text));
+            fix_up(x);
+            return;
+        }
+
+        let (l, rest) = split(self.take_root(), range.start);
+        let (mid, r) = split(rest, range.end - range.start);
+        drop(from_root(mid.0));
+        self.set_root(join2(l, r).0);
+    }
+
+    /// Splits the rope into [0, at) and [at, len)
+    pub fn split(mut self, at: usize) -> (Self, Self) {
+        assert!(
+            at <= self.len(),
+            "index {} out of bounds, len is {}",
+            at,
+            self.len()
+        );
+        let (l, r) = split(self.take_root(), at);
+        (from_root(l.0), from_root(r.0))
+    }
+
+    /// Concatenates two ropes, the text of left then the text of right
+    pub fn concat(mut left: Self, mut right: Self) -> Self {
+        from_root(join2(left.take_root(), right.take_root()).0)
+    }
+
+    /// Returns the line of the char at index i, the lines are
+    /// numbered from 0
+    pub fn line_of(&self, i: usize) -> usize {
+        assert!(
+            i <= self.len(),
+            "index {} out of bounds, len is {}",
+            i,
+            self.len()
+        );
+        let mut x = NodeQuery::new(self.tree.root);
+        let mut i = i;
+        let mut line = 0;
+        while x.is_some() {
+            let left = summary(x.left().node);
+            let c = chunk(x.node.unwrap());
+            if i < left.chars {
+                x = x.left();
+            } else if i - left.chars < c.chars {
+                let n = c.text.chars().take(i - left.chars);
+                return line + left.newlines + n.filter(|&ch| ch == '\n').count();
+            } else {
+                i -= left.chars + c.chars;
+                line += left.newlines + c.newlines;
+                x = x.right();
+            }
+        }
+        line
+    }
+
+    /// Returns the char index where the line starts
+    pub fn line_start(&self, line: usize) -> usize {
+        assert!(
+            line < self.len_lines(),
+            "line {} out of bounds, {} lines",
+            line,
+            self.len_lines()
+        );
+        if line == 0 {
+            return 0;
+        }
+
+        // find the line-th '\n'
+        let mut x = NodeQuery::new(self.tree.root);
+        let mut k = line;
+        let mut start = 0;
+        loop {
+            let left = summary(x.left().node);
+            let c = chunk(x.node.unwrap());
+            if k <= left.newlines {
+                x = x.left();
+            } else if k - left.newlines <= c.newlines {
+                k -= left.newlines;
+                let (pos, _) = c
+                    .text
+                    .chars()
+                    .enumerate()
+                    .filter(|&(_, ch)| ch == '\n')
+                    .nth(k - 1)
+                    .unwrap();
+                return start + left.chars + pos + 1;
+            } else {
+                k -= left.newlines + c.newlines;
+                start += left.chars + c.chars;
+                x = x.right();
+            }
+        }
+    }
+
+    /// Returns the line, with its '\n' if it has one
+    pub fn line(&self, line: usize) -> String {
+        let start = self.line_start(line);
+        let end = if line + 1 < self.len_lines() {
+            self.line_start(line + 1)
+        } else {
+            self.len()
+        };
+        self.slice(start..end)
+    }
+
+    /// Returns the chunks of the text in order
+    pub fn chunks(&self) -> impl Iterator<Item = &str> {
+        self.tree.iter().map(|(c, _)| c.text.as_str())
+    }
+
+    /// Returns the chars of the text in order
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(str::chars)
+    }
+
+    pub fn height(&self) -> usize {
+        self.tree.height()
+    }
+
+    /// Checks the red-black tree invariants, the parent links and the
+    /// counts of every node
+    pub fn check(&self) -> Result<(), String> {
+        let root = NodeQuery::new(self.tree.root);
+        if root.is_red() {
+            return Err("root is red".to_string());
+        }
+        check(self.tree.root, 0).map(|_| ())
+    }
+
+    /// takes the tree out, with its black height
+    fn take_root(&mut self) -> Part {
+        let root = self.tree.root.take();
+        (root, rb2::black_height(root))
+    }
+
+    fn set_root(&mut self, root: Link) {
+        self.tree.root = root;
+        self.tree.set_size(Node::size(root));
+    }
+
+    fn check_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "range {:?} out of bounds, len is {}",
+            range,
+            self.len()
+        );
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        from_root(build(text).0)
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.chunks()).finish()
+    }
+}
+
+impl Chunk {
+    fn new(text: String) -> Self {
+        let mut chars = 0;
+        let mut newlines = 0;
+        for ch in text.chars() {
+            chars += 1;
+            if ch == '\n' {
+                newlines += 1;
+            }
+        }
+        Self {
+            text,
+            chars,
+            newlines,
+        }
+    }
+}
+
+impl Augment<Chunk, Summary> for Weight {
+    fn update(h: &mut NodeQuery<Chunk, Summary>) {
+        h.update_size();
+        if let Some(mut node) = h.node {
+            let c = chunk(node);
+            let mut s = Summary {
+                bytes: c.text.len(),
+                chars: c.chars,
+                newlines: c.newlines,
+            };
+            for child in [h.left().node, h.right().node].iter() {
+                let t = summary(*child);
+                s.bytes += t.bytes;
+                s.chars += t.chars;
+                s.newlines += t.newlines;
+            }
+            unsafe { node.as_mut().val = Some(s) }
+        }
+    }
+}
+
+fn chunk<'a>(x: NonNull<Node<Chunk, Summary>>) -> &'a Chunk {
+    unsafe { &(*x.as_ptr()).key }
+}
+
+/// counts of the subtree rooted at x
+fn summary(x: Link) -> Summary {
+    unsafe { x.and_then(|x| x.as_ref().val).unwrap_or_default() }
+}
+
+fn from_root(root: Link) -> Rope {
+    let mut rope = Rope::new();
+    rope.set_root(root);
+    rope
+}
+
+/// byte offset of the n-th char of s
+fn byte_offset(s: &str, n: usize) -> usize {
+    s.char_indices().nth(n).map_or(s.len(), |(i, _)| i)
+}
+
+/// the node holding the char at index i and the offset of the char in
+/// the chunk, i == len is the end of the last chunk
+///
+/// the tree must not be empty
+fn locate(root: Link, mut i: usize) -> (NonNull<Node<Chunk, Summary>>, usize) {
+    let mut x = NodeQuery::new(root);
+    loop {
+        let left = summary(x.left().node).chars;
+        let node = x.node.unwrap();
+        if i < left {
+            x = x.left();
+        } else if i - left < chunk(node).chars || x.right().is_none() {
+            return (node, i - left);
+        } else {
+            i -= left + chunk(node).chars;
+            x = x.right();
+        }
+    }
+}
+
+/// the chunk of x changed, update the counts on the path to the root
+fn fix_up(x: NonNull<Node<Chunk, Summary>>) {
+    let mut x = NodeQuery::new(Some(x));
+    while x.is_some() {
+        Weight::update(&mut x);
+        x = x.parent();
+    }
+}
+
+/// builds the tree of text, in chunks of at most MAX_CHUNK bytes
+fn build(text: &str) -> Part {
+    let mut nodes = Vec::with_capacity(text.len() / MAX_CHUNK + 1);
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut at = rest.len().min(MAX_CHUNK);
+        while !rest.is_char_boundary(at) {
+            at -= 1;
+        }
+        let (head, tail) = rest.split_at(at);
+        nodes.push(Node::new_entry(
+            Chunk::new(head.to_string()),
+            Summary::default(),
+        ));
+        rest = tail;
+    }
+
+    // the tallest 2-3 tree with every level full: 2^b - 1 <= n
+    let b = (nodes.len() + 1).ilog2() as usize;
+    (build_tree(&nodes, b), b)
+}
+
+/// builds the 2-3 tree of black height b of the nodes in order,
+/// 2^b - 1 <= n <= 3^b - 1
+fn build_tree(nodes: &[NonNull<Node<Chunk, Summary>>], b: usize) -> Link {
+    if b == 0 {
+        return None;
+    }
+    // max nodes of a subtree of black height b - 1
+    let max = 3usize.saturating_pow(b as u32 - 1) - 1;
+    let n = nodes.len();
+    if n - 1 <= max.saturating_mul(2) {
+        // 2-node: A x C
+        let a = (n - 1) / 2;
+        let l = build_tree(&nodes[..a], b - 1);
+        let r = build_tree(&nodes[a + 1..], b - 1);
+        link(l, nodes[a], r, Color::Black)
+    } else {
+        // 3-node: A y B x C, y is the red left child of x
+        let a = (n - 2) / 3;
+        let c = (n - 2 - a) / 2;
+        let (y, x) = (a, n - 1 - c);
+        let l = build_tree(&nodes[..y], b - 1);
+        let m = build_tree(&nodes[y + 1..x], b - 1);
+        let r = build_tree(&nodes[x + 1..], b - 1);
+        let y = link(l, nodes[y], m, Color::Red);
+        link(y, nodes[x], r, Color::Black)
+    }
+}
+
+/// links l and r to x, then counts x
+fn link(l: Link, x: NonNull<Node<Chunk, Summary>>, r: Link, color: Color) -> Link {
+    let mut x = NodeQuery::new(Some(x));
+    x.set_left(l);
+    x.set_right(r);
+    x.set_color(color);
+    Weight::update(&mut x);
+    x.node
+}
+
+/// splits the tree h into the chars [0, at) and [at, len)
+fn split((h, hb): Part, at: usize) -> (Part, Part) {
+    let h = match h {
+        None => return ((None, 0), (None, 0)),
+        Some(h) => h,
+    };
+    let (l, lb, mut h, r, rb) = rb2::expose(h, hb);
+    let left = summary(l).chars;
+    let chars = chunk(h).chars;
+    if at <= left {
+        let (ll, lr) = split((l, lb), at);
+        (ll, join3(lr, h, (r, rb)))
+    } else if at >= left + chars {
+        let (rl, rr) = split((r, rb), at - left - chars);
+        (join3((l, lb), h, rl), rr)
+    } else {
+        // cut the chunk, h keeps the head
+        let c = unsafe { &mut h.as_mut().key };
+        let tail = c.text.split_off(byte_offset(&c.text, at - left));
+        *c = Chunk::new(std::mem::take(&mut c.text));
+        let tail = Node::new_entry(Chunk::new(tail), Summary::default());
+        (
+            join3((l, lb), h, (None, 0)),
+            join3((None, 0), tail, (r, rb)),
+        )
+    }
+}
+
+fn join3((l, lb): Part, k: NonNull<Node<Chunk, Summary>>, (r, rb): Part) -> Part {
+    rb2::join3::<_, _, Weight>(l, lb, k, r, rb)
+}
+
+fn join2((l, lb): Part, (r, rb): Part) -> Part {
+    rb2::join2::<_, _, Weight>(l, lb, r, rb)
+}
+
+/// appends the chars [lo, hi) of the subtree rooted at x to s
+fn collect(x: Link, lo: usize, hi: usize, s: &mut String) {
+    let x = match x {
+        Some(x) if lo < hi => NodeQuery::new(Some(x)),
+        _ => return,
+    };
+    let left = summary(x.left().node).chars;
+    let c = chunk(x.node.unwrap());
+    if lo < left {
+        collect(x.left().node, lo, hi.min(left), s);
+    }
+    let (a, b) = (lo.max(left), hi.min(left + c.chars));
+    if a < b {
+        s.extend(c.text.chars().skip(a - left).take(b - a));
+    }
+    if hi > left + c.chars {
+        let end = left + c.chars;
+        collect(x.right().node, lo.saturating_sub(end), hi - end, s);
+    }
+}
+
+/// black height of the subtree rooted at x, checks it bottom-up
+fn check(x: Link, depth: usize) -> Result<usize, String> {
+    let h = match x {
+        None => return Ok(0),
+        Some(h) => NodeQuery::new(Some(h)),
+    };
+    let lb = check(h.left().node, depth + 1)?;
+    let rb = check(h.right().node, depth + 1)?;
+
+    let node = h.node.unwrap();
+    let c = chunk(node);
+    let at = |msg: &str| format!("chunk {:?} at depth {}: {}", c.text, depth, msg);
+    if c.text.is_empty() {
+        return Err(at("chunk is empty"));
+    }
+    let fresh = Chunk::new(c.text.clone());
+    if (c.chars, c.newlines) != (fresh.chars, fresh.newlines) {
+        return Err(at("counts of chunk are wrong"));
+    }
+    let mut expected = Summary {
+        bytes: c.text.len(),
+        chars: c.chars,
+        newlines: c.newlines,
+    };
+    for child in [h.left(), h.right()].iter() {
+        if child.is_some() && child.parent().node != h.node {
+            return Err(at("parent link of child is wrong"));
+        }
+        let t = summary(child.node);
+        expected.bytes += t.bytes;
+        expected.chars += t.chars;
+        expected.newlines += t.newlines;
+    }
+    if summary(h.node) != expected {
+        return Err(at("counts of subtree are wrong"));
+    }
+    if h.size() != 1 + Node::size(h.left().node) + Node::size(h.right().node) {
+        return Err(at("size is wrong"));
+    }
+    if h.right().is_red() {
+        return Err(at("red link leans right"));
+    }
+    if h.is_red() && h.left().is_red() {
+        return Err(at("red node has red child"));
+    }
+    if lb != rb {
+        return Err(at(&format!(
+            "black heights differ, left {}, right {}",
+            lb, rb
+        )));
+    }
+    Ok(lb
+        + if h.color() == Some(Color::Black) {
+            1
+        } else {
+            0
+        })
+}
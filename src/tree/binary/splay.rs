@@ -0,0 +1,325 @@
+//! Splay tree
+//!
+//! A splay tree is a self-adjusting BST, every access moves the accessed
+//! node to the root by rotations (splaying), so recently accessed keys are
+//! near the root. There is no balance information in the nodes.
+//!
+//! Splaying node x, with parent p and grandparent g:
+//!   - zig: p is the root, rotate at p
+//!   - zig-zig: x and p are both left (or right) children,
+//!     rotate at g first, then at p
+//!   - zig-zag: x is a left child and p is a right child (or vice versa),
+//!     rotate at p, then at g
+//!
+//! The amortized cost of an operation is O(log N), and the tree is very
+//! fast for skewed access, a sequence of accesses to a small working set
+//! costs about log of the working set size per access.
+//!
+//! Splaying changes the tree, so only the &mut self accessors
+//! (splay_get, splay_min, splay_max, splay_succ, splay_pred, and
+//! get_mut, insert, entry, remove of BSTree) splay. The &self methods
+//! of BSTree only search, like the other BSTs.
+
+use crate::tree::binary::bst::{self, BSTree};
+use crate::tree::binary::entry::Entry;
+use crate::tree::binary::{ordered, rb, Node, NodeQuery, Tree};
+use std::cmp::Ordering;
+use std::ptr::NonNull;
+
+/// The SplayTree represents an ordered symbol table of generic key-value pairs.
+pub struct SplayTree<K, V> {
+    root: Option<NonNull<Node<K, V>>>,
+    size: usize,
+}
+
+impl<K, V> SplayTree<K, V> {
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the key of the root, the most recently accessed key
+    pub fn root(&self) -> Option<&K> {
+        self.root.map(|p| unsafe { &(*p.as_ptr()).key })
+    }
+
+    /// no recursion, the tree may be degenerate
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(NonNull<Node<K, V>>, usize)> =
+            self.root.into_iter().map(|p| (p, 1)).collect();
+        while let Some((p, depth)) = stack.pop() {
+            height = height.max(depth);
+            let p = unsafe { p.as_ref() };
+            stack.extend(p.left.into_iter().map(|c| (c, depth + 1)));
+            stack.extend(p.right.into_iter().map(|c| (c, depth + 1)));
+        }
+        height
+    }
+
+    /// Is the tree a BST with consistent parent links and sizes?
+    pub fn is_valid(&self) -> bool
+    where
+        K: Ord,
+    {
+        fn is_linked<K, V>(x: NodeQuery<K, V>) -> bool {
+            x.is_none()
+                || [x.left(), x.right()]
+                    .iter()
+                    .all(|c| c.is_none() || c.parent().node == x.node)
+                    && is_linked(x.left())
+                    && is_linked(x.right())
+        }
+
+        let root = self.root;
+        NodeQuery::new(root).parent().is_none()
+            && Node::size(root) == self.size
+            && bst::is_bst(root, None, None)
+            && ordered::is_size_consistent(root)
+            && is_linked(NodeQuery::new(root))
+    }
+
+    fn splay(&mut self, x: NonNull<Node<K, V>>) {
+        self.root = splay(self.root, x);
+    }
+
+    /// splays x, returns its key
+    fn splay_key(&mut self, x: Option<NonNull<Node<K, V>>>) -> Option<&K> {
+        let x = x?;
+        self.splay(x);
+        Some(unsafe { &(*x.as_ptr()).key })
+    }
+}
+
+impl<K, V> SplayTree<K, V>
+where
+    K: Ord,
+{
+    /// finds the node of key, splays the last node on the search path
+    fn access(&mut self, key: &K) -> Option<NonNull<Node<K, V>>> {
+        let (found, last) = search(self.root, key);
+        if let Some(x) = found.or(last) {
+            self.splay(x);
+        }
+        found
+    }
+
+    /// inserts the key which is not in the tree, returns the new node
+    fn insert_new(&mut self, key: K, val: V) -> NonNull<Node<K, V>> {
+        let (_, last) = search(self.root, &key);
+        let go_left = last.is_some_and(|p| unsafe { key < p.as_ref().key });
+        let x = Node::new_entry(key, val);
+        let mut parent = NodeQuery::new(last);
+        if parent.is_none() {
+            self.root = Some(x);
+        } else if go_left {
+            parent.set_left(Some(x));
+        } else {
+            parent.set_right(Some(x));
+        }
+        bst::update_size_upward(last);
+        self.size += 1;
+        self.splay(x);
+        x
+    }
+
+    /// Returns the value of key, and splays it to the root
+    /// (the last node on the search path if not found)
+    pub fn splay_get(&mut self, key: &K) -> Option<&V> {
+        self.access(key)
+            .and_then(|p| unsafe { (*p.as_ptr()).val.as_ref() })
+    }
+
+    /// Returns the smallest key, and splays it to the root
+    pub fn splay_min(&mut self) -> Option<&K> {
+        let min = unsafe { bst::find_min(self.root) };
+        self.splay_key(min)
+    }
+
+    /// Returns the largest key, and splays it to the root
+    pub fn splay_max(&mut self) -> Option<&K> {
+        let max = unsafe { bst::find_max(self.root) };
+        self.splay_key(max)
+    }
+
+    /// Returns the successor of key, and splays it to the root
+    pub fn splay_succ(&mut self, key: &K) -> Option<&K> {
+        let succ = unsafe { bst::succ(self.root, key) };
+        self.splay_key(succ)
+    }
+
+    /// Returns the predecessor of key, and splays it to the root
+    pub fn splay_pred(&mut self, key: &K) -> Option<&K> {
+        let pred = unsafe { bst::pred(self.root, key) };
+        self.splay_key(pred)
+    }
+}
+
+impl<K, V> BSTree<K, V> for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V) {
+        match self.access(&key) {
+            Some(mut x) => unsafe { x.as_mut().val = Some(val) },
+            None => {
+                self.insert_new(key, val);
+            }
+        }
+    }
+
+    fn delete(&mut self, key: &K) {
+        let _ = self.remove(key);
+    }
+
+    /// no splaying, see splay_get
+    fn get(&self, key: &K) -> Option<&V> {
+        search(self.root, key)
+            .0
+            .and_then(|p| unsafe { (*p.as_ptr()).val.as_ref() })
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.access(key)
+            .and_then(|p| unsafe { (*p.as_ptr()).val.as_mut() })
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.access(&key) {
+            Some(x) => {
+                let Node { key, val, .. } = unsafe { &mut *x.as_ptr() };
+                Entry::occupied(key, val.as_mut().unwrap())
+            }
+            None => Entry::vacant(key, move |key, val| {
+                let x = self.insert_new(key, val);
+                unsafe { (*x.as_ptr()).val.as_mut().unwrap() }
+            }),
+        }
+    }
+
+    /// splay x to the root, then join the left and right subtrees:
+    /// splay the max of the left subtree to its root, which has no
+    /// right child, and link the right subtree to it
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let mut x = self.access(key)?;
+        let h = NodeQuery::new(Some(x));
+        let (l, r) = (h.left().node, h.right().node);
+        for mut c in [l, r].iter().flatten().copied() {
+            unsafe { c.as_mut().parent = None }
+        }
+
+        let root = match l {
+            None => r,
+            Some(l) => {
+                let max = unsafe { bst::find_max(Some(l)).unwrap() };
+                let mut m = NodeQuery::new(splay(Some(l), max));
+                m.set_right(r);
+                m.update_size();
+                m.node
+            }
+        };
+        self.root = root;
+        self.size -= 1;
+
+        let val = unsafe { x.as_mut().val.take() };
+        Node::release(x);
+        val
+    }
+
+    fn min(&self) -> Option<&K> {
+        unsafe { bst::find_min(self.root).map(|p| &(*p.as_ptr()).key) }
+    }
+
+    fn max(&self) -> Option<&K> {
+        unsafe { bst::find_max(self.root).map(|p| &(*p.as_ptr()).key) }
+    }
+
+    fn succ(&self, key: &K) -> Option<&K> {
+        unsafe { bst::succ(self.root, key).map(|p| &(*p.as_ptr()).key) }
+    }
+
+    fn pred(&self, key: &K) -> Option<&K> {
+        unsafe { bst::pred(self.root, key).map(|p| &(*p.as_ptr()).key) }
+    }
+}
+
+impl<K, V> Default for SplayTree<K, V> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            size: 0,
+        }
+    }
+}
+
+impl<K, V> Drop for SplayTree<K, V> {
+    fn drop(&mut self) {
+        let mut tree = Tree::default();
+        tree.root = self.root.take();
+        drop(tree);
+    }
+}
+
+/// returns (node of key, last node on the search path)
+#[allow(clippy::type_complexity)]
+fn search<K, V>(
+    root: Option<NonNull<Node<K, V>>>,
+    key: &K,
+) -> (Option<NonNull<Node<K, V>>>, Option<NonNull<Node<K, V>>>)
+where
+    K: Ord,
+{
+    let mut x = NodeQuery::new(root);
+    let mut last = None;
+    while let Some(k) = x.get_key() {
+        last = x.node;
+        x = match key.cmp(k) {
+            Ordering::Less => x.left(),
+            Ordering::Greater => x.right(),
+            Ordering::Equal => return (last, last),
+        };
+    }
+    (None, last)
+}
+
+/// moves x to the root of the tree, returns the new root
+fn splay<K, V>(
+    mut root: Option<NonNull<Node<K, V>>>,
+    x: NonNull<Node<K, V>>,
+) -> Option<NonNull<Node<K, V>>> {
+    let x = NodeQuery::new(Some(x));
+    loop {
+        let p = x.parent();
+        let g = p.parent();
+        root = match (p.node, g.node) {
+            (None, _) => break,
+            // zig
+            (Some(p), None) if x.i_am_left() => rb::rotate_right(root, p),
+            (Some(p), None) => rb::rotate_left(root, p),
+            // zig-zig
+            (Some(p_node), Some(g)) if x.i_am_left() && p.i_am_left() => {
+                let root = rb::rotate_right(root, g);
+                rb::rotate_right(root, p_node)
+            }
+            (Some(p_node), Some(g)) if x.i_am_right() && p.i_am_right() => {
+                let root = rb::rotate_left(root, g);
+                rb::rotate_left(root, p_node)
+            }
+            // zig-zag
+            (Some(p_node), Some(g)) if x.i_am_left() => {
+                let root = rb::rotate_right(root, p_node);
+                rb::rotate_left(root, g)
+            }
+            (Some(p_node), Some(g)) => {
+                let root = rb::rotate_left(root, p_node);
+                rb::rotate_right(root, g)
+            }
+        };
+    }
+    root
+}
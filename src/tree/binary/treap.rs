@@ -0,0 +1,190 @@
+//! Treap (tree + heap)
+//!
+//! A treap is a binary search tree in which every node also has a random
+//! priority, and the priorities are heap-ordered: the priority of a node
+//! is not less than the priorities of its children.
+//!
+//! The shape of a treap is the same as a BST built by inserting the keys
+//! in decreasing order of priority, so the expected height is ~ 2 ln N
+//! whatever the order of insertions, like a BST built from random keys.
+//!
+//! Insert: insert the node as a leaf like BST, then rotate it up
+//! while its priority is greater than its parent's.
+//!
+//! Delete: rotate the node down to the child with greater priority
+//! until it is a leaf, then cut it off.
+
+use crate::tree::binary::bst::{self, BSTree};
+use crate::tree::binary::entry::Entry;
+use crate::tree::binary::iter::Iter;
+use crate::tree::binary::{ordered, rb, Node, NodeQuery, Tree};
+use std::ptr::NonNull;
+
+/// The Treap represents an ordered symbol table of generic key-value pairs.
+pub struct Treap<K, V> {
+    tree: Tree<K, V>,
+}
+
+impl<K, V> Treap<K, V> {
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Is this symbol table empty
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn height(&self) -> usize {
+        self.tree.height()
+    }
+
+    /// Gets an iterator over the entries, sorted by key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.tree.iter()
+    }
+
+    /// Is the tree both a BST and a heap by priority?
+    pub fn is_valid(&self) -> bool
+    where
+        K: Ord,
+    {
+        fn is_heap<K, V>(x: NodeQuery<K, V>) -> bool {
+            x.is_none()
+                || [x.left(), x.right()].iter().all(|c| {
+                    c.is_none()
+                        || (c.parent().node == x.node && priority(c.node) <= priority(x.node))
+                }) && is_heap(x.left())
+                    && is_heap(x.right())
+        }
+
+        let root = NodeQuery::new(self.tree.root);
+        root.parent().is_none()
+            && bst::is_bst(self.tree.root, None, None)
+            && ordered::is_size_consistent(self.tree.root)
+            && is_heap(root)
+    }
+}
+
+impl<K, V> BSTree<K, V> for Treap<K, V>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, val: V) {
+        match unsafe { bst::find(self.tree.root, &key) } {
+            Some(mut x) => unsafe { x.as_mut().val = Some(val) },
+            None => {
+                insert_new(&mut self.tree, key, val);
+            }
+        }
+    }
+
+    fn delete(&mut self, key: &K) {
+        let _ = self.remove(key);
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        unsafe { bst::find(self.tree.root, key).and_then(|p| p.as_ref().val.as_ref()) }
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { bst::find(self.tree.root, key).and_then(|mut p| p.as_mut().val.as_mut()) }
+    }
+
+    fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(&mut self.tree, key, insert_new)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let mut x = unsafe { bst::find(self.tree.root, key) }?;
+        let mut root = self.tree.root;
+
+        // rotate x down until it is a leaf
+        loop {
+            let h = NodeQuery::new(Some(x));
+            root = match (h.left().node, h.right().node) {
+                (None, None) => break,
+                (Some(_), None) => rb::rotate_right(root, x),
+                (None, Some(_)) => rb::rotate_left(root, x),
+                (l, r) if priority(l) > priority(r) => rb::rotate_right(root, x),
+                _ => rb::rotate_left(root, x),
+            };
+        }
+
+        let h = NodeQuery::new(Some(x));
+        let mut parent = h.parent();
+        if parent.is_none() {
+            root = None;
+        } else if h.i_am_left() {
+            parent.set_left(None);
+        } else {
+            parent.set_right(None);
+        }
+        bst::update_size_upward(parent.node);
+
+        self.tree.root = root;
+        self.tree.set_size(self.tree.size() - 1);
+        let val = unsafe { x.as_mut().val.take() };
+        Node::release(x);
+        val
+    }
+
+    fn min(&self) -> Option<&K> {
+        unsafe { bst::find_min(self.tree.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn max(&self) -> Option<&K> {
+        unsafe { bst::find_max(self.tree.root).map(|p| &p.as_ref().key) }
+    }
+
+    fn succ(&self, key: &K) -> Option<&K> {
+        unsafe { bst::succ(self.tree.root, key).map(|p| &p.as_ref().key) }
+    }
+
+    fn pred(&self, key: &K) -> Option<&K> {
+        unsafe { bst::pred(self.tree.root, key).map(|p| &p.as_ref().key) }
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self {
+            tree: Tree::default(),
+        }
+    }
+}
+
+/// inserts the key which is not in the tree, returns the new node
+fn insert_new<K, V>(tree: &mut Tree<K, V>, key: K, val: V) -> NonNull<Node<K, V>>
+where
+    K: Ord,
+{
+    let mut x = unsafe { bst::insert(tree.root, key, val) }.expect("key exists");
+    unsafe { x.as_mut().priority = rand::random() };
+
+    let mut root = tree.root.or(Some(x));
+    // rotate x up until the heap order is restored
+    loop {
+        let h = NodeQuery::new(Some(x));
+        let parent = h.parent();
+        match parent.node {
+            Some(p) if priority(Some(p)) < priority(Some(x)) => {
+                root = if h.i_am_left() {
+                    rb::rotate_right(root, p)
+                } else {
+                    rb::rotate_left(root, p)
+                };
+            }
+            _ => break,
+        }
+    }
+
+    tree.root = root;
+    tree.set_size(tree.size() + 1);
+    x
+}
+
+fn priority<K, V>(x: Option<NonNull<Node<K, V>>>) -> u32 {
+    x.map_or(0, |x| unsafe { x.as_ref().priority })
+}
@@ -1,4 +1,6 @@
+use crate::tree::binary::iter::{Iter, IterMut};
 use crate::tree::binary::node::Node;
+use std::ops::RangeBounds;
 use std::ptr::NonNull;
 
 pub struct Tree<K, V> {
@@ -22,6 +24,55 @@ impl<K, V> Tree<K, V> {
     pub fn is_empty(&self) -> bool {
         self.root.is_none()
     }
+
+    /// Gets an iterator over the entries of the tree, sorted by key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root)
+    }
+
+    /// Gets a mutable iterator over the entries of the tree, sorted by key
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.root)
+    }
+}
+
+impl<K, V> Tree<K, V>
+where
+    K: Ord,
+{
+    /// Gets an iterator over a sub-range of entries in the tree
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Iter::range(self.root, range)
+    }
+
+    /// Gets a mutable iterator over a sub-range of entries in the tree
+    pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        IterMut::range(self.root, range)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Tree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Tree<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
 }
 
 impl<K, V> Default for Tree<K, V> {
@@ -34,15 +85,15 @@ impl<K, V> Default for Tree<K, V> {
 }
 
 impl<K, V> Drop for Tree<K, V> {
+    // no recursion, a degenerate tree (e.g. BST built by sorted keys)
+    // would overflow the stack
     fn drop(&mut self) {
-        fn visitor<K, V>(p: Option<NonNull<Node<K, V>>>) {
-            if let Some(p) = p {
-                let p = unsafe { Box::from_raw(p.as_ptr()) };
-                visitor(p.left);
-                visitor(p.right);
-            }
+        let mut stack: Vec<NonNull<Node<K, V>>> = self.root.take().into_iter().collect();
+        while let Some(p) = stack.pop() {
+            let p = unsafe { Box::from_raw(p.as_ptr()) };
+            stack.extend(p.left);
+            stack.extend(p.right);
         }
-        visitor(self.root);
     }
 }
 
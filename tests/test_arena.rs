@@ -0,0 +1,206 @@
+use algo::common::drop::{self, Elem};
+use algo::common::Arena;
+use algo::strings::{ArenaTST, ArenaTrieST, TrieST, TST};
+use algo::tree::binary::arena_tree::ArenaTree;
+use algo::tree::binary::bst::BSTree;
+use algo::tree::binary::{Node, NodeQuery, Tree};
+
+#[test]
+fn arena() {
+    let mut arena = Arena::default();
+    let a = arena.alloc("a");
+    let b = arena.alloc("b");
+    assert_eq!(2, arena.len());
+    assert_eq!("a", arena[a]);
+    arena[b] = "B";
+    assert_eq!(Some(&"B"), arena.get(b));
+
+    assert_eq!("a", arena.free(a));
+    assert_eq!(None, arena.get(a));
+    assert_eq!(1, arena.len());
+
+    // the free slot is reused
+    let c = arena.alloc("c");
+    assert_eq!(a, c);
+    assert_eq!(2, arena.capacity());
+    assert_eq!("c", arena[c]);
+
+    arena.clear();
+    assert!(arena.is_empty());
+    assert_eq!(None, arena.get(b));
+}
+
+#[test]
+#[should_panic]
+fn arena_double_free() {
+    let mut arena = Arena::default();
+    let a = arena.alloc(1);
+    arena.free(a);
+    arena.free(a);
+}
+
+#[test]
+fn arena_drop() {
+    drop::with(|ctx| {
+        let mut arena = Arena::default();
+        let handles: Vec<_> = (0..10).map(|_| arena.alloc(Elem)).collect();
+        drop(arena.free(handles[3]));
+        assert_eq!(1, ctx.get());
+        drop(arena);
+        assert_eq!(10, ctx.get());
+    });
+}
+
+#[test]
+fn arena_tree() {
+    let mut tree = ArenaTree::with_capacity(1000);
+    // sorted insertions
+    for v in 0..1000 {
+        tree.insert(v, v);
+        assert_eq!(Ok(()), tree.check());
+    }
+    assert_eq!(1000, tree.len());
+    assert!(tree.height() <= 2 * 10);
+
+    for v in (0..1000).step_by(2) {
+        tree.delete(&v);
+        assert_eq!(Ok(()), tree.check());
+    }
+    assert_eq!(500, tree.len());
+    let keys: Vec<i32> = tree.iter().map(|(k, _)| *k).collect();
+    assert_eq!((1..1000).step_by(2).collect::<Vec<i32>>(), keys);
+    assert_eq!((500, Some(500)), tree.iter().size_hint());
+
+    // the freed slots are reused
+    for v in (0..1000).step_by(2) {
+        tree.insert(v, v);
+    }
+    assert_eq!(Ok(()), tree.check());
+    assert_eq!(1000, tree.len());
+
+    tree.clear();
+    assert!(tree.is_empty());
+    assert_eq!(None, tree.min());
+}
+
+#[test]
+fn drop_degenerate_tree() {
+    // a linked list of 1M nodes, dropped without recursion
+    let n = 1_000_000;
+    let mut tree = Tree::default();
+    let mut last: Option<std::ptr::NonNull<Node<i32, i32>>> = None;
+    for v in 0..n {
+        let x = Node::new_entry(v, v);
+        match last {
+            None => tree.root = Some(x),
+            Some(p) => NodeQuery::new(Some(p)).set_right(Some(x)),
+        }
+        last = Some(x);
+    }
+    tree.set_size(n as usize);
+    drop(tree);
+}
+
+#[test]
+fn tries_long_key() {
+    let key = "a".repeat(1000);
+    let mut trie = ArenaTrieST::default();
+    trie.put(&key, Some(1));
+    trie.put("ab", Some(2));
+    assert_eq!(Some(&1), trie.get(&key));
+    trie.delete(&key);
+    assert_eq!(1, trie.len());
+    assert_eq!(Some(&2), trie.get("ab"));
+    drop(trie);
+
+    let mut tst = ArenaTST::default();
+    tst.put(&key, Some(1));
+    tst.put("ab", Some(2));
+    assert_eq!(Some(&1), tst.get(&key));
+    tst.put(&key, None);
+    assert_eq!(1, tst.len());
+    assert_eq!(Some(&2), tst.get("ab"));
+    drop(tst);
+}
+
+const WORDS: [&str; 12] = [
+    "she", "sells", "sea", "shells", "by", "the", "sea", "shore", "s", "shell", "sh", "t",
+];
+
+#[test]
+fn arena_tries_agree() {
+    // the arena-backed tries answer the same as the Box-based ones
+    let mut trie = TrieST::default();
+    let mut arena_trie = ArenaTrieST::default();
+    let mut tst = TST::default();
+    let mut arena_tst = ArenaTST::default();
+    for (i, &w) in WORDS.iter().enumerate() {
+        trie.put(w, Some(i));
+        arena_trie.put(w, Some(i));
+        tst.put(w, Some(i));
+        arena_tst.put(w, Some(i));
+    }
+    for &w in ["shells", "t", "sh", "nothing"].iter() {
+        trie.delete(w);
+        arena_trie.delete(w);
+        tst.put(w, None);
+        arena_tst.put(w, None);
+    }
+
+    assert_eq!(trie.len(), arena_trie.len());
+    assert_eq!(tst.len(), arena_tst.len());
+    let keys: Vec<String> = trie.keys().iter().cloned().collect();
+    assert_eq!(keys, arena_trie.keys().iter().cloned().collect::<Vec<_>>());
+    let keys: Vec<String> = tst.keys().iter().cloned().collect();
+    assert_eq!(keys, arena_tst.keys().iter().cloned().collect::<Vec<_>>());
+    for &w in WORDS.iter().chain(["shellsort", "se", ""].iter()) {
+        assert_eq!(trie.get(w), arena_trie.get(w));
+        assert_eq!(tst.get(w), arena_tst.get(w));
+        assert_eq!(trie.longest_prefix_of(w), arena_trie.longest_prefix_of(w));
+        assert_eq!(tst.longest_prefix_of(w), arena_tst.longest_prefix_of(w));
+    }
+    for &prefix in ["s", "sh", "x"].iter() {
+        let expect: Vec<String> = trie.keys_with_prefix(prefix).iter().cloned().collect();
+        let found: Vec<String> = arena_trie
+            .keys_with_prefix(prefix)
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(expect, found);
+        let expect: Vec<String> = tst.keys_with_prefix(prefix).iter().cloned().collect();
+        let found: Vec<String> = arena_tst.keys_with_prefix(prefix).iter().cloned().collect();
+        assert_eq!(expect, found);
+    }
+    let expect: Vec<String> = trie.keys_that_match("s.e").iter().cloned().collect();
+    let found: Vec<String> = arena_trie.keys_that_match("s.e").iter().cloned().collect();
+    assert_eq!(expect, found);
+    let expect: Vec<String> = tst.keys_that_match("s.e").iter().cloned().collect();
+    let found: Vec<String> = arena_tst.keys_that_match("s.e").iter().cloned().collect();
+    assert_eq!(expect, found);
+}
+
+#[test]
+fn arena_tries_drop() {
+    let mut trie = ArenaTrieST::default();
+    drop::with(|ctx| {
+        for &w in ["aaa", "bbb", "ccc", "ddd"].iter() {
+            trie.put(w, Some(Elem));
+        }
+        trie.delete("aaa");
+        trie.put("bbb", Some(Elem));
+        assert_eq!(2, ctx.get());
+        drop(trie);
+        assert_eq!(5, ctx.get());
+    });
+
+    let mut tst = ArenaTST::default();
+    drop::with(|ctx| {
+        for &w in ["aaa", "bbb", "ccc", "ddd"].iter() {
+            tst.put(w, Some(Elem));
+        }
+        tst.put("aaa", None);
+        assert_eq!(1, ctx.get());
+        drop(tst);
+        assert_eq!(4, ctx.get());
+    });
+}
@@ -0,0 +1,148 @@
+use algo::common::util;
+use algo::tree::binary::avl::AvlTree;
+use algo::tree::binary::traverse::{InOrderVisitor, PreOrderVisitor};
+use algo::tree::binary::Tree;
+
+#[test]
+fn height() {
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        tree.insert(v, v);
+    }
+    // 即使输入升序数列，avl tree仍然是平衡的
+    // 含有n个节点的avl tree，其高度不会超过 1.44 * lg(n + 2)
+    assert!(tree.height() as f32 <= 1.44 * 102.0f32.log2());
+    assert_eq!(100, tree.size());
+    assert!(tree.is_balanced());
+}
+
+#[test]
+fn insert() {
+    /*
+    [100, 150, 50, 125, 114, 107]
+
+              114
+            /     \
+         100       125
+        /   \         \
+      50    107       150
+    */
+    let mut tree = Tree::default();
+    for v in [100, 150, 50, 125, 114, 107] {
+        tree.insert(v, v);
+        assert!(tree.is_balanced());
+    }
+
+    let r = unsafe { PreOrderVisitor::iterate(&tree) };
+    assert_eq!(r, vec![114, 100, 50, 107, 125, 150]);
+    let r = unsafe { InOrderVisitor::iterate(&tree) };
+    assert_eq!(r, vec![50, 100, 107, 114, 125, 150]);
+}
+
+#[test]
+fn repeat_insert() {
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        tree.insert(v, v);
+    }
+    tree.insert(0, 100);
+    assert_eq!(10, tree.size());
+    assert_eq!(Some(&100), tree.get(&0));
+}
+
+#[test]
+fn get() {
+    let mut tree = Tree::default();
+    let data = vec![4, 3, 8, 1, 7, 16, 2, 10, 9, 14];
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+    for v in data {
+        assert_eq!(Some(&v), tree.get(&v));
+    }
+    assert_eq!(None, tree.get(&100));
+}
+
+#[test]
+fn min_max() {
+    let mut tree = Tree::default();
+    assert_eq!(None, tree.min());
+    assert_eq!(None, tree.max());
+
+    for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+        tree.insert(v, v);
+    }
+    assert_eq!(Some(&1), tree.min());
+    assert_eq!(Some(&16), tree.max());
+}
+
+#[test]
+fn succ_pred() {
+    let mut tree = Tree::default();
+    for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+        tree.insert(v, v);
+    }
+
+    assert_eq!(Some(&9), tree.succ(&8));
+    assert_eq!(Some(&3), tree.succ(&2));
+    assert_eq!(None, tree.succ(&16));
+
+    assert_eq!(Some(&8), tree.pred(&9));
+    assert_eq!(Some(&2), tree.pred(&3));
+    assert_eq!(None, tree.pred(&1));
+}
+
+#[test]
+fn delete() {
+    let mut tree = Tree::default();
+    let mut data: Vec<i32> = (0..1000).collect();
+    util::shuffle(&mut data);
+    for v in &data {
+        tree.insert(*v, *v);
+    }
+
+    util::shuffle(&mut data);
+    for (i, v) in data.iter().enumerate() {
+        tree.delete(v);
+        assert_eq!(None, tree.get(v));
+        assert_eq!(data.len() - i - 1, tree.size());
+        assert!(tree.is_balanced());
+    }
+    assert!(tree.is_empty());
+
+    // 删除一个不存在的
+    tree.delete(&10000);
+    assert_eq!(0, tree.size());
+}
+
+#[test]
+fn drop_clear() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut tree = Tree::default();
+        for v in 0..100 {
+            tree.insert(v, Elem);
+        }
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
+
+#[test]
+fn drop_with_delete() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut tree = Tree::default();
+        for v in 0..100 {
+            tree.insert(v, Elem);
+        }
+
+        for v in 0..10 {
+            tree.delete(&v);
+        }
+        assert_eq!(10, ctx.get());
+
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
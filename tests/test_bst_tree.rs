@@ -152,3 +152,68 @@ fn drop_with_delete() {
         assert_eq!(100, ctx.get());
     });
 }
+
+#[test]
+fn get_mut_entry_remove() {
+    let mut tree = Tree::default();
+    for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+        tree.insert(v, v);
+    }
+    *tree.get_mut(&7).unwrap() += 100;
+    assert_eq!(Some(&107), tree.get(&7));
+    assert_eq!(None, tree.get_mut(&100));
+
+    // count
+    let mut counter = Tree::default();
+    for c in "SEARCHEXAMPLE".chars() {
+        *counter.entry(c).or_insert(0) += 1;
+    }
+    assert_eq!(Some(&3), counter.get(&'E'));
+    assert_eq!(Some(&2), counter.get(&'A'));
+    assert_eq!(Some(&1), counter.get(&'X'));
+
+    tree.entry(8).and_modify(|v| *v = 0).or_insert(1);
+    tree.entry(5).and_modify(|v| *v = 0).or_insert(1);
+    assert_eq!(Some(&0), tree.get(&8));
+    assert_eq!(Some(&1), tree.get(&5));
+    assert_eq!(&5, tree.entry(5).key());
+    assert!(bst::is_bst(tree.root, None, None));
+
+    // 8 has two children
+    assert_eq!(Some(0), tree.remove(&8));
+    assert_eq!(None, tree.remove(&8));
+    assert_eq!(None, tree.get(&8));
+    assert!(bst::is_bst(tree.root, None, None));
+}
+
+#[test]
+fn remove_not_copy() {
+    let mut tree = Tree::default();
+    let data = [4, 3, 8, 1, 7, 16, 2, 10, 9, 14];
+    for v in data {
+        tree.insert(v.to_string(), v.to_string());
+    }
+    for v in data {
+        assert_eq!(Some(v.to_string()), tree.remove(&v.to_string()));
+    }
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn drop_with_remove() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut tree = Tree::default();
+        for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+            tree.insert(v, Elem);
+        }
+        let elem = tree.remove(&8);
+        assert!(elem.is_some());
+        assert_eq!(0, ctx.get());
+        drop(elem);
+        assert_eq!(1, ctx.get());
+
+        drop(tree);
+        assert_eq!(10, ctx.get());
+    });
+}
@@ -0,0 +1,113 @@
+use algo::common::util;
+use algo::tree::binary::btree::BTree;
+
+#[test]
+fn insert_get() {
+    let mut tree = BTree::default();
+    for (i, c) in "SEARCHEXAMPLE".chars().enumerate() {
+        tree.insert(c, i);
+        assert!(tree.is_valid());
+    }
+    assert_eq!(10, tree.len());
+    assert_eq!(Some(&12), tree.get(&'E'));
+    assert_eq!(Some(&8), tree.get(&'A'));
+    assert_eq!(None, tree.get(&'Z'));
+    assert_eq!(Some(&'A'), tree.min());
+    assert_eq!(Some(&'X'), tree.max());
+
+    let keys: String = tree.iter().map(|(k, _)| *k).collect();
+    assert_eq!("ACEHLMPRSX", keys);
+}
+
+#[test]
+fn height() {
+    // 2-3-4 tree
+    let mut tree = BTree::default();
+    assert_eq!(0, tree.height());
+    for v in 0..3 {
+        tree.insert(v, v);
+    }
+    assert_eq!(0, tree.height());
+    tree.insert(3, 3);
+    assert_eq!(1, tree.height());
+
+    // height of a B-tree of order M is at most log(M/2, (N + 1) / 2)
+    for m in [4, 6, 16, 64] {
+        let mut tree = BTree::new(m);
+        for v in 0..10000 {
+            tree.insert(v, v);
+        }
+        assert!(tree.is_valid());
+        let bound = (10001.0f64 / 2.0).log((m / 2) as f64);
+        assert!(tree.height() as f64 <= bound, "m = {}", m);
+    }
+}
+
+#[test]
+fn delete() {
+    for m in [4, 6, 8, 32] {
+        let mut tree = BTree::new(m);
+        let mut data: Vec<i32> = (0..1000).collect();
+        util::shuffle(&mut data);
+        for v in &data {
+            tree.insert(*v, *v);
+        }
+
+        util::shuffle(&mut data);
+        for (i, v) in data.iter().enumerate() {
+            tree.delete(v);
+            assert!(!tree.contains(v));
+            assert_eq!(data.len() - i - 1, tree.len());
+            assert!(tree.is_valid());
+        }
+        assert!(tree.is_empty());
+        assert_eq!(0, tree.height());
+
+        // delete not exist
+        tree.delete(&10000);
+        assert!(tree.is_empty());
+    }
+}
+
+#[test]
+fn range() {
+    let mut tree = BTree::new(6);
+    for v in (0..1000).step_by(2) {
+        tree.insert(v, v * 10);
+    }
+
+    let keys = |it: algo::tree::binary::btree::Range<i32, i32>| -> Vec<i32> {
+        it.map(|(k, _)| *k).collect()
+    };
+    assert_eq!(vec![10, 12, 14, 16, 18], keys(tree.range(10..20)));
+    assert_eq!(vec![10, 12, 14, 16, 18, 20], keys(tree.range(10..=20)));
+    assert_eq!(vec![12, 14, 16, 18], keys(tree.range(11..19)));
+    assert_eq!(vec![0, 2, 4], keys(tree.range(..5)));
+    assert_eq!(vec![996, 998], keys(tree.range(995..)));
+    assert_eq!(500, keys(tree.range(..)).len());
+    assert!(keys(tree.range(2000..3000)).is_empty());
+    assert!(keys(tree.range(11..12)).is_empty());
+    let (lo, hi) = (20, 10);
+    assert!(keys(tree.range(lo..hi)).is_empty());
+
+    let vals: Vec<i32> = tree.range(100..106).map(|(_, v)| *v).collect();
+    assert_eq!(vec![1000, 1020, 1040], vals);
+}
+
+#[test]
+fn drop_clear() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut tree = BTree::default();
+        for v in 0..100 {
+            tree.insert(v, Elem);
+        }
+        for v in 0..10 {
+            tree.delete(&v);
+        }
+        assert_eq!(10, ctx.get());
+
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
@@ -1,6 +1,8 @@
 use algo::common::{
-    IndexPQ, Queue, QuickFindUF, QuickUnionUF, Stack, TopM, WeightedQuickUnionUF, PQ, UF,
+    ConcurrentUF, Contradiction, IndexPQ, IndexPQError, KWayMerge, MergeOrder, PotentialUF, Queue,
+    QuickFindUF, QuickUnionUF, RollbackUF, Stack, TopM, UnionFind, WeightedQuickUnionUF, PQ, UF,
 };
+use rand::{Rng, SeedableRng};
 use std::str::FromStr;
 
 const TOBE: &'static str = include_str!("../res/common/tobe.txt");
@@ -87,6 +89,59 @@ fn max_pq() {
     assert_eq!(max_pq.dequeue(), None);
 }
 
+#[test]
+fn pq_from_vec() {
+    let t = vec![4, 1, 3, 2, 16, 9, 10, 14, 8, 7];
+    for &d in [2, 3, 4, 16].iter() {
+        let pq = PQ::from_vec(t.clone()).with_arity(d);
+        assert_eq!(vec![1, 2, 3, 4, 7, 8, 9, 10, 14, 16], pq.into_sorted_vec());
+
+        let mut pq = PQ::from_vec_max(t.clone()).with_arity(d);
+        pq.enqueue(5);
+        pq.enqueue(20);
+        assert_eq!(Some(&20), pq.peek());
+        assert_eq!(
+            vec![20, 16, 14, 10, 9, 8, 7, 5, 4, 3, 2, 1],
+            pq.into_sorted_vec()
+        );
+    }
+    assert!(PQ::<i32>::from_vec(vec![]).into_sorted_vec().is_empty());
+
+    // keys without Default
+    struct Job(u32);
+    impl PartialEq for Job {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl PartialOrd for Job {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            self.0.partial_cmp(&other.0)
+        }
+    }
+    let mut pq = PQ::new_min_pq(2).with_arity(4);
+    pq.enqueue(Job(2));
+    pq.enqueue(Job(1));
+    assert_eq!(Some(1), pq.dequeue().map(|job| job.0));
+}
+
+#[test]
+fn pq_peek_mut() {
+    let mut pq = PQ::from_vec_max(vec![5, 9, 3, 7]);
+    *pq.peek_mut().unwrap() = 1;
+    assert_eq!(Some(&7), pq.peek());
+    {
+        let mut top = pq.peek_mut().unwrap();
+        *top += 10;
+    }
+    assert_eq!(Some(&17), pq.peek());
+    assert_eq!(17, algo::common::PeekMut::pop(pq.peek_mut().unwrap()));
+    assert_eq!(vec![5, 3, 1], pq.into_sorted_vec());
+
+    let mut pq: PQ<i32> = PQ::new_min_pq(0);
+    assert!(pq.peek_mut().is_none());
+}
+
 #[test]
 fn index_min_pq() {
     fn queue(i: &str) -> Queue<&str> {
@@ -155,6 +210,169 @@ fn index_max_pq() {
     assert_eq!("B D H P Q Q A B E F J N A B C F G I I Z", vec.join(" "));
 }
 
+#[test]
+fn index_pq_api() {
+    let keys = [5, 3, 8, 1, 9, 2];
+    let mut pq = IndexPQ::new_min_pq(keys.len());
+    assert_eq!(None, pq.peek_key());
+    for (i, &k) in keys.iter().enumerate() {
+        pq.enqueue(i, k).unwrap();
+    }
+    assert_eq!(Err(IndexPQError::AlreadyExists(1)), pq.enqueue(1, 0));
+    assert_eq!(Err(IndexPQError::OutOfBounds(6)), pq.enqueue(6, 0));
+    assert_eq!(Some(&8), pq.key_of(2));
+
+    // priority order, and pq is unchanged
+    let order: Vec<(usize, i32)> = pq.iter().map(|(i, &k)| (i, k)).collect();
+    assert_eq!(vec![(3, 1), (5, 2), (1, 3), (0, 5), (2, 8), (4, 9)], order);
+    assert_eq!(6, pq.len());
+
+    pq.decrease_key(4, 0).unwrap();
+    assert_eq!(Some(&4), pq.peek_index());
+    assert_eq!(Err(IndexPQError::NotDecreased(4)), pq.decrease_key(4, 0));
+    pq.increase_key(4, 10).unwrap();
+    assert_eq!(Err(IndexPQError::NotIncreased(4)), pq.increase_key(4, 10));
+    assert_eq!(Some(&3), pq.peek_index());
+    pq.change_key(0, -1).unwrap();
+    pq.change_key(3, 7).unwrap();
+
+    assert_eq!(Ok(8), pq.delete(2));
+    assert_eq!(Err(IndexPQError::NotFound(2)), pq.delete(2));
+    assert_eq!(Err(IndexPQError::NotFound(2)), pq.change_key(2, 1));
+    assert!(!pq.contains(2));
+    assert_eq!(None, pq.key_of(2));
+
+    let order: Vec<usize> = pq.iter().map(|(i, _)| i).collect();
+    assert_eq!(vec![0, 5, 1, 3, 4], order);
+    let mut dequeued = Vec::new();
+    while let Some(i) = pq.dequeue() {
+        dequeued.push(i);
+    }
+    assert_eq!(order, dequeued);
+    assert_eq!(None, pq.iter().next());
+
+    assert_eq!(
+        "index 2 is not in the priority queue",
+        IndexPQError::NotFound(2).to_string()
+    );
+}
+
+#[test]
+fn index_pq_iter_random() {
+    // the iterator agrees with dequeue for min and max pq, after
+    // random changes and deletes
+    let n = 200;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    for &max in [false, true].iter() {
+        let mut pq = if max {
+            IndexPQ::new_max_pq(n)
+        } else {
+            IndexPQ::new_min_pq(n)
+        };
+        for i in 0..n {
+            pq.enqueue(i, rng.gen_range(0..1000u32)).unwrap();
+        }
+        for _ in 0..100 {
+            let i = rng.gen_range(0..n);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let _ = pq.delete(i);
+                }
+                _ => {
+                    let _ = pq.change_key(i, rng.gen_range(0..1000));
+                }
+            }
+        }
+        let keys: Vec<u32> = pq.iter().map(|(_, &k)| k).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        if max {
+            sorted.reverse();
+        }
+        assert_eq!(sorted, keys);
+        let mut dequeued = Vec::new();
+        while let Some((_, k)) = pq.dequeue_entry() {
+            dequeued.push(k);
+        }
+        assert_eq!(keys, dequeued);
+    }
+}
+
+#[test]
+fn kway_merge() {
+    let inputs = ["A B C F G I I Z", "B D H P Q Q", "A B E F J N"];
+    let expect = "A A B B B C D E F F G H I I J N P Q Q Z";
+    let streams = || inputs.iter().map(|s| s.split_whitespace());
+    let merged: Vec<&str> = KWayMerge::tournament(streams(), MergeOrder::Ascending).collect();
+    assert_eq!(expect, merged.join(" "));
+    let merged: Vec<&str> = KWayMerge::index_pq(streams(), MergeOrder::Ascending).collect();
+    assert_eq!(expect, merged.join(" "));
+
+    let expect = "Z Q Q P N J I I H G F F E D C B B B A A";
+    let streams = || inputs.iter().map(|s| s.split_whitespace().rev());
+    let merged: Vec<&str> = KWayMerge::tournament(streams(), MergeOrder::Descending).collect();
+    assert_eq!(expect, merged.join(" "));
+    let merged: Vec<&str> = KWayMerge::index_pq(streams(), MergeOrder::Descending).collect();
+    assert_eq!(expect, merged.join(" "));
+}
+
+#[test]
+fn kway_merge_lazy() {
+    // infinite streams: multiples of 2, 3 and 5
+    let streams = [2, 3, 5].iter().map(|&k| (1..).map(move |i| i * k));
+    let merged: Vec<i32> = KWayMerge::tournament(streams, MergeOrder::Ascending)
+        .take(10)
+        .collect();
+    assert_eq!(vec![2, 3, 4, 5, 6, 6, 8, 9, 10, 10], merged);
+
+    // not Copy items, empty streams, and no stream at all
+    let streams = vec![
+        vec![],
+        vec!["b".to_string(), "d".to_string()],
+        vec![],
+        vec!["a".to_string(), "c".to_string(), "e".to_string()],
+    ];
+    let merge = KWayMerge::index_pq(streams.clone(), MergeOrder::Ascending);
+    assert_eq!((5, Some(5)), merge.size_hint());
+    assert_eq!(vec!["a", "b", "c", "d", "e"], merge.collect::<Vec<_>>());
+    let merge = KWayMerge::tournament(streams, MergeOrder::Ascending);
+    assert_eq!(vec!["a", "b", "c", "d", "e"], merge.collect::<Vec<_>>());
+    let streams: Vec<Vec<i32>> = vec![];
+    assert_eq!(
+        None,
+        KWayMerge::tournament(streams, MergeOrder::Ascending).next()
+    );
+}
+
+#[test]
+fn kway_merge_stable() {
+    // ordered by key only
+    #[derive(Debug, PartialEq, Eq)]
+    struct Item(i32, char);
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    // equal items are taken from the streams in order
+    let streams = vec![
+        vec![Item(1, 'a'), Item(3, 'a')],
+        vec![Item(1, 'b'), Item(2, 'b')],
+        vec![Item(3, 'c')],
+        vec![Item(1, 'd'), Item(3, 'd')],
+    ];
+    let merged: String = KWayMerge::tournament(streams, MergeOrder::Ascending)
+        .map(|item| item.1)
+        .collect();
+    assert_eq!("abdbacd", merged);
+}
+
 #[test]
 fn quick_find_uf() {
     let i = TINY_UF;
@@ -172,6 +390,176 @@ fn quick_find_uf() {
     assert_eq!(2, uf.count());
 }
 
+fn check_union_find<U: UnionFind>() {
+    let mut uf = U::new(10);
+    assert_eq!(10, uf.count());
+    assert_eq!(1, uf.component_size(3));
+
+    for (p, q) in [(4, 3), (3, 8), (6, 5), (9, 4), (2, 1), (8, 9), (5, 0)] {
+        uf.union(p, q);
+    }
+    // 重复的 union 不改变 count
+    uf.union(3, 9);
+    assert_eq!(4, uf.count());
+    assert!(uf.connected(3, 9));
+    assert!(uf.connected(0, 6));
+    assert!(!uf.connected(0, 1));
+    assert_eq!(uf.find(4), uf.find(8));
+    assert_eq!(4, uf.component_size(8));
+    assert_eq!(3, uf.component_size(0));
+    assert_eq!(2, uf.component_size(1));
+    assert_eq!(1, uf.component_size(7));
+
+    uf.union(7, 2);
+    uf.union(6, 1);
+    assert_eq!(2, uf.count());
+    assert_eq!(6, uf.component_size(7));
+    assert!(uf.connected(0, 7));
+}
+
+#[test]
+fn union_find_trait() {
+    check_union_find::<QuickFindUF>();
+    check_union_find::<QuickUnionUF>();
+    check_union_find::<WeightedQuickUnionUF>();
+    check_union_find::<UF>();
+    check_union_find::<RollbackUF>();
+    check_union_find::<PotentialUF<i64>>();
+    check_union_find::<PotentialUF<bool>>();
+    check_union_find::<ConcurrentUF>();
+}
+
+#[test]
+fn potential_uf() {
+    let mut uf = PotentialUF::<i64>::new(6);
+    assert_eq!(None, uf.diff(0, 1));
+    assert_eq!(Some(0), uf.diff(2, 2));
+
+    // x1 is 5 more than x0, x2 is 3 less than x1, ...
+    assert_eq!(Ok(true), uf.union_with_diff(0, 1, 5));
+    assert_eq!(Ok(true), uf.union_with_diff(1, 2, -3));
+    assert_eq!(Ok(true), uf.union_with_diff(4, 3, 10));
+    assert_eq!(Ok(true), uf.union_with_diff(3, 5, 1));
+    assert_eq!(Some(2), uf.diff(0, 2));
+    assert_eq!(Some(-2), uf.diff(2, 0));
+    assert_eq!(Some(11), uf.diff(4, 5));
+    assert_eq!(None, uf.diff(0, 5));
+    assert_eq!(2, uf.count());
+
+    assert_eq!(Ok(true), uf.union_with_diff(2, 4, 7));
+    assert_eq!(Some(9), uf.diff(0, 4));
+    assert_eq!(Some(20), uf.diff(0, 5));
+    assert_eq!(Some(-15), uf.diff(5, 1));
+    assert_eq!(1, uf.count());
+    assert_eq!(6, uf.component_size(3));
+
+    // agrees with the constraints before
+    assert_eq!(Ok(false), uf.union_with_diff(5, 0, -20));
+    let err = uf.union_with_diff(0, 5, 19).unwrap_err();
+    assert_eq!(
+        Contradiction {
+            p: 0,
+            q: 5,
+            expected: 20,
+            given: 19
+        },
+        err
+    );
+    assert_eq!("diff(0, 5) = 19 contradicts 20", err.to_string());
+    // rejected, nothing changes
+    assert_eq!(Some(20), uf.diff(0, 5));
+    assert_eq!(1, uf.count());
+}
+
+#[test]
+#[should_panic(expected = "diff(1, 0) = 0 contradicts -5")]
+fn potential_uf_union_contradiction() {
+    // union is a zero diff
+    let mut uf = PotentialUF::<i32>::new(2);
+    uf.union_with_diff(0, 1, 5).unwrap();
+    uf.union(1, 0);
+}
+
+#[test]
+fn potential_uf_parity() {
+    // true: x and y have different parity
+    let mut uf = PotentialUF::<bool>::new(4);
+    assert_eq!(Ok(true), uf.union_with_diff(0, 1, true));
+    assert_eq!(Ok(true), uf.union_with_diff(1, 2, true));
+    assert_eq!(Some(false), uf.diff(0, 2));
+    assert_eq!(Ok(true), uf.union_with_diff(2, 3, true));
+    assert_eq!(Some(true), uf.diff(3, 0));
+    // odd cycle 0-1-2-3-0 with 0, 2 differ
+    assert!(uf.union_with_diff(2, 0, true).is_err());
+    assert!(uf.connected(0, 3));
+}
+
+#[test]
+fn potential_uf_random() {
+    // the constraints of random values agree with each other
+    let n = 50;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+    let values: Vec<i64> = (0..n).map(|_| rng.gen_range(-500..500)).collect();
+    let mut uf = PotentialUF::new(n);
+    let mut merged = 0;
+    for _ in 0..200 {
+        let (p, q) = (rng.gen_range(0..n), rng.gen_range(0..n));
+        if uf.union_with_diff(p, q, values[q] - values[p]).unwrap() {
+            merged += 1;
+        }
+        // a wrong diff always contradicts
+        let wrong = values[q] - values[p] + 1;
+        assert!(uf.union_with_diff(p, q, wrong).is_err());
+    }
+    assert_eq!(n - merged, uf.count());
+    for p in 0..n {
+        for q in 0..n {
+            match uf.diff(p, q) {
+                Some(d) => assert_eq!(values[q] - values[p], d),
+                None => assert!(!uf.connected(p, q)),
+            }
+        }
+    }
+}
+
+#[test]
+fn rollback_uf() {
+    let uf = RollbackUF::from_str(TINY_UF).unwrap();
+    assert_eq!(2, uf.count());
+    let all = uf.snapshot();
+
+    let mut uf = RollbackUF::new(10);
+    let empty = uf.snapshot();
+    uf.union(4, 3);
+    uf.union(3, 8);
+    let s1 = uf.snapshot();
+    uf.union(6, 5);
+    uf.union(9, 4);
+    // no link made, nothing to undo
+    uf.union(8, 9);
+    assert_eq!(s1 + 2, uf.snapshot());
+    assert_eq!(4, uf.component_size(3));
+    assert_eq!(6, uf.count());
+
+    uf.rollback(s1);
+    assert_eq!(8, uf.count());
+    assert_eq!(3, uf.component_size(4));
+    assert_eq!(1, uf.component_size(9));
+    assert!(!uf.connected(5, 6));
+    assert!(uf.connected(4, 8));
+
+    // the same unions again after a rollback
+    uf.union(9, 4);
+    assert_eq!(4, uf.component_size(9));
+    uf.rollback(empty);
+    assert_eq!(10, uf.count());
+    for i in 0..10 {
+        assert_eq!(i, uf.find(i));
+        assert_eq!(1, uf.component_size(i));
+    }
+    assert_eq!(8, all);
+}
+
 #[test]
 fn top_m() {
     let mut top = TopM::new(5);
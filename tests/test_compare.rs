@@ -0,0 +1,112 @@
+use algo::common::compare::{By, CaseInsensitive, Compare, Natural, Reverse, TotalOrder};
+use algo::search::SkipList;
+use algo::tree::binary::check::Balance;
+use algo::tree::binary::treap::Treap;
+use algo::tree::binary::Tree;
+use std::cmp::Ordering;
+
+#[test]
+fn case_insensitive() {
+    use algo::tree::binary::bst::BSTree;
+    let mut tree: Tree<By<String, CaseInsensitive>, i32> = Tree::default();
+    for (i, s) in ["banana", "Apple", "cherry", "APPLE", "Banana"]
+        .iter()
+        .enumerate()
+    {
+        tree.insert(By::new(s.to_string()), i as i32);
+    }
+    // "APPLE" and "Banana" replaced the entries of "Apple" and "banana"
+    let key = "apple".to_string();
+    assert_eq!(Some(&3), tree.get(By::from_ref(&key)));
+    assert_eq!(Some(&4), tree.get(&By::new("BANANA".to_string())));
+    let keys: Vec<&str> = tree.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(vec!["APPLE", "Banana", "cherry"], keys);
+
+    assert_eq!(Some(4), tree.remove(&"BaNaNa".to_string().into()));
+    assert_eq!(Ok(()), tree.check(Balance::None));
+}
+
+#[test]
+fn reverse() {
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let mut tree: Tree<By<i32, Reverse>, i32> = Tree::default();
+    for v in 0..100 {
+        tree.insert(By::new(v), v);
+    }
+    assert_eq!(Ok(()), tree.check(Balance::LeftLeaningRedBlack));
+    assert_eq!(Some(&By::new(99)), tree.min());
+    assert_eq!(Some(&By::new(0)), tree.max());
+    let keys: Vec<i32> = tree.iter().map(|(k, _)| **k).collect();
+    assert_eq!((0..100).rev().collect::<Vec<i32>>(), keys);
+
+    // range follows the ordering of the keys
+    let keys: Vec<i32> = tree
+        .range(By::new(10)..By::new(5))
+        .map(|(k, _)| **k)
+        .collect();
+    assert_eq!(vec![10, 9, 8, 7, 6], keys);
+}
+
+#[test]
+fn total_order() {
+    use algo::tree::binary::bst::BSTree;
+    let data = [
+        2.5,
+        f64::NAN,
+        -0.0,
+        0.0,
+        f64::NEG_INFINITY,
+        -1.0,
+        f64::INFINITY,
+    ];
+    let mut treap: Treap<By<f64, TotalOrder>, usize> = Treap::default();
+    let mut list: SkipList<By<f64, TotalOrder>, usize> = SkipList::default();
+    for (i, &v) in data.iter().enumerate() {
+        treap.insert(By::new(v), i);
+        list.insert(By::new(v), i);
+    }
+    assert!(treap.is_valid());
+    assert!(list.is_valid());
+
+    // -0.0 and 0.0 are different keys
+    assert_eq!(7, treap.len());
+    assert_eq!(Some(&2), treap.get(&By::new(-0.0)));
+    assert_eq!(Some(&1), list.get(&By::new(f64::NAN)));
+
+    let expect = ["-inf", "-1", "-0", "0", "2.5", "inf", "NaN"];
+    let keys: Vec<String> = treap.iter().map(|(k, _)| k.to_string()).collect();
+    assert_eq!(expect.to_vec(), keys);
+    let keys: Vec<String> = list.iter().map(|(k, _)| k.to_string()).collect();
+    assert_eq!(expect.to_vec(), keys);
+}
+
+#[test]
+fn custom_strategy() {
+    // by length, then by content
+    struct ByLen;
+    impl Compare<String> for ByLen {
+        fn compare(a: &String, b: &String) -> Ordering {
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        }
+    }
+
+    use algo::tree::binary::bst::BSTree;
+    let mut tree: Tree<By<String, ByLen>, ()> = Tree::default();
+    for s in ["ccc", "a", "bb", "aa", "dddd"] {
+        tree.insert(By::new(s.to_string()), ());
+    }
+    let keys: Vec<String> = tree.iter().map(|(k, _)| k.to_string()).collect();
+    assert_eq!(vec!["a", "aa", "bb", "ccc", "dddd"], keys);
+}
+
+#[test]
+fn build_by_keys() {
+    use algo::tree::binary::builder::level::BuildTreeInLevel;
+    use algo::tree::binary::builder::TreeBuilder;
+    // a BST in reverse order
+    let tree: Tree<By<i32, Reverse>, i32> = TreeBuilder::build_in_level(&["2", "3", "1"]);
+    assert_eq!(Ok(()), tree.check(Balance::None));
+    let tree: Tree<By<i32, Natural>, i32> = TreeBuilder::build_in_level(&["2", "3", "1"]);
+    assert!(tree.check(Balance::None).is_err());
+    assert_eq!(vec!["2", "3", "1"], tree.to_level());
+}
@@ -1,6 +1,9 @@
 #[macro_use]
 extern crate approx;
 
+use algo::common::{
+    IndexFibonacciHeap, IndexPairingHeap, QuickFindUF, QuickUnionUF, WeightedQuickUnionUF,
+};
 use algo::graph::mst::{EWGraph, KruskalMST, LazyPrimMST, PrimMST, MST};
 use algo::graph::IEWGraph;
 
@@ -54,6 +57,18 @@ fn Prim_mst() {
     // }
 }
 
+#[allow(non_snake_case)]
+#[test]
+fn Prim_mst_heaps() {
+    let g = create_graph(TINY_EWG);
+    let mst = PrimMST::with_pq::<IndexPairingHeap<f32>>(g.as_ref());
+    assert_relative_eq!(1.81, mst.weight());
+    assert!(mst.check(g.as_ref()).is_ok());
+    let mst = PrimMST::with_pq::<IndexFibonacciHeap<f32>>(g.as_ref());
+    assert_relative_eq!(1.81, mst.weight());
+    assert!(mst.check(g.as_ref()).is_ok());
+}
+
 #[allow(non_snake_case)]
 #[test]
 fn Kruskal_mst() {
@@ -68,6 +83,21 @@ fn Kruskal_mst() {
     // }
 }
 
+#[allow(non_snake_case)]
+#[test]
+fn Kruskal_mst_ufs() {
+    let g = create_graph(TINY_EWG);
+    let mst = KruskalMST::with_uf::<QuickFindUF>(g.as_ref());
+    assert_relative_eq!(1.81, mst.weight());
+    assert!(mst.check(g.as_ref()).is_ok());
+    let mst = KruskalMST::with_uf::<QuickUnionUF>(g.as_ref());
+    assert_relative_eq!(1.81, mst.weight());
+    assert!(mst.check(g.as_ref()).is_ok());
+    let mst = KruskalMST::with_uf::<WeightedQuickUnionUF>(g.as_ref());
+    assert_relative_eq!(1.81, mst.weight());
+    assert!(mst.check(g.as_ref()).is_ok());
+}
+
 fn create_graph(i: &str) -> Box<dyn IEWGraph> {
     Box::new(EWGraph::from(i))
 }
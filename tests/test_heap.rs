@@ -0,0 +1,152 @@
+use algo::common::drop::{self, Elem};
+use algo::common::{FibonacciHeap, MeldableHeap, PairingHeap};
+use rand::{Rng, SeedableRng};
+
+macro_rules! meldable_heap_tests {
+    ($($name:ident: $heap:ident,)*) => {
+        $(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn push_pop() {
+                    let mut heap = $heap::new();
+                    assert_eq!(None, heap.pop());
+                    for &k in [5, 3, 8, 1, 9, 2, 7].iter() {
+                        heap.push(k, k * 10);
+                    }
+                    assert_eq!(7, heap.len());
+                    assert_eq!(Some((&1, &10)), heap.peek());
+                    let mut keys = Vec::new();
+                    while let Some((k, v)) = heap.pop() {
+                        assert_eq!(k * 10, v);
+                        keys.push(k);
+                    }
+                    assert_eq!(vec![1, 2, 3, 5, 7, 8, 9], keys);
+                    assert!(heap.is_empty());
+                }
+
+                #[test]
+                fn decrease_key() {
+                    let mut heap = $heap::new();
+                    let handles: Vec<_> = (0..10).map(|i| heap.push(10 + i, i)).collect();
+                    assert_eq!(Some((10, 0)), heap.pop());
+                    unsafe {
+                        heap.decrease_key(handles[9], 5);
+                        heap.decrease_key(handles[4], 4);
+                        heap.decrease_key(handles[7], 17);
+                        assert_eq!(&5, heap.key_of(handles[9]));
+                    }
+                    assert_eq!(Some((4, 4)), heap.pop());
+                    assert_eq!(Some((5, 9)), heap.pop());
+                    assert_eq!(Some((11, 1)), heap.pop());
+                }
+
+                #[test]
+                #[should_panic(expected = "key is greater than the key of the entry")]
+                fn increase_key() {
+                    let mut heap = $heap::new();
+                    let h = heap.push(1, ());
+                    unsafe { heap.decrease_key(h, 2) };
+                }
+
+                #[test]
+                fn meld() {
+                    let mut a = $heap::new();
+                    let mut b = $heap::new();
+                    for i in 0..5 {
+                        a.push(2 * i, ());
+                    }
+                    let hb: Vec<_> = (0..5).map(|i| b.push(2 * i + 1, ())).collect();
+                    a.meld(b);
+                    assert_eq!(10, a.len());
+                    // the handles of b are handles of a
+                    unsafe { a.decrease_key(hb[4], -1) };
+                    let keys: Vec<i32> = std::iter::from_fn(|| a.pop().map(|(k, _)| k)).collect();
+                    assert_eq!(vec![-1, 0, 1, 2, 3, 4, 5, 6, 7, 8], keys);
+
+                    a.meld($heap::new());
+                    assert!(a.is_empty());
+                }
+
+                #[test]
+                fn random() {
+                    let mut rng = rand::rngs::StdRng::seed_from_u64(20);
+                    let mut heap = $heap::new();
+                    // model: keys of the entries still in the heap, by id
+                    let mut keys: Vec<Option<i64>> = Vec::new();
+                    let mut handles = Vec::new();
+                    for _ in 0..5000 {
+                        match rng.gen_range(0..10) {
+                            0..=3 => {
+                                let k = rng.gen_range(0..1000);
+                                handles.push(heap.push(k, keys.len()));
+                                keys.push(Some(k));
+                            }
+                            4..=6 => {
+                                let i = rng.gen_range(0..keys.len().max(1));
+                                if let Some(Some(k)) = keys.get(i).cloned() {
+                                    let k = k - rng.gen_range(0..500);
+                                    unsafe { heap.decrease_key(handles[i], k) };
+                                    keys[i] = Some(k);
+                                }
+                            }
+                            _ => {
+                                let min = keys.iter().flatten().min().cloned();
+                                match heap.pop() {
+                                    None => assert_eq!(None, min),
+                                    Some((k, i)) => {
+                                        assert_eq!(Some(k), min);
+                                        assert_eq!(Some(k), keys[i].take());
+                                    }
+                                }
+                            }
+                        }
+                        assert_eq!(keys.iter().flatten().count(), heap.len());
+                    }
+                    let mut rest: Vec<i64> = keys.into_iter().flatten().collect();
+                    rest.sort();
+                    let popped: Vec<i64> =
+                        std::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect();
+                    assert_eq!(rest, popped);
+                }
+
+                #[test]
+                fn drop_no_leak() {
+                    drop::with(|ctx| {
+                        let mut heap = $heap::new();
+                        let handles: Vec<_> = (0..100).map(|i| heap.push(i, Elem)).collect();
+                        for _ in 0..10 {
+                            heap.pop();
+                        }
+                        // build deeper trees
+                        for (i, &h) in handles.iter().enumerate().skip(50) {
+                            unsafe { heap.decrease_key(h, i as i32 - 100) };
+                        }
+                        heap.pop();
+                        assert_eq!(11, ctx.get());
+                        std::mem::drop(heap);
+                        assert_eq!(100, ctx.get());
+                    });
+                }
+
+                #[test]
+                fn generic() {
+                    fn drain<H: MeldableHeap<i32, ()>>(mut heap: H) -> Vec<i32> {
+                        std::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect()
+                    }
+                    let mut heap = $heap::new();
+                    for &k in [3, 1, 2].iter() {
+                        heap.push(k, ());
+                    }
+                    assert_eq!(vec![1, 2, 3], drain(heap));
+                }
+            }
+        )*
+    };
+}
+
+meldable_heap_tests! {
+    pairing_heap: PairingHeap,
+    fibonacci_heap: FibonacciHeap,
+}
@@ -0,0 +1,207 @@
+use algo::common::util;
+use algo::tree::binary::interval::{Interval, IntervalST};
+use algo::tree::binary::kd_tree::{KdTree, Point2D, RectHV};
+
+fn gen_intervals(n: i32) -> Vec<Interval<i32>> {
+    let mut data: Vec<Interval<i32>> = (0..n)
+        .map(|i| Interval::new(i * 3, i * 3 + (i * 37) % 50))
+        .collect();
+    util::shuffle(&mut data);
+    data
+}
+
+#[test]
+fn interval_insert_get() {
+    let mut st = IntervalST::default();
+    assert!(st.is_empty());
+    st.insert(Interval::new(17, 19), 'a');
+    st.insert(Interval::new(5, 8), 'b');
+    st.insert(Interval::new(21, 24), 'c');
+    st.insert(Interval::new(4, 8), 'd');
+    st.insert(Interval::new(15, 18), 'e');
+    st.insert(Interval::new(7, 10), 'f');
+    st.insert(Interval::new(16, 22), 'g');
+    assert_eq!(7, st.len());
+    assert_eq!(Some(&'d'), st.get(&Interval::new(4, 8)));
+    assert_eq!(None, st.get(&Interval::new(4, 9)));
+
+    // update
+    st.insert(Interval::new(4, 8), 'x');
+    assert_eq!(7, st.len());
+    assert_eq!(Some(&'x'), st.get(&Interval::new(4, 8)));
+
+    // (23, 25) intersects only (21, 24)
+    assert_eq!(
+        Some((&Interval::new(21, 24), &'c')),
+        st.search(&Interval::new(23, 25))
+    );
+    // (12, 14) intersects nothing
+    assert_eq!(None, st.search(&Interval::new(12, 14)));
+    let found: Vec<char> = st
+        .search_all(&Interval::new(9, 16))
+        .into_iter()
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq!(vec!['f', 'e', 'g'], found);
+}
+
+#[test]
+fn interval_search_brute_force() {
+    let data = gen_intervals(300);
+    let mut st = IntervalST::default();
+    for (i, v) in data.iter().enumerate() {
+        st.insert(*v, i);
+    }
+    assert_eq!(data.len(), st.len());
+
+    for lo in (0..1000).step_by(7) {
+        let query = Interval::new(lo, lo + lo % 11);
+        let mut expect: Vec<Interval<i32>> = data
+            .iter()
+            .filter(|v| v.intersects(&query))
+            .copied()
+            .collect();
+        expect.sort();
+        let r: Vec<Interval<i32>> = st.search_all(&query).into_iter().map(|(k, _)| *k).collect();
+        assert_eq!(expect, r);
+        match st.search(&query) {
+            Some((k, _)) => assert!(k.intersects(&query)),
+            None => assert!(expect.is_empty()),
+        }
+    }
+}
+
+#[test]
+fn interval_delete() {
+    let mut data = gen_intervals(200);
+    let mut st = IntervalST::default();
+    for (i, v) in data.iter().enumerate() {
+        st.insert(*v, i);
+    }
+    util::shuffle(&mut data);
+    let (removed, kept) = data.split_at(100);
+    for v in removed {
+        st.delete(v);
+        assert!(!st.contains(v));
+    }
+    assert_eq!(100, st.len());
+
+    let query = Interval::new(0, 1000);
+    let mut expect = kept.to_vec();
+    expect.sort();
+    let r: Vec<Interval<i32>> = st.search_all(&query).into_iter().map(|(k, _)| *k).collect();
+    assert_eq!(expect, r);
+
+    // delete not exist
+    st.delete(&Interval::new(-2, -1));
+    assert_eq!(100, st.len());
+}
+
+#[test]
+fn interval_drop() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut st = IntervalST::default();
+        for i in 0..100 {
+            st.insert(Interval::new(i, i + 5), Elem);
+        }
+        for i in 0..10 {
+            st.delete(&Interval::new(i, i + 5));
+        }
+        assert_eq!(10, ctx.get());
+
+        drop(st);
+        assert_eq!(100, ctx.get());
+    });
+}
+
+fn gen_points(n: usize) -> Vec<Point2D> {
+    // distinct points on a grid, random order
+    let mut data: Vec<Point2D> = (0..n)
+        .map(|i| Point2D::new((i % 37) as f64 / 37.0, ((i * 13) % 41) as f64 / 41.0))
+        .collect();
+    util::shuffle(&mut data);
+    data
+}
+
+#[test]
+fn kd_insert_get() {
+    let mut tree = KdTree::default();
+    assert!(tree.is_empty());
+    assert_eq!(None, tree.nearest(&Point2D::new(0.5, 0.5)));
+
+    let points = [(0.7, 0.2), (0.5, 0.4), (0.2, 0.3), (0.4, 0.7), (0.9, 0.6)];
+    for (i, &(x, y)) in points.iter().enumerate() {
+        tree.insert(Point2D::new(x, y), i);
+    }
+    assert_eq!(5, tree.len());
+    assert_eq!(Some(&3), tree.get(&Point2D::new(0.4, 0.7)));
+    assert!(!tree.contains(&Point2D::new(0.4, 0.2)));
+
+    // same x as root, goes right
+    tree.insert(Point2D::new(0.7, 0.9), 5);
+    assert_eq!(Some(&5), tree.get(&Point2D::new(0.7, 0.9)));
+
+    // update
+    tree.insert(Point2D::new(0.2, 0.3), 10);
+    assert_eq!(6, tree.len());
+    assert_eq!(Some(&10), tree.get(&Point2D::new(0.2, 0.3)));
+}
+
+#[test]
+fn kd_range_brute_force() {
+    let data = gen_points(500);
+    let mut tree = KdTree::default();
+    for (i, p) in data.iter().enumerate() {
+        tree.insert(*p, i);
+    }
+    assert_eq!(data.len(), tree.len());
+
+    let rects = [
+        RectHV::new(0.0, 0.0, 1.0, 1.0),
+        RectHV::new(0.1, 0.2, 0.3, 0.5),
+        RectHV::new(0.5, 0.5, 0.5, 0.5),
+        RectHV::new(0.25, 0.0, 0.75, 0.1),
+        RectHV::new(2.0, 2.0, 3.0, 3.0),
+    ];
+    for rect in rects.iter() {
+        let mut expect: Vec<usize> = (0..data.len())
+            .filter(|&i| rect.contains(&data[i]))
+            .collect();
+        let mut r: Vec<usize> = tree.range(rect).into_iter().map(|(_, v)| *v).collect();
+        expect.sort_unstable();
+        r.sort_unstable();
+        assert_eq!(expect, r);
+    }
+}
+
+#[test]
+fn kd_nearest_brute_force() {
+    let data = gen_points(500);
+    let mut tree = KdTree::default();
+    for (i, p) in data.iter().enumerate() {
+        tree.insert(*p, i);
+    }
+
+    for i in 0..100 {
+        let q = Point2D::new((i * 7 % 100) as f64 / 97.0, (i * 31 % 100) as f64 / 89.0);
+        let expect = data
+            .iter()
+            .map(|p| p.distance_squared_to(&q))
+            .fold(f64::INFINITY, f64::min);
+        let (p, v) = tree.nearest(&q).unwrap();
+        assert_eq!(expect, p.distance_squared_to(&q));
+        assert_eq!(p, &data[*v]);
+    }
+}
+
+#[test]
+fn rect() {
+    let r = RectHV::new(0.0, 0.0, 1.0, 1.0);
+    assert!(r.contains(&Point2D::new(1.0, 0.5)));
+    assert!(!r.contains(&Point2D::new(1.1, 0.5)));
+    assert!(r.intersects(&RectHV::new(1.0, 1.0, 2.0, 2.0)));
+    assert!(!r.intersects(&RectHV::new(1.5, 0.0, 2.0, 2.0)));
+    assert_eq!(0.0, r.distance_squared_to(&Point2D::new(0.5, 0.5)));
+    assert_eq!(2.0, r.distance_squared_to(&Point2D::new(2.0, 2.0)));
+}
@@ -0,0 +1,244 @@
+//! The same tests for every implementation of BSTree
+
+use algo::common::drop::{self, Elem};
+use algo::common::util;
+use algo::search::SkipList;
+use algo::tree::binary::arena_tree::ArenaTree;
+use algo::tree::binary::bst::BSTree;
+use algo::tree::binary::splay::SplayTree;
+use algo::tree::binary::treap::Treap;
+use algo::tree::binary::Tree;
+use std::collections::BTreeMap;
+
+const DATA: [i32; 10] = [4, 3, 8, 1, 7, 16, 2, 10, 9, 14];
+
+fn build<T: BSTree<i32, i32> + Default>() -> T {
+    let mut tree = T::default();
+    for v in DATA.iter() {
+        tree.insert(*v, *v);
+    }
+    tree
+}
+
+fn get_min_max<T: BSTree<i32, i32> + Default>() {
+    let tree: T = build();
+    for v in DATA.iter() {
+        assert_eq!(Some(v), tree.get(v));
+    }
+    assert_eq!(None, tree.get(&100));
+    assert_eq!(Some(&1), tree.min());
+    assert_eq!(Some(&16), tree.max());
+
+    let tree = T::default();
+    assert_eq!(None, tree.min());
+    assert_eq!(None, tree.max());
+}
+
+fn succ_pred<T: BSTree<i32, i32> + Default>() {
+    let tree: T = build();
+    assert_eq!(Some(&9), tree.succ(&8));
+    assert_eq!(Some(&3), tree.succ(&2));
+    assert_eq!(None, tree.succ(&16));
+    assert_eq!(None, tree.succ(&5));
+    assert_eq!(Some(&8), tree.pred(&9));
+    assert_eq!(Some(&2), tree.pred(&3));
+    assert_eq!(None, tree.pred(&1));
+    assert_eq!(None, tree.pred(&5));
+}
+
+fn delete<T: BSTree<i32, i32> + Default>() {
+    let mut tree: T = build();
+    for v in [1, 8, 4] {
+        tree.delete(&v);
+        assert_eq!(None, tree.get(&v));
+    }
+    // delete not exist
+    tree.delete(&100);
+    for v in [3, 7, 16, 2, 10, 9, 14] {
+        assert_eq!(Some(&v), tree.get(&v));
+    }
+    assert_eq!(Some(&2), tree.min());
+}
+
+fn entry_remove<T: BSTree<i32, i32> + Default>() {
+    let mut tree: T = build();
+    *tree.get_mut(&7).unwrap() += 100;
+    assert_eq!(Some(&107), tree.get(&7));
+    assert_eq!(None, tree.get_mut(&100));
+
+    *tree.entry(7).or_insert(0) += 1;
+    *tree.entry(5).or_insert(0) += 1;
+    assert_eq!(Some(&108), tree.get(&7));
+    assert_eq!(Some(&1), tree.get(&5));
+    tree.entry(5).and_modify(|v| *v = 50).or_default();
+    assert_eq!(Some(&50), tree.get(&5));
+
+    assert_eq!(Some(108), tree.remove(&7));
+    assert_eq!(None, tree.remove(&7));
+    assert_eq!(Some(16), tree.remove(&16));
+    assert_eq!(Some(&14), tree.max());
+}
+
+fn brute_force<T: BSTree<i32, i32> + Default>() {
+    let mut data: Vec<i32> = (0..1000).collect();
+    util::shuffle(&mut data);
+    let mut tree = T::default();
+    let mut expect = BTreeMap::new();
+    for (i, &v) in data.iter().enumerate() {
+        tree.insert(v % 500, i as i32);
+        expect.insert(v % 500, i as i32);
+    }
+    for (i, &v) in data.iter().enumerate().step_by(3) {
+        assert_eq!(expect.remove(&v), tree.remove(&v), "i = {}", i);
+    }
+    for v in 0..1000 {
+        assert_eq!(expect.get(&v), tree.get(&v));
+    }
+    assert_eq!(expect.keys().next(), tree.min());
+    assert_eq!(expect.keys().next_back(), tree.max());
+}
+
+fn drop_with_remove<T: BSTree<i32, Elem> + Default>() {
+    drop::with(|ctx| {
+        let mut tree = T::default();
+        for v in 0..100 {
+            tree.insert(v, Elem);
+        }
+        for v in 0..10 {
+            tree.delete(&v);
+        }
+        assert_eq!(10, ctx.get());
+        let elem = tree.remove(&50);
+        assert_eq!(10, ctx.get());
+        drop(elem);
+        assert_eq!(11, ctx.get());
+
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
+
+macro_rules! ordered_map_tests {
+    ($name:ident, $t:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn get_min_max() {
+                super::get_min_max::<$t<i32, i32>>();
+            }
+
+            #[test]
+            fn succ_pred() {
+                super::succ_pred::<$t<i32, i32>>();
+            }
+
+            #[test]
+            fn delete() {
+                super::delete::<$t<i32, i32>>();
+            }
+
+            #[test]
+            fn entry_remove() {
+                super::entry_remove::<$t<i32, i32>>();
+            }
+
+            #[test]
+            fn brute_force() {
+                super::brute_force::<$t<i32, i32>>();
+            }
+
+            #[test]
+            fn drop_with_remove() {
+                super::drop_with_remove::<$t<i32, Elem>>();
+            }
+        }
+    };
+}
+
+ordered_map_tests!(bst, Tree);
+ordered_map_tests!(treap, Treap);
+ordered_map_tests!(splay, SplayTree);
+ordered_map_tests!(skip_list, SkipList);
+ordered_map_tests!(arena_tree, ArenaTree);
+
+#[test]
+fn treap_is_valid() {
+    let mut tree = Treap::default();
+    // sorted insertions do not degrade a treap
+    for v in 0..1000 {
+        tree.insert(v, v);
+        assert!(tree.is_valid());
+    }
+    assert_eq!(1000, tree.len());
+    assert!(tree.height() < 50);
+    for v in (0..1000).step_by(2) {
+        tree.delete(&v);
+        assert!(tree.is_valid());
+    }
+    assert_eq!(500, tree.len());
+    let keys: Vec<i32> = tree.iter().map(|(k, _)| *k).collect();
+    assert_eq!((1..1000).step_by(2).collect::<Vec<i32>>(), keys);
+}
+
+#[test]
+fn splay_access() {
+    let mut tree = SplayTree::default();
+    for v in 0..1000 {
+        tree.insert(v, v);
+        assert_eq!(Some(&v), tree.root());
+    }
+    assert!(tree.is_valid());
+
+    // the accessed key is moved to the root
+    assert_eq!(Some(&0), tree.splay_get(&0));
+    assert_eq!(Some(&0), tree.root());
+    assert!(tree.is_valid());
+    // splaying the deep node halves the depth of the path
+    assert!(tree.height() < 600);
+
+    // not found, the last node on the search path is splayed
+    let _ = tree.splay_get(&2000);
+    assert_eq!(Some(&999), tree.root());
+
+    // get does not splay
+    assert_eq!(Some(&500), tree.get(&500));
+    assert_eq!(Some(&999), tree.root());
+    assert_eq!(Some(&1), tree.splay_succ(&0));
+    assert_eq!(Some(&1), tree.root());
+    assert_eq!(Some(&998), tree.splay_pred(&999));
+    assert_eq!(Some(&998), tree.root());
+    assert_eq!(Some(&0), tree.splay_min());
+    assert_eq!(Some(&0), tree.root());
+    assert_eq!(Some(&999), tree.splay_max());
+    assert_eq!(Some(&999), tree.root());
+    assert!(tree.is_valid());
+
+    for v in (0..1000).step_by(3) {
+        tree.delete(&v);
+        assert!(tree.is_valid());
+    }
+    assert_eq!(666, tree.len());
+}
+
+#[test]
+fn skip_list_is_valid() {
+    let mut list = SkipList::default();
+    let mut data: Vec<i32> = (0..1000).collect();
+    util::shuffle(&mut data);
+    for v in &data {
+        list.insert(*v, *v);
+    }
+    assert!(list.is_valid());
+    assert_eq!(1000, list.len());
+    assert!(list.level() > 3);
+    let keys: Vec<i32> = list.iter().map(|(k, _)| *k).collect();
+    assert_eq!((0..1000).collect::<Vec<i32>>(), keys);
+
+    for v in &data {
+        list.delete(v);
+    }
+    assert!(list.is_valid());
+    assert!(list.is_empty());
+    assert_eq!(0, list.level());
+}
@@ -0,0 +1,133 @@
+use algo::common::util;
+use algo::tree::binary::ordered::{self, OrderedST};
+use algo::tree::binary::{avl, bst, rb, rb2, Tree};
+
+// keys of "SEARCHEXAMPLE", sorted: A C E H L M P R S X
+const KEYS: &str = "SEARCHEXAMPLE";
+
+fn build_bst() -> Tree<char, usize> {
+    use bst::BSTree;
+    let mut tree = Tree::default();
+    for (i, c) in KEYS.chars().enumerate() {
+        tree.insert(c, i);
+    }
+    tree
+}
+
+fn build_rb() -> Tree<char, usize> {
+    use rb::RedBlackTree;
+    let mut tree = Tree::default();
+    for (i, c) in KEYS.chars().enumerate() {
+        tree.insert(c, i);
+    }
+    tree
+}
+
+fn build_rb2() -> Tree<char, usize> {
+    use rb2::RedBlackTreeV2;
+    let mut tree = Tree::default();
+    for (i, c) in KEYS.chars().enumerate() {
+        tree.insert(c, i);
+    }
+    tree
+}
+
+fn build_avl() -> Tree<char, usize> {
+    use avl::AvlTree;
+    let mut tree = Tree::default();
+    for (i, c) in KEYS.chars().enumerate() {
+        tree.insert(c, i);
+    }
+    tree
+}
+
+fn all_trees() -> Vec<Tree<char, usize>> {
+    vec![build_bst(), build_rb(), build_rb2(), build_avl()]
+}
+
+#[test]
+fn rank_select() {
+    let sorted: Vec<char> = "ACEHLMPRSX".chars().collect();
+    for tree in all_trees() {
+        assert!(ordered::is_size_consistent(tree.root));
+        for (i, c) in sorted.iter().enumerate() {
+            assert_eq!(i, tree.rank(c));
+            assert_eq!(Some(c), tree.select(i));
+        }
+        assert_eq!(None, tree.select(sorted.len()));
+        assert_eq!(0, tree.rank(&'0'));
+        assert_eq!(2, tree.rank(&'D'));
+        assert_eq!(sorted.len(), tree.rank(&'Z'));
+    }
+}
+
+#[test]
+fn floor_ceiling() {
+    for tree in all_trees() {
+        assert_eq!(Some(&'E'), tree.floor(&'E'));
+        assert_eq!(Some(&'E'), tree.floor(&'G'));
+        assert_eq!(Some(&'X'), tree.floor(&'Z'));
+        assert_eq!(None, tree.floor(&'0'));
+
+        assert_eq!(Some(&'E'), tree.ceiling(&'E'));
+        assert_eq!(Some(&'R'), tree.ceiling(&'Q'));
+        assert_eq!(Some(&'A'), tree.ceiling(&'0'));
+        assert_eq!(None, tree.ceiling(&'Z'));
+    }
+}
+
+#[test]
+fn range() {
+    for tree in all_trees() {
+        assert_eq!(6, tree.size_between(&'E', &'R'));
+        assert_eq!(5, tree.size_between(&'D', &'Q'));
+        assert_eq!(10, tree.size_between(&'0', &'Z'));
+        assert_eq!(0, tree.size_between(&'R', &'E'));
+        assert_eq!(0, tree.size_between(&'T', &'W'));
+
+        let keys: String = tree.keys_between(&'D', &'Q').into_iter().collect();
+        assert_eq!("EHLMP", keys);
+        let keys: String = tree.keys_between(&'E', &'R').into_iter().collect();
+        assert_eq!("EHLMPR", keys);
+        assert!(tree.keys_between(&'R', &'E').is_empty());
+    }
+}
+
+#[test]
+fn size_after_delete() {
+    let mut data: Vec<i32> = (0..200).collect();
+    util::shuffle(&mut data);
+
+    let mut t1 = Tree::default();
+    let mut t2 = Tree::default();
+    let mut t3 = Tree::default();
+    let mut t4 = Tree::default();
+    for v in &data {
+        bst::BSTree::insert(&mut t1, *v, *v);
+        rb::RedBlackTree::insert(&mut t2, *v, *v);
+        rb2::RedBlackTreeV2::insert(&mut t3, *v, *v);
+        avl::AvlTree::insert(&mut t4, *v, *v);
+    }
+
+    util::shuffle(&mut data);
+    for (i, v) in data.iter().take(100).enumerate() {
+        bst::BSTree::delete(&mut t1, v);
+        rb::RedBlackTree::delete(&mut t2, v);
+        rb2::RedBlackTreeV2::delete(&mut t3, v);
+        avl::AvlTree::delete(&mut t4, v);
+
+        for tree in [&t1, &t2, &t3, &t4] {
+            assert!(ordered::is_size_consistent(tree.root));
+            assert_eq!(200 - i - 1, algo::tree::binary::Node::size(tree.root));
+        }
+    }
+
+    let mut remain: Vec<i32> = data.iter().skip(100).cloned().collect();
+    remain.sort_unstable();
+    for tree in [&t1, &t2, &t3, &t4] {
+        for (i, v) in remain.iter().enumerate() {
+            assert_eq!(i, tree.rank(v));
+            assert_eq!(Some(v), tree.select(i));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use algo::common::util;
+use algo::tree::binary::persistent::PersistentMap;
+use std::collections::BTreeMap;
+
+#[test]
+fn insert_get() {
+    let v0 = PersistentMap::new();
+    let v1 = v0.insert('S', 0);
+    let v2 = v1.insert('E', 1);
+    let v3 = v2.insert('S', 2);
+
+    assert!(v0.is_empty());
+    assert_eq!(Some(&0), v1.get(&'S'));
+    assert_eq!(None, v1.get(&'E'));
+    assert_eq!(Some(&1), v2.get(&'E'));
+    assert_eq!(Some(&0), v2.get(&'S'));
+    assert_eq!(Some(&2), v3.get(&'S'));
+    assert_eq!(2, v3.len());
+    assert_eq!(Some(&'E'), v3.min());
+    assert_eq!(Some(&'S'), v3.max());
+}
+
+#[test]
+fn versions_brute_force() {
+    let mut data: Vec<i32> = (0..300).collect();
+    util::shuffle(&mut data);
+
+    let mut versions = vec![PersistentMap::new()];
+    let mut expects = vec![BTreeMap::new()];
+    for (i, &v) in data.iter().enumerate() {
+        let mut expect = expects.last().unwrap().clone();
+        let version = if i % 3 == 2 {
+            // delete an earlier key
+            let k = data[i / 2];
+            expect.remove(&k);
+            versions.last().unwrap().delete(&k)
+        } else {
+            expect.insert(v, i);
+            versions.last().unwrap().insert(v, i)
+        };
+        assert!(version.is_valid());
+        versions.push(version);
+        expects.push(expect);
+    }
+
+    // all versions are intact
+    for (version, expect) in versions.iter().zip(expects.iter()) {
+        assert_eq!(expect.len(), version.len());
+        let r: Vec<(i32, usize)> = version.iter().map(|(k, v)| (*k, *v)).collect();
+        let expect: Vec<(i32, usize)> = expect.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(expect, r);
+    }
+}
+
+#[test]
+fn delete() {
+    let mut map = PersistentMap::new();
+    for v in 0..200 {
+        map = map.insert(v, v);
+    }
+    let full = map.snapshot();
+    for v in (0..200).rev() {
+        map = map.delete(&v);
+        assert!(!map.contains(&v));
+        assert_eq!(v as usize, map.len());
+        assert!(map.is_valid());
+    }
+    assert!(map.is_empty());
+
+    // delete not exist
+    let same = full.delete(&1000);
+    assert_eq!(200, same.len());
+    assert_eq!(200, full.len());
+    assert_eq!(
+        (0..200).collect::<Vec<i32>>(),
+        full.iter().map(|(k, _)| *k).collect::<Vec<i32>>()
+    );
+}
+
+#[test]
+fn drop_versions() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut map = PersistentMap::new();
+        for v in 0..100 {
+            map = map.insert(v, Elem);
+        }
+        let old = map.snapshot();
+        for v in 0..10 {
+            map = map.delete(&v);
+        }
+        // shared by the old version
+        assert_eq!(0, ctx.get());
+        drop(old);
+        assert_eq!(10, ctx.get());
+
+        drop(map);
+        assert_eq!(100, ctx.get());
+    });
+}
@@ -11,3 +11,81 @@ fn rb_tree_height() {
     // 证明：含有n个节点的红黑树，其高度不会超过 2 * lg(n + 1)
     assert!(tree.height() as f32 <= 2.0 * 100.0f32.log2())
 }
+
+#[test]
+fn rb_tree_repeat_insert() {
+    use algo::tree::binary::rb::RedBlackTree;
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        tree.insert(v, v);
+    }
+    tree.insert(0, 100);
+    assert_eq!(10, tree.size());
+    assert_eq!(Some(&100), tree.get(&0));
+    assert!(tree.is_valid());
+}
+
+#[test]
+fn rb_tree_get_min_max() {
+    use algo::tree::binary::rb::RedBlackTree;
+    let mut tree = Tree::default();
+    assert_eq!(None, tree.min());
+    assert_eq!(None, tree.max());
+
+    let data = [4, 3, 8, 1, 7, 16, 2, 10, 9, 14];
+    for v in data {
+        tree.insert(v, v);
+    }
+    for v in data {
+        assert_eq!(Some(&v), tree.get(&v));
+    }
+    assert_eq!(None, tree.get(&100));
+    assert_eq!(Some(&1), tree.min());
+    assert_eq!(Some(&16), tree.max());
+}
+
+#[test]
+fn rb_tree_delete() {
+    use algo::common::util;
+    use algo::tree::binary::rb::RedBlackTree;
+    let mut tree = Tree::default();
+    let mut data: Vec<i32> = (0..1000).collect();
+    util::shuffle(&mut data);
+    for v in &data {
+        tree.insert(*v, *v);
+        assert!(tree.is_valid());
+    }
+
+    util::shuffle(&mut data);
+    for (i, v) in data.iter().enumerate() {
+        tree.delete(v);
+        assert_eq!(None, tree.get(v));
+        assert_eq!(data.len() - i - 1, tree.size());
+        assert!(tree.is_valid());
+    }
+    assert!(tree.is_empty());
+
+    // 删除一个不存在的
+    tree.delete(&10000);
+    assert_eq!(0, tree.size());
+}
+
+#[test]
+fn rb_tree_drop_with_delete() {
+    use algo::common::drop::{self, Elem};
+    use algo::tree::binary::rb::RedBlackTree;
+    drop::with(|ctx| {
+        let mut tree = Tree::default();
+        for v in 0..100 {
+            tree.insert(v, Elem);
+        }
+
+        for v in 0..10 {
+            tree.delete(&v);
+        }
+        assert_eq!(10, ctx.get());
+
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
@@ -1,6 +1,8 @@
-use algo::tree::binary::rb2::RedBlackTreeV2;
+use algo::tree::binary::rb2::{self, RedBlackTreeV2};
 use algo::tree::binary::traverse::{InOrderVisitor, PreOrderVisitor};
 use algo::tree::binary::Tree;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeSet;
 
 #[test]
 fn height() {
@@ -151,3 +153,252 @@ fn drop_with_delete() {
         assert_eq!(100, ctx.get());
     });
 }
+
+#[test]
+fn get_mut_entry_remove() {
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        tree.insert(v, v);
+    }
+    *tree.get_mut(&7).unwrap() += 100;
+    assert_eq!(Some(&107), tree.get(&7));
+    assert_eq!(None, tree.get_mut(&100));
+
+    // count
+    let mut counter = Tree::default();
+    for c in "SEARCHEXAMPLE".chars() {
+        *counter.entry(c).or_insert(0) += 1;
+    }
+    assert_eq!(10, counter.size());
+    assert_eq!(Some(&3), counter.get(&'E'));
+    assert_eq!(Some(&2), counter.get(&'A'));
+    assert_eq!(Some(&1), counter.get(&'X'));
+
+    for v in 90..110 {
+        tree.entry(v).and_modify(|v| *v = 0).or_insert_with(|| 1);
+    }
+    assert_eq!(110, tree.size());
+    assert_eq!(Some(&0), tree.get(&99));
+    assert_eq!(Some(&1), tree.get(&100));
+    let keys: Vec<i32> = tree.keys().into_iter().copied().collect();
+    assert_eq!((0..110).collect::<Vec<i32>>(), keys);
+
+    assert_eq!(Some(107), tree.remove(&7));
+    for v in (0..50).filter(|&v| v != 7) {
+        assert_eq!(Some(v), tree.remove(&v));
+        assert_eq!(None, tree.remove(&v));
+    }
+    assert_eq!(60, tree.size());
+    assert_eq!(Some(&50), tree.min());
+}
+
+#[test]
+fn drop_with_remove() {
+    use algo::common::drop::{self, Elem};
+    drop::with(|ctx| {
+        let mut tree = Tree::default();
+        for v in 0..100 {
+            tree.insert(v.to_string(), Elem);
+        }
+        let removed: Vec<Elem> = (0..10)
+            .filter_map(|v| tree.remove(&v.to_string()))
+            .collect();
+        assert_eq!(10, removed.len());
+        assert_eq!(0, ctx.get());
+        drop(removed);
+        assert_eq!(10, ctx.get());
+
+        drop(tree);
+        assert_eq!(100, ctx.get());
+    });
+}
+
+fn build(keys: impl Iterator<Item = i32>) -> Tree<i32, i32> {
+    let mut tree = Tree::default();
+    for v in keys {
+        tree.insert(v, v);
+    }
+    tree
+}
+
+fn keys_of(tree: &Tree<i32, i32>) -> Vec<i32> {
+    tree.keys().into_iter().copied().collect()
+}
+
+#[test]
+fn split() {
+    for n in [0, 1, 2, 3, 10, 100, 257] {
+        for at in [-1, 0, 1, n / 3, n / 2, n - 1, n, n + 5] {
+            let tree = build(0..n);
+            let (l, r) = tree.split(&at);
+            assert!(rb2::is_llrb(l.root), "n = {}, at = {}", n, at);
+            assert!(rb2::is_llrb(r.root), "n = {}, at = {}", n, at);
+            let at = at.clamp(0, n);
+            assert_eq!((0..at).collect::<Vec<i32>>(), keys_of(&l));
+            assert_eq!((at..n).collect::<Vec<i32>>(), keys_of(&r));
+            assert_eq!(at as usize, l.size());
+            assert_eq!((n - at) as usize, r.size());
+        }
+    }
+
+    // key not in tree
+    let tree = build((0..100).map(|v| v * 2));
+    let (l, r) = tree.split(&51);
+    assert_eq!(Some(&50), l.max());
+    assert_eq!(Some(&52), r.min());
+}
+
+#[test]
+fn join() {
+    for (a, b) in [
+        (0, 0),
+        (0, 5),
+        (5, 0),
+        (1, 1),
+        (1, 100),
+        (100, 1),
+        (37, 200),
+        (200, 37),
+    ] {
+        let left = build(0..a);
+        let right = build(a..a + b);
+        let tree = Tree::join(left, right);
+        assert!(rb2::is_llrb(tree.root), "a = {}, b = {}", a, b);
+        assert_eq!((0..a + b).collect::<Vec<i32>>(), keys_of(&tree));
+        assert_eq!((a + b) as usize, tree.size());
+    }
+
+    // split then join back
+    let tree = build(0..500);
+    let (l, r) = tree.split(&123);
+    let tree = Tree::join(l, r);
+    assert!(rb2::is_llrb(tree.root));
+    assert_eq!((0..500).collect::<Vec<i32>>(), keys_of(&tree));
+}
+
+#[test]
+#[should_panic]
+fn join_overlap() {
+    let _ = Tree::join(build(0..10), build(5..15));
+}
+
+#[test]
+fn range_extraction() {
+    let tree = build(0..1000);
+    let (lo, rest) = tree.split(&100);
+    let (mid, hi) = rest.split(&200);
+    assert_eq!((100..200).collect::<Vec<i32>>(), keys_of(&mid));
+    let tree = Tree::join(lo, hi);
+    assert!(rb2::is_llrb(tree.root));
+    assert_eq!(900, tree.size());
+    assert_eq!(None, tree.get(&150));
+}
+
+#[test]
+fn set_operations() {
+    let a = || build((0..300).filter(|v| v % 2 == 0));
+    let b = || {
+        let mut tree = Tree::default();
+        for v in (0..300).filter(|v| v % 3 == 0) {
+            tree.insert(v, -v);
+        }
+        tree
+    };
+
+    let union = a().union(b());
+    assert!(rb2::is_llrb(union.root));
+    let expect: Vec<i32> = (0..300).filter(|v| v % 2 == 0 || v % 3 == 0).collect();
+    assert_eq!(expect, keys_of(&union));
+    assert_eq!(expect.len(), union.size());
+    // values of self are kept
+    assert_eq!(Some(&6), union.get(&6));
+    assert_eq!(Some(&-9), union.get(&9));
+
+    let inter = a().intersection(b());
+    assert!(rb2::is_llrb(inter.root));
+    let expect: Vec<i32> = (0..300).filter(|v| v % 6 == 0).collect();
+    assert_eq!(expect, keys_of(&inter));
+    assert_eq!(Some(&6), inter.get(&6));
+
+    let diff = a().difference(b());
+    assert!(rb2::is_llrb(diff.root));
+    let expect: Vec<i32> = (0..300).filter(|v| v % 2 == 0 && v % 3 != 0).collect();
+    assert_eq!(expect, keys_of(&diff));
+    assert_eq!(expect.len(), diff.size());
+
+    let empty = Tree::default();
+    assert_eq!(150, a().union(Tree::default()).size());
+    assert!(a().intersection(Tree::default()).is_empty());
+    assert_eq!(150, a().difference(empty).size());
+    assert!(Tree::default().difference(a()).is_empty());
+}
+
+#[test]
+fn drop_set_operations() {
+    use algo::common::drop::{self, Elem};
+    let build = |keys: std::ops::Range<i32>| {
+        let mut tree = Tree::default();
+        for v in keys {
+            tree.insert(v, Elem);
+        }
+        tree
+    };
+    drop::with(|ctx| {
+        let tree = build(0..100).union(build(50..150));
+        assert_eq!(50, ctx.get());
+        drop(tree);
+        assert_eq!(200, ctx.get());
+    });
+    drop::with(|ctx| {
+        let tree = build(0..100).intersection(build(50..150));
+        assert_eq!(150, ctx.get());
+        drop(tree);
+        assert_eq!(200, ctx.get());
+    });
+    drop::with(|ctx| {
+        let tree = build(0..100).difference(build(50..150));
+        assert_eq!(150, ctx.get());
+        drop(tree);
+        assert_eq!(200, ctx.get());
+    });
+}
+
+#[test]
+fn set_operations_random() {
+    // random sets of different sizes, the black heights carried through
+    // split and join are checked by debug assertions
+    let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+    for _ in 0..50 {
+        let a: BTreeSet<i32> = (0..rng.gen_range(0..300))
+            .map(|_| rng.gen_range(0..500))
+            .collect();
+        let b: BTreeSet<i32> = (0..rng.gen_range(0..300))
+            .map(|_| rng.gen_range(0..500))
+            .collect();
+        let ta = || build(a.iter().copied());
+        let tb = || build(b.iter().copied());
+
+        let union = ta().union(tb());
+        assert!(rb2::is_llrb(union.root));
+        assert_eq!(a.union(&b).copied().collect::<Vec<_>>(), keys_of(&union));
+        let inter = ta().intersection(tb());
+        assert!(rb2::is_llrb(inter.root));
+        assert_eq!(
+            a.intersection(&b).copied().collect::<Vec<_>>(),
+            keys_of(&inter)
+        );
+        let diff = ta().difference(tb());
+        assert!(rb2::is_llrb(diff.root));
+        assert_eq!(
+            a.difference(&b).copied().collect::<Vec<_>>(),
+            keys_of(&diff)
+        );
+
+        let at = rng.gen_range(-1..501);
+        let (l, r) = ta().split(&at);
+        assert!(rb2::is_llrb(l.root) && rb2::is_llrb(r.root));
+        let joined = Tree::join(l, r);
+        assert!(rb2::is_llrb(joined.root));
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), keys_of(&joined));
+    }
+}
@@ -0,0 +1,165 @@
+use algo::tree::binary::rope::Rope;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn empty() {
+    let rope = Rope::new();
+    assert!(rope.is_empty());
+    assert_eq!(0, rope.len());
+    assert_eq!(1, rope.len_lines());
+    assert_eq!(None, rope.char_at(0));
+    assert_eq!("", rope.to_string());
+    assert_eq!(0, rope.line_of(0));
+    assert_eq!(0, rope.line_start(0));
+    assert!(rope.check().is_ok());
+}
+
+#[test]
+fn insert_delete() {
+    let mut rope = Rope::from("hello world");
+    rope.insert(5, ",");
+    rope.insert(12, "!");
+    assert_eq!("hello, world!", rope.to_string());
+    rope.delete(0..7);
+    assert_eq!("world!", rope.to_string());
+    rope.insert(0, "");
+    rope.delete(3..3);
+    assert_eq!(Some('w'), rope.char_at(0));
+    assert_eq!(Some('!'), rope.char_at(5));
+    assert_eq!(None, rope.char_at(6));
+    rope.delete(0..6);
+    assert!(rope.is_empty());
+    assert!(rope.check().is_ok());
+}
+
+#[test]
+fn multibyte() {
+    let mut rope = Rope::from("héllo, 世界");
+    assert_eq!(9, rope.len());
+    assert_eq!(14, rope.len_bytes());
+    assert_eq!(Some('世'), rope.char_at(7));
+    rope.insert(8, "🦀");
+    assert_eq!("héllo, 世🦀界", rope.to_string());
+    rope.delete(1..2);
+    assert_eq!("hllo, 世🦀界", rope.to_string());
+    assert_eq!("世🦀", rope.slice(6..8));
+}
+
+#[test]
+fn split_concat() {
+    let text: String = (0..5000).map(|i| format!("{} ", i)).collect();
+    let rope = Rope::from(text.as_str());
+    assert!(rope.chunks().count() > 1);
+    for &at in [0, 1, 1023, 1024, 2500, text.len() - 1, text.len()].iter() {
+        let rope = Rope::from(text.as_str());
+        let (l, r) = rope.split(at);
+        assert!(l.check().is_ok());
+        assert!(r.check().is_ok());
+        assert_eq!(&text[..at], l.to_string());
+        assert_eq!(&text[at..], r.to_string());
+        let rope = Rope::concat(l, r);
+        assert!(rope.check().is_ok());
+        assert_eq!(text, rope.to_string());
+    }
+
+    // ropes of different heights
+    let small = Rope::from("ab");
+    let big = Rope::from(text.as_str());
+    let rope = Rope::concat(small, big);
+    assert!(rope.check().is_ok());
+    assert_eq!(format!("ab{}", text), rope.to_string());
+}
+
+#[test]
+fn lines() {
+    let rope = Rope::from("one\ntwo\n\nfour");
+    assert_eq!(4, rope.len_lines());
+    assert_eq!("one\n", rope.line(0));
+    assert_eq!("two\n", rope.line(1));
+    assert_eq!("\n", rope.line(2));
+    assert_eq!("four", rope.line(3));
+    assert_eq!(0, rope.line_of(3));
+    assert_eq!(1, rope.line_of(4));
+    assert_eq!(2, rope.line_of(8));
+    assert_eq!(3, rope.line_of(9));
+    assert_eq!(3, rope.line_of(rope.len()));
+    assert_eq!(9, rope.line_start(3));
+}
+
+#[test]
+#[should_panic(expected = "line 2 out of bounds, 2 lines")]
+fn line_out_of_bounds() {
+    Rope::from("a\nb").line_start(2);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds, len is 3")]
+fn insert_out_of_bounds() {
+    Rope::from("abc").insert(4, "d");
+}
+
+#[test]
+fn large_lines() {
+    let text: String = (0..20000).map(|i| format!("line {}\n", i)).collect();
+    let rope = Rope::from(text.as_str());
+    assert_eq!(20001, rope.len_lines());
+    let n = rope.chunks().count() as f64;
+    assert!(rope.height() as f64 <= 2.0 * (n + 1.0).log2());
+    for &i in [0, 1, 999, 12345, 19999].iter() {
+        assert_eq!(format!("line {}\n", i), rope.line(i));
+        let start = rope.line_start(i);
+        assert_eq!(i, rope.line_of(start));
+        assert_eq!(i, rope.line_of(start + 5));
+    }
+    assert_eq!("", rope.line(20000));
+}
+
+#[test]
+fn build_chunks() {
+    // every number of chunks up to a few levels of 2-3 trees
+    for n in 0..100 {
+        let text = "x".repeat(1024 * n);
+        let rope = Rope::from(text.as_str());
+        assert_eq!(n, rope.chunks().count());
+        assert_eq!(Ok(()), rope.check());
+        assert_eq!(text, rope.to_string());
+    }
+}
+
+#[test]
+fn random_edits() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+    let words = ["a", "bc", "\n", "é世", "🦀\n", "xyz"];
+    let mut rope = Rope::new();
+    let mut model: Vec<char> = Vec::new();
+    for _ in 0..3000 {
+        if model.is_empty() || rng.gen_bool(0.6) {
+            let i = rng.gen_range(0..=model.len());
+            let n = if rng.gen_bool(0.05) { 400 } else { 1 };
+            let text: String = (0..n)
+                .map(|_| words[rng.gen_range(0..words.len())])
+                .collect();
+            rope.insert(i, &text);
+            model.splice(i..i, text.chars());
+        } else {
+            let i = rng.gen_range(0..model.len());
+            let j = rng.gen_range(i..=model.len().min(i + 2000));
+            rope.delete(i..j);
+            model.drain(i..j);
+        }
+        assert_eq!(model.len(), rope.len());
+    }
+    assert!(rope.check().is_ok());
+
+    let text: String = model.iter().collect();
+    assert_eq!(text, rope.to_string());
+    assert_eq!(text.len(), rope.len_bytes());
+    assert!(rope.chars().eq(model.iter().cloned()));
+    for _ in 0..200 {
+        let i = rng.gen_range(0..model.len());
+        assert_eq!(Some(model[i]), rope.char_at(i));
+        let line = model[..i].iter().filter(|&&c| c == '\n').count();
+        assert_eq!(line, rope.line_of(i));
+        assert!(rope.line_start(line) <= i);
+    }
+}
@@ -16,3 +16,81 @@ fn binary_search() {
         assert_eq!(search::binary::search(&xs, v), p);
     }
 }
+
+#[test]
+fn separate_chaining_hash_st() {
+    use algo::search::SeparateChainingHashST;
+    let mut st = SeparateChainingHashST::default();
+    for (i, c) in "SEARCHEXAMPLE".chars().enumerate() {
+        st.put(c, i);
+    }
+    assert_eq!(10, st.len());
+    assert_eq!(Some(&12), st.get(&'E'));
+    assert_eq!(Some(&8), st.get(&'A'));
+    assert_eq!(None, st.get(&'Z'));
+    let mut keys: Vec<char> = st.keys().into_iter().cloned().collect();
+    keys.sort_unstable();
+    assert_eq!("ACEHLMPRSX", keys.iter().collect::<String>());
+
+    // resize keeps average list length between 2 and 10
+    let mut st = SeparateChainingHashST::default();
+    for v in 0..1000 {
+        st.put(v, v);
+        assert!(st.load_factor() <= 10.0);
+    }
+    for v in 0..1000 {
+        assert_eq!(Some(&v), st.get(&v));
+    }
+    for v in 0..1000 {
+        st.delete(&v);
+        assert!(!st.contains(&v));
+        assert_eq!(1000 - v - 1, st.len());
+        // never shrink below the initial 4 chains
+        assert!(st.len() <= 8 || st.load_factor() > 2.0);
+    }
+    assert!(st.is_empty());
+}
+
+#[test]
+fn linear_probing_hash_st() {
+    use algo::search::LinearProbingHashST;
+    let mut st = LinearProbingHashST::default();
+    for (i, c) in "SEARCHEXAMPLE".chars().enumerate() {
+        st.put(c, i);
+    }
+    assert_eq!(10, st.len());
+    assert_eq!(Some(&12), st.get(&'E'));
+    assert_eq!(Some(&8), st.get(&'A'));
+    assert_eq!(None, st.get(&'Z'));
+    let mut keys: Vec<char> = st.keys().into_iter().cloned().collect();
+    keys.sort_unstable();
+    assert_eq!("ACEHLMPRSX", keys.iter().collect::<String>());
+
+    // resize keeps the table between 1/8 and 1/2 full
+    let mut st = LinearProbingHashST::default();
+    for v in 0..1000 {
+        st.put(v, v);
+        assert!(st.load_factor() <= 0.5);
+    }
+    for v in 0..1000 {
+        assert_eq!(Some(&v), st.get(&v));
+    }
+    // delete must keep the rest of the cluster reachable
+    for v in (0..1000).step_by(2) {
+        st.delete(&v);
+        assert!(!st.contains(&v));
+    }
+    assert_eq!(500, st.len());
+    for v in (1..1000).step_by(2) {
+        assert_eq!(Some(&v), st.get(&v));
+    }
+    for v in (1..1000).step_by(2) {
+        st.delete(&v);
+        assert!(st.is_empty() || st.load_factor() > 0.125);
+    }
+    assert!(st.is_empty());
+
+    // delete not exist
+    st.delete(&10000);
+    assert!(st.is_empty());
+}
@@ -1,11 +1,13 @@
 #[macro_use]
 extern crate approx;
 
+use algo::common::{IndexFibonacciHeap, IndexPQ, IndexPairingHeap};
 use algo::graph::directed::{DepthFirstOrders, EdgeWeightedDirectedCycle};
 use algo::graph::shortest::{
     AcyclicLP, AcyclicSP, Arbitrage, BellmanFordSP, DijkstraAllPairsSP, DijkstraSP, EWDigraph, CPM,
 };
 use algo::graph::IEWDigraph;
+use rand::{Rng, SeedableRng};
 
 const TINY_EWD: &'static str = include_str!("../res/graph/tinyEWD.txt");
 const TINY_EWD_NEGATIVE: &'static str = include_str!("../res/graph/tinyEWDn.txt");
@@ -76,6 +78,41 @@ fn Dijkstra_sp() {
     assert!(sp.check(graph.as_ref(), 0).is_ok());
 }
 
+#[allow(non_snake_case)]
+#[test]
+fn Dijkstra_sp_heaps() {
+    let graph = create_graph(TINY_EWD);
+    let sp = DijkstraSP::new(graph.as_ref(), 0);
+    let pairing = DijkstraSP::with_pq::<IndexPairingHeap<f32>>(graph.as_ref(), 0);
+    let fibonacci = DijkstraSP::with_pq::<IndexFibonacciHeap<f32>>(graph.as_ref(), 0);
+    for v in 0..graph.V() {
+        assert_eq!(sp.dist_to(v), pairing.dist_to(v));
+        assert_eq!(sp.dist_to(v), fibonacci.dist_to(v));
+    }
+    assert!(pairing.check(graph.as_ref(), 0).is_ok());
+    assert!(fibonacci.check(graph.as_ref(), 0).is_ok());
+
+    // dense graph, many decrease_key
+    let n = 60;
+    let mut graph = EWDigraph::from(n);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    for v in 0..n {
+        for w in 0..n {
+            if v != w {
+                graph.add_edge(v, w, rng.gen());
+            }
+        }
+    }
+    let sp = DijkstraSP::with_pq::<IndexPQ<f32>>(&graph, 3);
+    let pairing = DijkstraSP::with_pq::<IndexPairingHeap<f32>>(&graph, 3);
+    let fibonacci = DijkstraSP::with_pq::<IndexFibonacciHeap<f32>>(&graph, 3);
+    for v in 0..n {
+        assert_eq!(sp.dist_to(v), pairing.dist_to(v));
+        assert_eq!(sp.dist_to(v), fibonacci.dist_to(v));
+    }
+    assert!(fibonacci.check(&graph, 3).is_ok());
+}
+
 #[allow(non_snake_case)]
 #[test]
 fn Dijkstra_all_pairs_sp() {
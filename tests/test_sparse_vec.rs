@@ -66,3 +66,18 @@ fn create() -> (SparseVector, SparseVector) {
 
     (a, b)
 }
+
+#[test]
+fn cancel() {
+    let (a, _) = create();
+    let c = a.clone() - a;
+    assert_eq!(0, c.nnz());
+
+    let mut a = SparseVector::new(10);
+    a.put(3, 0.5);
+    a.put(3, 0.25);
+    assert_eq!(1, a.nnz());
+    assert_relative_eq!(0.25, a.get(3));
+    a.put(3, 0.0);
+    assert_eq!(0, a.nnz());
+}
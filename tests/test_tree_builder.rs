@@ -0,0 +1,148 @@
+use algo::tree::binary::builder::level::BuildTreeInLevel;
+use algo::tree::binary::builder::order::BuildTreeInOrder;
+use algo::tree::binary::builder::TreeBuilder;
+use algo::tree::binary::check::Balance;
+use algo::tree::binary::traverse::{InOrderVisitor, PostOrderVisitor, PreOrderVisitor};
+use algo::tree::binary::Tree;
+
+const LEVELS: [&[&str]; 7] = [
+    &[],
+    &["1"],
+    &["1", "#", "2", "3"],
+    &["1", "2"],
+    &["3", "9", "20", "#", "#", "15", "7"],
+    &["1", "2", "#", "3", "4", "5"],
+    &["1", "2", "#", "3", "#", "#", "#", "#", "4"],
+];
+
+#[test]
+fn to_level() {
+    for level in LEVELS {
+        let tree: Tree<usize, usize> = TreeBuilder::build_in_level(level);
+        assert_eq!(level.to_vec(), tree.to_level());
+    }
+
+    // trailing "#" and the children of "#" are omitted
+    for (level, expect) in [
+        (vec!["1", "#", "2", "#", "#"], vec!["1", "#", "2"]),
+        (
+            vec!["1", "2", "#", "3", "#", "#", "#", "4"],
+            vec!["1", "2", "#", "3", "#", "4"],
+        ),
+        (vec!["#"], vec![]),
+    ] {
+        let tree: Tree<usize, usize> = TreeBuilder::build_in_level(level.as_slice());
+        assert_eq!(expect, tree.to_level());
+    }
+}
+
+#[test]
+fn build_from_order() {
+    for level in LEVELS {
+        let tree: Tree<usize, usize> = TreeBuilder::build_in_level(level);
+        let pre = unsafe { PreOrderVisitor::iterate(&tree) };
+        let inorder = unsafe { InOrderVisitor::iterate(&tree) };
+        let post = unsafe { PostOrderVisitor::iterate(&tree) };
+
+        let t1: Tree<usize, usize> = TreeBuilder::build_from_pre_in(&pre, &inorder).unwrap();
+        let t2: Tree<usize, usize> = TreeBuilder::build_from_post_in(&post, &inorder).unwrap();
+        for t in [t1, t2] {
+            assert_eq!(level.to_vec(), t.to_level());
+            assert_eq!(tree.size(), t.size());
+        }
+    }
+
+    // parent links and sizes
+    let mut tree = Tree::default();
+    for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+        algo::tree::binary::bst::BSTree::insert(&mut tree, v, v);
+    }
+    let pre = unsafe { PreOrderVisitor::iterate(&tree) };
+    let inorder = unsafe { InOrderVisitor::iterate(&tree) };
+    let post = unsafe { PostOrderVisitor::iterate(&tree) };
+    let t1: Tree<i32, i32> = TreeBuilder::build_from_pre_in(&pre, &inorder).unwrap();
+    let t2: Tree<i32, i32> = TreeBuilder::build_from_post_in(&post, &inorder).unwrap();
+    for t in [t1, t2] {
+        assert_eq!(Ok(()), t.check(Balance::None));
+        assert_eq!(tree.to_level(), t.to_level());
+    }
+
+    let tree: Tree<i32, i32> =
+        TreeBuilder::build_from_pre_in(&[3, 9, 20, 15, 7], &[9, 3, 15, 20, 7]).unwrap();
+    assert_eq!(vec!["3", "9", "20", "#", "#", "15", "7"], tree.to_level());
+}
+
+#[test]
+fn build_from_order_error() {
+    let build = |pre: &[i32], inorder: &[i32]| -> Result<Tree<i32, i32>, String> {
+        TreeBuilder::build_from_pre_in(pre, inorder)
+    };
+    assert_eq!(
+        Some("lengths of sequences differ, 2 and 1".to_string()),
+        build(&[1, 2], &[1]).err()
+    );
+    assert_eq!(
+        Some("key 1 is duplicated".to_string()),
+        build(&[1, 1], &[1, 1]).err()
+    );
+    assert_eq!(
+        Some("key 3 is not in inorder".to_string()),
+        build(&[1, 3], &[1, 2]).err()
+    );
+    // 2 follows 1 in preorder, it is the root of the left subtree of 1,
+    // but it is right of 1 in inorder
+    assert_eq!(
+        Some("key 2 is out of its subtree".to_string()),
+        build(&[1, 2, 3], &[3, 1, 2]).err()
+    );
+}
+
+#[test]
+fn encode_decode() {
+    for level in LEVELS {
+        let tree: Tree<usize, usize> = TreeBuilder::build_in_level(level);
+        let buf = tree.encode();
+        let t: Tree<usize, usize> = Tree::decode(&buf).unwrap();
+        assert_eq!(level.to_vec(), t.to_level());
+        assert_eq!(tree.size(), t.size());
+    }
+    assert!(Tree::<i32, i32>::default().encode().is_empty());
+
+    // colors are kept
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let mut tree = Tree::default();
+    for v in 0..1000 {
+        tree.insert(v, v);
+    }
+    let t: Tree<i32, i32> = Tree::decode(&tree.encode()).unwrap();
+    assert_eq!(Ok(()), t.check(Balance::LeftLeaningRedBlack));
+    assert_eq!(tree.to_level(), t.to_level());
+
+    // long keys
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        algo::tree::binary::bst::BSTree::insert(&mut tree, "x".repeat(v * 50), v);
+    }
+    let t: Tree<String, usize> = Tree::decode(&tree.encode()).unwrap();
+    assert_eq!(tree.to_level(), t.to_level());
+}
+
+#[test]
+fn decode_error() {
+    let tree: Tree<i32, i32> = TreeBuilder::build_in_level(&["1", "#", "2", "3"]);
+    let buf = tree.encode();
+    for n in 1..buf.len() {
+        assert!(Tree::<i32, i32>::decode(&buf[..n]).is_err());
+    }
+    let mut long = buf.clone();
+    long.push(0);
+    assert_eq!(
+        Some("1 trailing bytes".to_string()),
+        Tree::<i32, i32>::decode(&long).err()
+    );
+    assert_eq!(
+        Some("bad key \"a\"".to_string()),
+        Tree::<i32, i32>::decode(&[0, 1, b'a']).err()
+    );
+    assert!(Tree::<i32, i32>::decode(&[0x80, 1, b'1']).is_err());
+}
@@ -0,0 +1,183 @@
+use algo::tree::binary::builder::level::BuildTreeInLevel;
+use algo::tree::binary::builder::TreeBuilder;
+use algo::tree::binary::check::Balance;
+use algo::tree::binary::Tree;
+
+const DATA: [i32; 10] = [4, 3, 8, 1, 7, 16, 2, 10, 9, 14];
+
+fn build_bst() -> Tree<i32, i32> {
+    use algo::tree::binary::bst::BSTree;
+    let mut tree = Tree::default();
+    for v in DATA {
+        tree.insert(v, v);
+    }
+    tree
+}
+
+#[test]
+fn valid_trees() {
+    let tree = build_bst();
+    assert_eq!(Ok(()), tree.check(Balance::None));
+
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        algo::tree::binary::rb::RedBlackTree::insert(&mut tree, v, v);
+    }
+    assert_eq!(Ok(()), tree.check(Balance::RedBlack));
+
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        algo::tree::binary::rb2::RedBlackTreeV2::insert(&mut tree, v, v);
+    }
+    assert_eq!(Ok(()), tree.check(Balance::RedBlack));
+    assert_eq!(Ok(()), tree.check(Balance::LeftLeaningRedBlack));
+
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        algo::tree::binary::avl::AvlTree::insert(&mut tree, v, v);
+    }
+    assert_eq!(Ok(()), tree.check(Balance::Avl));
+
+    let tree: Tree<i32, i32> = Tree::default();
+    assert_eq!(Ok(()), tree.check(Balance::RedBlack));
+}
+
+#[test]
+fn symmetric_order() {
+    //   1
+    //  / \
+    // 2   3
+    let tree: Tree<i32, i32> = TreeBuilder::build_in_level(&["1", "2", "3"]);
+    assert_eq!(
+        Err("key 2 violates symmetric order".to_string()),
+        tree.check(Balance::None)
+    );
+
+    // 7 is in the right subtree of 4
+    let tree = build_bst();
+    unsafe {
+        let mut x = tree.root.unwrap().as_ref().right.unwrap();
+        x.as_mut().left.unwrap().as_mut().key = 2;
+    }
+    assert_eq!(
+        Err("key 2 violates symmetric order".to_string()),
+        tree.check(Balance::None)
+    );
+}
+
+#[test]
+fn parent_link() {
+    let tree = build_bst();
+    unsafe {
+        let mut x = tree.root.unwrap().as_ref().left.unwrap();
+        x.as_mut().parent = None;
+    }
+    assert_eq!(
+        Err("parent link of 3 does not point to 4".to_string()),
+        tree.check(Balance::None)
+    );
+
+    let tree = build_bst();
+    unsafe {
+        let root = tree.root;
+        let mut x = root.unwrap().as_ref().left.unwrap();
+        x.as_mut().left.unwrap().as_mut().parent = root;
+    }
+    assert_eq!(
+        Err("parent link of 1 does not point to 3".to_string()),
+        tree.check(Balance::None)
+    );
+}
+
+#[test]
+fn size() {
+    let tree = build_bst();
+    unsafe {
+        let mut x = tree.root.unwrap().as_ref().right.unwrap();
+        x.as_mut().size = 3;
+    }
+    assert_eq!(
+        Err("size of 8 is 3, expected 6".to_string()),
+        tree.check(Balance::None)
+    );
+}
+
+#[test]
+fn red_black() {
+    use algo::tree::binary::rb::RedBlackTree;
+
+    // rb tree allows right-leaning red links
+    let mut tree = Tree::default();
+    for v in [1, 2] {
+        tree.insert(v, v);
+    }
+    assert_eq!(Ok(()), tree.check(Balance::RedBlack));
+    assert_eq!(
+        Err("red link of 1 leans right".to_string()),
+        tree.check(Balance::LeftLeaningRedBlack)
+    );
+
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        tree.insert(v, v);
+    }
+    unsafe {
+        let mut root = tree.root.unwrap();
+        root.as_mut().color = tree.root.unwrap().as_ref().color.flip();
+        let key = root.as_ref().key;
+        assert_eq!(
+            Err(format!("root {} is red", key)),
+            tree.check(Balance::RedBlack)
+        );
+    }
+
+    // make a black leaf red
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        tree.insert(v, v);
+    }
+    unsafe {
+        let mut x = tree.root.unwrap();
+        while let Some(l) = x.as_ref().left {
+            x = l;
+        }
+        // 0 is black and its parent is black
+        x.as_mut().color = x.as_ref().color.flip();
+    }
+    let err = tree.check(Balance::RedBlack).unwrap_err();
+    assert!(err.starts_with("black heights of 1 differ"), "{}", err);
+}
+
+#[test]
+fn avl() {
+    use algo::tree::binary::avl::AvlTree;
+    let mut tree = Tree::default();
+    for v in 0..10 {
+        tree.insert(v, v);
+    }
+    unsafe {
+        tree.root.unwrap().as_mut().delta = 0;
+    }
+    let key = unsafe { tree.root.unwrap().as_ref().key };
+    assert_eq!(
+        Err(format!("δ of {} is 0, expected 1", key)),
+        tree.check(Balance::Avl)
+    );
+
+    // a BST built by sorted keys is a linked list
+    let mut tree = Tree::default();
+    for v in 0..3 {
+        algo::tree::binary::bst::BSTree::insert(&mut tree, v, v);
+    }
+    assert_eq!(
+        Err("δ of 1 is 0, expected 1".to_string()),
+        tree.check(Balance::Avl)
+    );
+    unsafe {
+        tree.root.unwrap().as_ref().right.unwrap().as_mut().delta = 1;
+    }
+    assert_eq!(
+        Err("0 is unbalanced, δ = 2".to_string()),
+        tree.check(Balance::Avl)
+    );
+}
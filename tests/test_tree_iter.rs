@@ -0,0 +1,112 @@
+use algo::tree::binary::builder::level::BuildTreeInLevel;
+use algo::tree::binary::builder::TreeBuilder;
+use algo::tree::binary::rb2::RedBlackTreeV2;
+use algo::tree::binary::Tree;
+
+fn build(n: i32) -> Tree<i32, i32> {
+    let mut tree = Tree::default();
+    for v in 0..n {
+        tree.insert(v, v * 10);
+    }
+    tree
+}
+
+fn keys<'a>(it: impl Iterator<Item = (&'a i32, &'a i32)>) -> Vec<i32> {
+    it.map(|(k, _)| *k).collect()
+}
+
+#[test]
+fn iter() {
+    let tree = build(100);
+    let r: Vec<(i32, i32)> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+    let expect: Vec<(i32, i32)> = (0..100).map(|v| (v, v * 10)).collect();
+    assert_eq!(expect, r);
+
+    let mut count = 0;
+    for (k, v) in &tree {
+        assert_eq!(*k * 10, *v);
+        count += 1;
+    }
+    assert_eq!(100, count);
+
+    let tree: Tree<i32, i32> = Tree::default();
+    assert_eq!(None, tree.iter().next());
+}
+
+#[test]
+fn iter_rev() {
+    let tree = build(100);
+    let r: Vec<i32> = tree.iter().rev().map(|(k, _)| *k).collect();
+    let expect: Vec<i32> = (0..100).rev().collect();
+    assert_eq!(expect, r);
+}
+
+#[test]
+fn iter_both_ends() {
+    let tree = build(5);
+    let mut it = tree.iter();
+    assert_eq!(Some((&0, &0)), it.next());
+    assert_eq!(Some((&4, &40)), it.next_back());
+    assert_eq!(Some((&1, &10)), it.next());
+    assert_eq!(Some((&3, &30)), it.next_back());
+    assert_eq!(Some((&2, &20)), it.next());
+    assert_eq!(None, it.next_back());
+    assert_eq!(None, it.next());
+}
+
+#[test]
+fn iter_mut() {
+    let mut tree = build(100);
+    for (_, v) in tree.iter_mut() {
+        *v += 1;
+    }
+    for (k, v) in &mut tree {
+        assert_eq!(*k * 10 + 1, *v);
+    }
+    for v in 0..100 {
+        assert_eq!(Some(&(v * 10 + 1)), tree.get(&v));
+    }
+}
+
+#[test]
+fn range() {
+    let tree = build(100);
+
+    assert_eq!((10..20).collect::<Vec<i32>>(), keys(tree.range(10..20)));
+    assert_eq!((10..=20).collect::<Vec<i32>>(), keys(tree.range(10..=20)));
+    assert_eq!((0..5).collect::<Vec<i32>>(), keys(tree.range(..5)));
+    assert_eq!((95..100).collect::<Vec<i32>>(), keys(tree.range(95..)));
+    assert_eq!((0..100).collect::<Vec<i32>>(), keys(tree.range(..)));
+    assert_eq!(
+        (90..100).rev().collect::<Vec<i32>>(),
+        keys(tree.range(90..200).rev())
+    );
+    assert!(keys(tree.range(200..300)).is_empty());
+    let (lo, hi) = (20, 10);
+    assert!(keys(tree.range(lo..hi)).is_empty());
+
+    // keys not in tree
+    let mut tree = Tree::default();
+    for v in (0..100).step_by(10) {
+        tree.insert(v, v);
+    }
+    assert_eq!(vec![20, 30, 40], keys(tree.range(15..45)));
+    assert_eq!(vec![40, 30, 20], keys(tree.range(15..45).rev()));
+}
+
+#[test]
+fn range_mut() {
+    let mut tree = build(10);
+    for (_, v) in tree.range_mut(3..6) {
+        *v = 0;
+    }
+    let r: Vec<i32> = tree.iter().map(|(_, v)| *v).collect();
+    assert_eq!(vec![0, 10, 20, 0, 0, 0, 60, 70, 80, 90], r);
+}
+
+#[test]
+fn skip_no_value() {
+    // nodes built by TreeBuilder only have key
+    let tree: Tree<i32, i32> = TreeBuilder::build_in_level(&["1", "#", "2", "3"]);
+    assert_eq!(None, tree.iter().next());
+}
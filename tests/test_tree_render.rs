@@ -0,0 +1,115 @@
+use algo::tree::binary::builder::level::BuildTreeInLevel;
+use algo::tree::binary::builder::TreeBuilder;
+use algo::tree::binary::render;
+use algo::tree::binary::Tree;
+
+const LEVELS: [&[&str]; 6] = [
+    &[],
+    &["1"],
+    &["1", "#", "2", "3"],
+    &["3", "9", "20", "#", "#", "15", "7"],
+    &["1", "2", "#", "3", "4", "5"],
+    &["5", "4", "8", "11", "#", "13", "4", "7", "2", "5", "1"],
+];
+
+#[test]
+fn ascii() {
+    let tree: Tree<i32, i32> = TreeBuilder::build_in_level(&["1", "#", "2", "3"]);
+    let expect = r#"
+1
+   \
+    2
+   /
+  3
+"#;
+    assert_eq!(&expect[1..], tree.to_ascii());
+
+    let mut tree = Tree::default();
+    for v in [4, 3, 8, 1, 7, 16, 2, 10, 9, 14] {
+        algo::tree::binary::bst::BSTree::insert(&mut tree, v, v);
+    }
+    let expect = r#"
+         4
+        /     \
+       3       8
+  /           /           \
+ 1           7             16
+  \                    /
+   2                 10
+                    /  \
+                   9    14
+"#;
+    assert_eq!(&expect[1..], tree.to_ascii());
+
+    let tree: Tree<i32, i32> = Tree::default();
+    assert_eq!("", tree.to_ascii());
+}
+
+#[test]
+fn dot() {
+    use algo::tree::binary::rb::RedBlackTree;
+    let mut tree = Tree::default();
+    for v in [1, 2, 3] {
+        tree.insert(v, v);
+    }
+    let expect = r#"digraph {
+    node [shape=circle, style=filled, fontcolor=white];
+    n [label="2", fillcolor=black];
+    n -> nl;
+    n -> nr;
+    nl [label="1", fillcolor=red];
+    nl -> n [style=dashed, color=gray, constraint=false];
+    nr [label="3", fillcolor=red];
+    nr -> n [style=dashed, color=gray, constraint=false];
+}
+"#;
+    assert_eq!(expect, tree.to_dot());
+
+    // a single child leans to its side
+    let tree: Tree<i32, i32> = TreeBuilder::build_in_level(&["1", "#", "2"]);
+    assert!(tree.to_dot().contains("nl [label=\"#\", shape=point];"));
+}
+
+#[test]
+fn level_round_trip() {
+    for level in LEVELS {
+        let tree: Tree<i32, i32> = TreeBuilder::build_in_level(level);
+        let expect: Vec<String> = level.iter().map(|s| s.to_string()).collect();
+        assert_eq!(Ok(expect.clone()), render::ascii_to_level(&tree.to_ascii()));
+        assert_eq!(Ok(expect), render::dot_to_level(&tree.to_dot()));
+    }
+
+    // the children of "#" are omitted
+    let tree: Tree<i32, i32> =
+        TreeBuilder::build_in_level(&["1", "2", "#", "3", "4", "#", "#", "5"]);
+    assert_eq!(
+        vec!["1", "2", "#", "3", "4", "5"],
+        render::ascii_to_level(&tree.to_ascii()).unwrap()
+    );
+}
+
+#[test]
+fn tree_round_trip() {
+    use algo::tree::binary::rb2::RedBlackTreeV2;
+    let mut tree = Tree::default();
+    for v in 0..100 {
+        tree.insert(v, v);
+    }
+    for tokens in [
+        render::ascii_to_level(&tree.to_ascii()).unwrap(),
+        render::dot_to_level(&tree.to_dot()).unwrap(),
+    ] {
+        let tokens: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+        let rebuilt: Tree<i32, i32> = TreeBuilder::build_in_level(&tokens);
+        assert_eq!(tree.to_ascii(), rebuilt.to_ascii());
+    }
+}
+
+#[test]
+fn parse_error() {
+    assert!(render::ascii_to_level("1\n\n2\n").is_err());
+    // "/" of 2 is on the wrong side
+    assert!(render::ascii_to_level("1\n /\n 2\n").is_err());
+    // no parent on the right of 2
+    assert!(render::ascii_to_level("  1\n   /\n  2\n").is_err());
+}
@@ -1,6 +1,10 @@
-use algo::graph::undirected::{Bipartite, Cycle, DepthFirstSearch, Graph, NonRecursiveDFS, CC};
+use algo::common::{ConcurrentUF, UnionFind, UF};
+use algo::graph::undirected::{
+    Bipartite, Cycle, DepthFirstSearch, DynamicCC, Graph, NonRecursiveDFS, ParallelCC, CC,
+};
 use algo::graph::util::{BreadthFirstPaths, DepthFirstPaths, Paths, SymbolGraph};
 use algo::graph::IGraph;
+use rand::{Rng, SeedableRng};
 use std::path::PathBuf;
 
 const TINY_G: &'static str = include_str!("../res/graph/tinyG.txt");
@@ -126,6 +130,146 @@ fn cc() {
     assert_eq!(expect, components);
 }
 
+#[test]
+fn parallel_cc() {
+    let graph = create_graph(TINY_G);
+    let cc = CC::new(graph.as_ref());
+    for threads in 1..5 {
+        let pcc = ParallelCC::from_graph(graph.as_ref(), threads);
+        assert_eq!(cc.count(), pcc.count());
+        for v in 0..graph.V() {
+            assert_eq!(cc.id(v), pcc.id(v));
+            assert_eq!(cc.size(v), pcc.size(v));
+        }
+        assert!(pcc.connected(9, 12));
+        assert!(!pcc.connected(0, 7));
+    }
+
+    let pcc = ParallelCC::new(3, &[], 4);
+    assert_eq!(3, pcc.count());
+}
+
+#[test]
+fn parallel_cc_random() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(25);
+    let n = 10_000;
+    let edges: Vec<(usize, usize)> = (0..8_000)
+        .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+        .collect();
+    let mut uf = UF::new(n);
+    for &(v, w) in edges.iter() {
+        uf.union(v, w);
+    }
+
+    let pcc = ParallelCC::new(n, &edges, 8);
+    assert_eq!(uf.count(), pcc.count());
+    for v in 0..n {
+        let w = rng.gen_range(0..n);
+        assert_eq!(uf.connected(v, w), pcc.connected(v, w));
+        assert_eq!(uf.component_size(v), pcc.size(v));
+    }
+}
+
+#[test]
+fn concurrent_uf() {
+    fn send_sync<T: Send + Sync>() {}
+    send_sync::<ConcurrentUF>();
+
+    // the threads union a path 0-1-...-n, while others query it
+    let n = 10_000;
+    let uf = ConcurrentUF::new(n);
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let uf = &uf;
+            s.spawn(move || {
+                for v in (t..n - 1).step_by(4) {
+                    uf.union(v, v + 1);
+                }
+            });
+        }
+        s.spawn(|| {
+            for v in 0..n {
+                // connected is monotonic
+                if uf.connected(0, v) {
+                    assert!(uf.connected(v, 0));
+                }
+            }
+        });
+    });
+    assert_eq!(1, uf.count());
+    assert!(uf.connected(0, n - 1));
+    assert_eq!(uf.find(0), uf.find(n / 2));
+    assert!(!uf.union(3, 7));
+    assert_eq!(n, uf.len());
+}
+
+#[test]
+fn dynamic_cc() {
+    let mut dc = DynamicCC::new(5);
+    assert_eq!(0, dc.query(0, 1));
+    dc.add_edge(0, 1);
+    dc.add_edge(1, 2);
+    dc.query(0, 2);
+    dc.add_edge(2, 0);
+    dc.remove_edge(1, 0);
+    dc.query(0, 1);
+    dc.remove_edge(2, 1);
+    dc.query(0, 1);
+    dc.query(0, 2);
+    // parallel edges, one copy removed
+    dc.add_edge(3, 4);
+    dc.add_edge(4, 3);
+    dc.remove_edge(3, 4);
+    dc.query(3, 4);
+    dc.query(4, 4);
+    assert_eq!(7, dc.queries());
+    assert_eq!(vec![false, true, true, false, true, true, true], dc.solve());
+
+    assert!(DynamicCC::new(3).solve().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "no edge 1-2")]
+fn dynamic_cc_remove_missing() {
+    let mut dc = DynamicCC::new(3);
+    dc.add_edge(0, 1);
+    dc.remove_edge(1, 2);
+}
+
+#[test]
+fn dynamic_cc_random() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(23);
+    for _ in 0..20 {
+        let n = rng.gen_range(1..12);
+        let mut dc = DynamicCC::new(n);
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut expect = Vec::new();
+        for _ in 0..200 {
+            let (v, w) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            match rng.gen_range(0..3) {
+                0 => {
+                    dc.add_edge(v, w);
+                    edges.push((v, w));
+                }
+                1 if !edges.is_empty() => {
+                    let (v, w) = edges.swap_remove(rng.gen_range(0..edges.len()));
+                    dc.remove_edge(w, v);
+                }
+                _ => {
+                    dc.query(v, w);
+                    // brute force, build the graph of the edges alive
+                    let mut uf = UF::new(n);
+                    for &(v, w) in edges.iter() {
+                        uf.union(v, w);
+                    }
+                    expect.push(uf.connected(v, w));
+                }
+            }
+        }
+        assert_eq!(expect, dc.solve());
+    }
+}
+
 #[test]
 fn cycle() {
     let graph = create_graph(TINY_G);